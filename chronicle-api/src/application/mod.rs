@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::*;
+use chronicle_broker::MilestoneEventSender;
 use rocket::Shutdown as RocketShutdown;
 use serde::{
     Deserialize,
@@ -80,7 +81,8 @@ impl<H: ChronicleAPIScope> Shutdown for ChronicleAPISender<H> {
 builder!(
     #[derive(Clone)]
     ChronicleAPIBuilder<H> {
-        rocket_listener_handle: RocketShutdown
+        rocket_listener_handle: RocketShutdown,
+        milestone_events: MilestoneEventSender
     }
 );
 
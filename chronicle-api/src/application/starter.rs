@@ -21,7 +21,12 @@ where
     async fn starter(mut self, handle: H, input: Option<Self::Input>) -> Result<Self::Ok, Self::Error> {
         #[cfg(feature = "rocket_listener")]
         let rocket_listener = {
-            let rocket = rocket::ignite();
+            // Without a broker there are no milestone events, so subscribers get an empty stream
+            let milestone_events = self
+                .milestone_events
+                .clone()
+                .unwrap_or_else(|| tokio::sync::broadcast::channel(1).0);
+            let rocket = rocket::ignite().manage(milestone_events);
             let rocket_listener_handle = rocket.shutdown();
             let rocket_listener = ListenerBuilder::new().data(RocketListener::new(rocket)).build();
             self = self.rocket_listener_handle(rocket_listener_handle);
@@ -10,14 +10,34 @@
 //!
 //! ### HTTP Endpoints
 //! - `/api/<keyspace>`
+//!     - `/sync[?<from>&<to>&<gaps_only>]`
 //!     - `/messages`
 //!         - `?<index>[&<page_size>]`
 //!         - `/<message_id>`
 //!         - `/<message_id>/metadata`
+//!         - `/<message_id>/raw`
 //!         - `/<message_id>/children[?<page_size>]`
+//!         - `/batch[?<metadata>]` (POST)
 //!     - `/outputs/<output_id>`
+//!     - `/outputs/batch` (POST)
 //!     - `/addresses/ed25519/<address>/outputs[?<page_size>]`
+//!     - `/addresses/<bech32_address>/outputs[?<page_size>]`
+//!     - `/addresses/ed25519/<address>/balance[?<milestone_index>]`
+//!     - `/addresses/<bech32_address>/balance[?<milestone_index>]`
+//!     - `/addresses/ed25519/<address>/transactions[?<page_size>]`
+//!     - `/milestones?<start>&<end>[&<page_size>]`
+//!     - `/milestones/latest`
+//!     - `/milestones/by-time?<timestamp>`
 //!     - `/milestones/<index>`
+//!     - `/milestones/<index>/messages[?<page_size>]`
+//!     - `/events/milestones[?<index>&<utf8>&<address>]` (server-sent events)
+//!     - `/receipts`
+//!     - `/receipts/<migrated_at>`
+//!     - `/receipts/tail-transactions/<tail_transaction_hash>`
+//!     - `/treasury`
+//!     - `/analytics[?<start>&<end>&<bucket>]`
+//! - `/api/info`, `/api/health/live` and `/api/health/ready`
+//! - `/api/v1/...`: the Hornet node API paths, served for the configured `hornet_keyspace`
 
 /// The main actor for the API
 pub mod application;
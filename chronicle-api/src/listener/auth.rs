@@ -0,0 +1,165 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    ListenerError,
+    RateLimited,
+};
+use chronicle_common::config::{
+    ApiConfig,
+    ApiScope,
+};
+use jsonwebtoken::{
+    Algorithm,
+    DecodingKey,
+    Validation,
+};
+use rocket::{
+    request::{
+        FromRequest,
+        Outcome,
+    },
+    Request,
+    State,
+};
+use serde::Deserialize;
+use std::{
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    convert::Infallible,
+};
+
+/// Resolves the scopes granted to the API key or bearer token of a request
+#[derive(Default)]
+pub(crate) struct Authenticator {
+    api_keys: HashMap<String, HashSet<ApiScope>>,
+    jwt_secret: Option<String>,
+}
+
+/// The claims of a JWT bearer token
+#[derive(Deserialize)]
+struct Claims {
+    #[serde(default)]
+    sub: Option<String>,
+    scopes: HashSet<ApiScope>,
+}
+
+impl From<&ApiConfig> for Authenticator {
+    fn from(config: &ApiConfig) -> Self {
+        Self {
+            api_keys: config
+                .api_keys
+                .iter()
+                .map(|api_key| (api_key.key.clone(), api_key.scopes.clone()))
+                .collect(),
+            jwt_secret: config.jwt_secret.clone(),
+        }
+    }
+}
+
+impl Authenticator {
+    fn is_public(&self) -> bool {
+        self.api_keys.is_empty() && self.jwt_secret.is_none()
+    }
+
+    /// Get the scopes granted to a token, which is either an API key or an (unexpired) JWT
+    fn scopes(&self, token: &str) -> Option<HashSet<ApiScope>> {
+        self.api_keys
+            .get(token)
+            .cloned()
+            .or_else(|| self.claims(token).map(|claims| claims.scopes))
+    }
+
+    /// Get the identity of the client holding a valid token, i.e. the API key or the
+    /// subject of the JWT (or the JWT itself if it has no subject)
+    pub fn client_id(&self, token: &str) -> Option<String> {
+        if self.api_keys.contains_key(token) {
+            Some(format!("key:{}", token))
+        } else {
+            self.claims(token)
+                .map(|claims| format!("jwt:{}", claims.sub.as_deref().unwrap_or(token)))
+        }
+    }
+
+    fn claims(&self, token: &str) -> Option<Claims> {
+        self.jwt_secret.as_ref().and_then(|secret| {
+            jsonwebtoken::decode::<Claims>(
+                token,
+                &DecodingKey::from_secret(secret.as_bytes()),
+                &Validation::new(Algorithm::HS256),
+            )
+            .ok()
+            .map(|data| data.claims)
+        })
+    }
+}
+
+/// The authentication of a request. Routes use it as a request guard and check the
+/// scope they require against it.
+pub(crate) enum Auth {
+    /// Authentication is not configured, so every route is accessible
+    Public,
+    /// The scopes granted to the API key or bearer token of the request
+    Scopes(HashSet<ApiScope>),
+    /// The request has no valid API key or bearer token
+    Unauthenticated,
+    /// The client exceeded its rate limit and may retry after the given number of seconds
+    RateLimited(u64),
+}
+
+impl Auth {
+    /// Require access to the routes of a keyspace
+    pub fn keyspace(&self, keyspace: &str) -> Result<(), ListenerError> {
+        self.require(&ApiScope::Keyspace(keyspace.to_owned()))
+    }
+
+    /// Require access to the monitoring routes
+    pub fn monitoring(&self) -> Result<(), ListenerError> {
+        self.require(&ApiScope::Monitoring)
+    }
+
+    /// Allow a public route, which is only subject to the rate limit
+    pub fn public(&self) -> Result<(), ListenerError> {
+        match self {
+            Auth::RateLimited(retry_after) => Err(ListenerError::TooManyRequests(*retry_after)),
+            _ => Ok(()),
+        }
+    }
+
+    fn require(&self, scope: &ApiScope) -> Result<(), ListenerError> {
+        match self {
+            Auth::Public => Ok(()),
+            Auth::Scopes(scopes) if scopes.contains(scope) => Ok(()),
+            Auth::Scopes(_) => Err(ListenerError::Forbidden),
+            Auth::Unauthenticated => Err(ListenerError::Unauthorized),
+            Auth::RateLimited(retry_after) => Err(ListenerError::TooManyRequests(*retry_after)),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for Auth {
+    type Error = Infallible;
+
+    async fn from_request(request: &'a Request<'r>) -> Outcome<Self, Self::Error> {
+        if let RateLimited(Some(retry_after)) = request.local_cache(|| RateLimited(None)) {
+            return Outcome::Success(Auth::RateLimited(*retry_after));
+        }
+        // Without a managed authenticator nothing is accessible
+        let authenticator = match request.guard::<State<'_, Authenticator>>().await.succeeded() {
+            Some(authenticator) => authenticator,
+            None => return Outcome::Success(Auth::Unauthenticated),
+        };
+        if authenticator.is_public() {
+            return Outcome::Success(Auth::Public);
+        }
+        let scopes = request
+            .headers()
+            .get_one("Authorization")
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .and_then(|token| authenticator.scopes(token.trim()));
+        Outcome::Success(scopes.map(Auth::Scopes).unwrap_or(Auth::Unauthenticated))
+    }
+}
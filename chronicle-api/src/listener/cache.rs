@@ -0,0 +1,101 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use super::Auth;
+use lru::LruCache;
+use rocket::{
+    http::Status,
+    response::{
+        self,
+        Responder,
+    },
+    Request,
+    Response,
+};
+use std::{
+    any::Any,
+    sync::{
+        Arc,
+        Mutex,
+    },
+};
+
+/// A bounded in-process cache of immutable records, i.e. confirmed messages and milestones.
+/// Records which may still change must never be inserted.
+pub(crate) struct ResponseCache(Option<Mutex<LruCache<String, Arc<dyn Any + Send + Sync>>>>);
+
+impl ResponseCache {
+    /// Create a cache holding up to `capacity` records. A capacity of zero disables the cache.
+    pub fn new(capacity: usize) -> Self {
+        Self(if capacity > 0 {
+            Some(Mutex::new(LruCache::new(capacity)))
+        } else {
+            None
+        })
+    }
+
+    /// Get a cached record
+    pub fn get<V: 'static + Clone>(&self, key: &str) -> Option<V> {
+        self.0
+            .as_ref()
+            .and_then(|cache| cache.lock().unwrap().get(key).cloned())
+            .and_then(|value| value.downcast_ref::<V>().cloned())
+    }
+
+    /// Cache an immutable record
+    pub fn insert<V: 'static + Send + Sync>(&self, key: String, value: V) {
+        if let Some(cache) = self.0.as_ref() {
+            cache.lock().unwrap().put(key, Arc::new(value));
+        }
+    }
+}
+
+/// A response which clients and CDNs may cache forever, if it has an ETag.
+/// Only responses for immutable records should be given one.
+/// Responses which required authentication may only be cached by the client itself.
+pub(crate) struct Cacheable<R> {
+    inner: R,
+    etag: Option<String>,
+    private: bool,
+}
+
+impl<R> Cacheable<R> {
+    pub fn new(inner: R, etag: Option<String>, auth: &Auth) -> Self {
+        Self {
+            inner,
+            etag,
+            private: !matches!(auth, Auth::Public),
+        }
+    }
+}
+
+impl<'r, R: Responder<'r, 'static>> Responder<'r, 'static> for Cacheable<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let etag = match self.etag {
+            Some(etag) => format!("\"{}\"", etag),
+            None => return self.inner.respond_to(req),
+        };
+        // The record is immutable, so any client holding the tag holds the same response
+        let not_modified = req
+            .headers()
+            .get("If-None-Match")
+            .flat_map(|value| value.split(','))
+            .any(|tag| {
+                let tag = tag.trim();
+                tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == etag
+            });
+        let mut response = if not_modified {
+            Response::build().status(Status::NotModified).finalize()
+        } else {
+            self.inner.respond_to(req)?
+        };
+        response.set_raw_header("ETag", etag);
+        if self.private {
+            response.set_raw_header("Cache-Control", "private, max-age=31536000, immutable");
+            response.set_raw_header("Vary", "Authorization");
+        } else {
+            response.set_raw_header("Cache-Control", "public, max-age=31536000, immutable");
+        }
+        Ok(response)
+    }
+}
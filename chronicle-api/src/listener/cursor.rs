@@ -0,0 +1,86 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use super::ListenerError;
+use anyhow::anyhow;
+use chronicle_storage::access::Hint;
+use hmac::{
+    Hmac,
+    Mac,
+    NewMac,
+};
+use rand::RngCore;
+use serde::{
+    de::DeserializeOwned,
+    Serialize,
+};
+use sha2::{
+    Digest,
+    Sha256,
+};
+use std::convert::TryInto;
+
+/// The current cursor format version. Cursors of any other version are rejected as stale.
+const CURSOR_VERSION: u8 = 2;
+/// The length of the HMAC-SHA256 tag which ends every cursor
+const TAG_LENGTH: usize = 32;
+
+/// The key used to sign and verify the paging cursors handed out by the API
+pub(crate) struct CursorKey(Vec<u8>);
+
+impl CursorKey {
+    /// Create a cursor key from the hex encoded secret of the api config. Without a configured
+    /// secret a random key is used, so cursors will not survive a restart.
+    pub fn new(secret: Option<&str>) -> anyhow::Result<Self> {
+        match secret {
+            Some(secret) => Ok(Self(hex::decode(secret)?)),
+            None => {
+                let mut key = vec![0; 32];
+                rand::thread_rng().fill_bytes(&mut key);
+                Ok(Self(key))
+            }
+        }
+    }
+
+    fn mac(&self) -> Hmac<Sha256> {
+        Hmac::<Sha256>::new_from_slice(&self.0).expect("HMAC can take a key of any size")
+    }
+
+    /// Encode a value as an opaque cursor, which is the version byte followed by the bincode encoded
+    /// value and its signature, encoded as url safe base64.
+    pub fn encode<T: Serialize>(&self, value: &T) -> Result<String, ListenerError> {
+        let mut bytes = vec![CURSOR_VERSION];
+        bincode::serialize_into(&mut bytes, value).map_err(|e| anyhow!(e))?;
+        let mut mac = self.mac();
+        mac.update(&bytes);
+        bytes.extend_from_slice(&mac.finalize().into_bytes());
+        Ok(base64::encode_config(bytes, base64::URL_SAFE_NO_PAD))
+    }
+
+    /// Verify and decode a cursor created with `encode`
+    pub fn decode<T: DeserializeOwned>(&self, cursor: &str) -> Result<T, ListenerError> {
+        let bytes = base64::decode_config(cursor, base64::URL_SAFE_NO_PAD).map_err(|_| ListenerError::InvalidCursor)?;
+        if bytes.len() <= TAG_LENGTH {
+            return Err(ListenerError::InvalidCursor);
+        }
+        let (payload, tag) = bytes.split_at(bytes.len() - TAG_LENGTH);
+        let mut mac = self.mac();
+        mac.update(payload);
+        mac.verify(tag).map_err(|_| ListenerError::InvalidCursor)?;
+        if payload[0] != CURSOR_VERSION {
+            return Err(ListenerError::StaleCursor);
+        }
+        bincode::deserialize(&payload[1..]).map_err(|_| ListenerError::InvalidCursor)
+    }
+}
+
+/// Compute the digest of a hint, which binds paging cursors to the hint they page through.
+/// The partition list of the hint is left out, because the latest milestone of its partitions moves
+/// whenever a record is added. Cursors snapshot the milestone paging started at instead, so records
+/// added afterwards never shift the pages.
+pub(crate) fn hint_digest(hint: &Hint) -> u64 {
+    let mut hasher = Sha256::new();
+    hasher.update(hint.hint.as_bytes());
+    hasher.update(hint.variant.to_string().as_bytes());
+    u64::from_le_bytes(hasher.finalize()[..8].try_into().unwrap())
+}
@@ -0,0 +1,189 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::responses::{
+    ListenerResponse,
+    MessageEventRecord,
+};
+use bee_message::prelude::Ed25519Address;
+use chronicle_broker::MilestoneEvent;
+use futures::{
+    stream,
+    StreamExt,
+};
+use log::error;
+use rocket::{
+    http::ContentType,
+    response::{
+        self,
+        Responder,
+    },
+    Request,
+    Response,
+};
+use std::{
+    io::Cursor,
+    sync::Arc,
+    time::Duration,
+};
+use tokio::sync::broadcast::Receiver;
+use tokio_stream::wrappers::{
+    errors::BroadcastStreamRecvError,
+    BroadcastStream,
+    IntervalStream,
+};
+use tokio_util::io::StreamReader;
+
+/// The interval of the comments which keep idle event streams from being closed by proxies
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Selects the messages of the milestone events a subscriber is interested in
+pub(crate) struct MilestoneEventFilter {
+    /// The keyspace the messages were stored in
+    pub keyspace: String,
+    /// The hex encoded index the messages must have
+    pub index: Option<String>,
+    /// The address the messages must have an output to
+    pub address: Option<Ed25519Address>,
+}
+
+impl MilestoneEventFilter {
+    /// Get the response for a milestone event, or nothing if none of the messages of the milestone were stored in
+    /// the keyspace or none of them match the filter
+    fn apply(&self, event: &MilestoneEvent) -> Option<ListenerResponse> {
+        let mut stored = event
+            .messages
+            .iter()
+            .filter(|message| message.keyspace == self.keyspace)
+            .peekable();
+        stored.peek()?;
+        let messages = stored
+            .filter(|message| {
+                self.index
+                    .as_ref()
+                    .map_or(true, |index| message.index.as_ref() == Some(index))
+                    && self
+                        .address
+                        .as_ref()
+                        .map_or(true, |address| message.addresses.contains(address))
+            })
+            .map(MessageEventRecord::from)
+            .collect::<Vec<_>>();
+        if messages.is_empty() && (self.index.is_some() || self.address.is_some()) {
+            return None;
+        }
+        Some(ListenerResponse::MilestoneEvent {
+            milestone_index: event.milestone_index,
+            timestamp: event.timestamp,
+            messages,
+        })
+    }
+}
+
+/// A server-sent events stream of the milestones whose data is completely in the database
+pub(crate) struct MilestoneEventStream {
+    receiver: Receiver<Arc<MilestoneEvent>>,
+    filter: MilestoneEventFilter,
+}
+
+impl MilestoneEventStream {
+    pub fn new(receiver: Receiver<Arc<MilestoneEvent>>, filter: MilestoneEventFilter) -> Self {
+        Self { receiver, filter }
+    }
+}
+
+impl<'r> Responder<'r, 'static> for MilestoneEventStream {
+    fn respond_to(self, _req: &'r Request<'_>) -> response::Result<'static> {
+        let filter = self.filter;
+        let events = BroadcastStream::new(self.receiver).filter_map(move |event| {
+            let chunk = match event {
+                Ok(event) => filter.apply(&event).and_then(|response| {
+                    serde_json::to_string(&response)
+                        .map(|data| format!("event: milestone\nid: {}\ndata: {}\n\n", event.milestone_index, data))
+                        .map_err(|e| error!("JSON failed to serialize: {:?}", e))
+                        .ok()
+                }),
+                // The subscriber fell behind, so let it know how many milestones it missed
+                Err(BroadcastStreamRecvError::Lagged(skipped)) => Some(format!("event: lagged\ndata: {}\n\n", skipped)),
+            };
+            futures::future::ready(chunk)
+        });
+        let keep_alive =
+            IntervalStream::new(tokio::time::interval(KEEP_ALIVE_INTERVAL)).map(|_| ": keep-alive\n\n".to_owned());
+        let body = stream::select(events, keep_alive)
+            .map(|chunk| Ok::<_, std::io::Error>(Cursor::new(chunk.into_bytes())))
+            .boxed();
+        Response::build()
+            .header(ContentType::new("text", "event-stream"))
+            .raw_header("Cache-Control", "no-cache")
+            .streamed_body(StreamReader::new(body))
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bee_message::prelude::MessageId;
+    use chronicle_broker::MessageEvent;
+
+    fn message(keyspace: &str, id: u8, index: Option<&str>, addresses: Vec<Ed25519Address>) -> MessageEvent {
+        MessageEvent {
+            keyspace: keyspace.to_owned(),
+            message_id: MessageId::new([id; 32]),
+            ledger_inclusion_state: None,
+            index: index.map(str::to_owned),
+            addresses,
+        }
+    }
+
+    fn filter(index: Option<&str>, address: Option<Ed25519Address>) -> MilestoneEventFilter {
+        MilestoneEventFilter {
+            keyspace: "permanode".to_owned(),
+            index: index.map(str::to_owned),
+            address,
+        }
+    }
+
+    fn message_ids(response: Option<ListenerResponse>) -> Option<Vec<String>> {
+        match response? {
+            ListenerResponse::MilestoneEvent { messages, .. } => {
+                Some(messages.into_iter().map(|message| message.message_id).collect())
+            }
+            response => panic!("Unexpected response: {:?}", response),
+        }
+    }
+
+    #[test]
+    fn apply_milestone_event_filter() {
+        let address = Ed25519Address::new([1; 32]);
+        let event = MilestoneEvent {
+            milestone_index: 5,
+            timestamp: 1_600_000_000,
+            messages: vec![
+                message("permanode", 1, Some("6869"), vec![]),
+                message("permanode", 2, None, vec![address]),
+                message("other", 3, Some("6869"), vec![address]),
+            ],
+        };
+        let id = |id: u8| MessageId::new([id; 32]).to_string();
+
+        // Only the messages stored in the keyspace are listed
+        assert_eq!(message_ids(filter(None, None).apply(&event)), Some(vec![id(1), id(2)]));
+        assert_eq!(message_ids(filter(Some("6869"), None).apply(&event)), Some(vec![id(1)]));
+        assert_eq!(
+            message_ids(filter(None, Some(address)).apply(&event)),
+            Some(vec![id(2)])
+        );
+        // Both filters must match
+        assert_eq!(message_ids(filter(Some("6869"), Some(address)).apply(&event)), None);
+        assert_eq!(message_ids(filter(Some("ff"), None).apply(&event)), None);
+
+        // Milestones without any message stored in the keyspace are skipped
+        let event = MilestoneEvent {
+            messages: vec![message("other", 3, Some("6869"), vec![address])],
+            ..event
+        };
+        assert_eq!(message_ids(filter(None, None).apply(&event)), None);
+    }
+}
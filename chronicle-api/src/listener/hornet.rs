@@ -0,0 +1,158 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use rocket::{
+    fairing::{
+        Fairing,
+        Info,
+        Kind,
+    },
+    http::{
+        uri::Origin,
+        ContentType,
+        Status,
+    },
+    Data,
+    Request,
+    Response,
+};
+use serde_json::Value;
+use std::io::Cursor;
+
+/// The kind of the Ed25519 address in Hornet responses
+const HORNET_ED25519_ADDRESS_KIND: u8 = 0;
+
+/// Serves a keyspace under the `/api/v1` paths of the Hornet node API by rewriting
+/// them to the equivalent Chronicle paths before they are routed, and the bodies of
+/// responses which differ from Hornet's into Hornet's shape.
+pub(crate) struct HornetFacade {
+    keyspace: String,
+}
+
+/// The Hornet response a rewritten request is answered with, kept in the request-local cache
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum HornetResponse {
+    /// Not a Hornet request
+    None,
+    /// A response which has the same shape in both APIs
+    Unchanged,
+    /// The balance of an address
+    Balance,
+    /// The outputs of an address
+    Outputs,
+    /// The children of a message
+    Children,
+}
+
+impl HornetFacade {
+    /// Create a facade over the given keyspace
+    pub fn new(keyspace: String) -> Self {
+        Self { keyspace }
+    }
+
+    /// Get the Chronicle path for a Hornet path, if it is one, along with the response it is answered with
+    fn rewrite(&self, path: &str) -> Option<(String, HornetResponse)> {
+        let path = path.strip_prefix("/api/v1")?;
+        if !path.is_empty() && !path.starts_with('/') {
+            return None;
+        }
+        if path == "/info" {
+            return Some(("/api/info".to_owned(), HornetResponse::Unchanged));
+        }
+        let segments = path.trim_start_matches('/').split('/').collect::<Vec<_>>();
+        let (path, response) = match segments.as_slice() {
+            // Hornet returns the balance of an address at the address itself
+            ["addresses", "ed25519", address] => (
+                format!("/addresses/ed25519/{}/balance", address),
+                HornetResponse::Balance,
+            ),
+            ["addresses", address] => (format!("/addresses/{}/balance", address), HornetResponse::Balance),
+            ["addresses", "ed25519", _, "outputs"] | ["addresses", _, "outputs"] => {
+                (path.to_owned(), HornetResponse::Outputs)
+            }
+            ["messages", _, "children"] => (path.to_owned(), HornetResponse::Children),
+            _ => (path.to_owned(), HornetResponse::Unchanged),
+        };
+        Some((format!("/api/{}{}", self.keyspace, path), response))
+    }
+}
+
+impl HornetResponse {
+    /// Convert the data of a Chronicle response into the data of the Hornet response
+    pub(crate) fn convert(self, mut data: Value) -> Value {
+        match self {
+            HornetResponse::Balance => {
+                let mut balance = serde_json::Map::new();
+                balance.insert("addressType".to_owned(), HORNET_ED25519_ADDRESS_KIND.into());
+                for field in ["address", "balance", "dustAllowed", "ledgerIndex"].iter() {
+                    if let Some(value) = data.get_mut(*field) {
+                        balance.insert((*field).to_owned(), value.take());
+                    }
+                }
+                Value::Object(balance)
+            }
+            HornetResponse::Outputs => {
+                if let Some(address_type) = data.get_mut("addressType") {
+                    *address_type = HORNET_ED25519_ADDRESS_KIND.into();
+                }
+                data
+            }
+            HornetResponse::Children => {
+                // Hornet does not page children, so there is no cursor to continue from
+                if let Some(children) = data.as_object_mut() {
+                    children.remove("state");
+                }
+                data
+            }
+            HornetResponse::None | HornetResponse::Unchanged => data,
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for HornetFacade {
+    fn info(&self) -> Info {
+        Info {
+            name: "Hornet API Facade",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _: &mut Data) {
+        let uri = request.uri().to_string();
+        let mut parts = uri.splitn(2, '?');
+        let path = parts.next().unwrap_or_default();
+        if let Some((mut rewritten, response)) = self.rewrite(path) {
+            if let Some(query) = parts.next() {
+                rewritten.push('?');
+                rewritten.push_str(query);
+            }
+            if let Ok(uri) = Origin::parse_owned(rewritten) {
+                request.set_uri(uri);
+                request.local_cache(|| response);
+            }
+        }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let hornet_response = *request.local_cache(|| HornetResponse::None);
+        if matches!(hornet_response, HornetResponse::None | HornetResponse::Unchanged)
+            || response.status() != Status::Ok
+            || response.content_type() != Some(ContentType::JSON)
+        {
+            return;
+        }
+        if let Some(body) = response.body_string().await {
+            let body = match serde_json::from_str::<Value>(&body) {
+                Ok(mut body) => {
+                    if let Some(data) = body.get_mut("data") {
+                        *data = hornet_response.convert(data.take());
+                    }
+                    body.to_string()
+                }
+                Err(_) => body,
+            };
+            response.set_sized_body(None, Cursor::new(body));
+        }
+    }
+}
@@ -3,8 +3,14 @@
 
 use super::*;
 use application::*;
+use auth::*;
+use cache::*;
 use chronicle_common::get_config_async;
 use chronicle_storage::access::*;
+use cursor::*;
+use events::*;
+use hornet::*;
+use rate_limit::*;
 use rocket::{
     http::Status,
     Rocket,
@@ -20,7 +26,13 @@ use std::{
 };
 use thiserror::Error;
 
+mod auth;
+mod cache;
+mod cursor;
+mod events;
+mod hornet;
 mod init;
+mod rate_limit;
 #[cfg(feature = "rocket_listener")]
 mod rocket_event_loop;
 mod terminating;
@@ -37,10 +49,28 @@ enum ListenerError {
     InvalidHex,
     #[error("Specified keyspace ({0}) is not configured!")]
     InvalidKeyspace(String),
-    #[error("Invalid state provided!")]
-    InvalidState,
+    #[error("Invalid cursor provided! It is malformed or was tampered with")]
+    InvalidCursor,
+    #[error("Stale cursor provided! It was issued by an incompatible version, restart paging without a cursor")]
+    StaleCursor,
+    #[error("Invalid bech32 address HRP! Expected: {0}, found: {1}")]
+    InvalidHrp(String, String),
+    #[error("Too many ids provided! (Max {0})")]
+    BatchTooLarge(usize),
+    #[error("Too many partitions to scan! (Max {0})")]
+    TooManyPartitions(usize),
+    #[error("Too many queries to run! (Max {0})")]
+    TooManyQueries(usize),
     #[error("No endpoint found!")]
     NotFound,
+    #[error("Missing or invalid API key or bearer token!")]
+    Unauthorized,
+    #[error("The provided API key or bearer token does not grant access to this resource!")]
+    Forbidden,
+    #[error("Too many requests! Retry after {0} seconds")]
+    TooManyRequests(u64),
+    #[error("Not ready! {0}")]
+    NotReady(String),
     #[error(transparent)]
     BadParse(anyhow::Error),
     #[error(transparent)]
@@ -51,7 +81,19 @@ impl ListenerError {
     pub fn status(&self) -> Status {
         match self {
             ListenerError::NoResults | ListenerError::InvalidKeyspace(_) => Status::NotFound,
-            ListenerError::IndexTooLarge | ListenerError::InvalidHex | ListenerError::BadParse(_) => Status::BadRequest,
+            ListenerError::IndexTooLarge
+            | ListenerError::InvalidHex
+            | ListenerError::InvalidHrp(_, _)
+            | ListenerError::BatchTooLarge(_)
+            | ListenerError::TooManyPartitions(_)
+            | ListenerError::TooManyQueries(_)
+            | ListenerError::InvalidCursor
+            | ListenerError::BadParse(_) => Status::BadRequest,
+            ListenerError::StaleCursor => Status::Gone,
+            ListenerError::Unauthorized => Status::Unauthorized,
+            ListenerError::Forbidden => Status::Forbidden,
+            ListenerError::TooManyRequests(_) => Status::TooManyRequests,
+            ListenerError::NotReady(_) => Status::ServiceUnavailable,
             _ => Status::InternalServerError,
         }
     }
@@ -0,0 +1,129 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    Authenticator,
+    ListenerError,
+};
+use chronicle_common::config::RateLimitConfig;
+use lru::LruCache;
+use rocket::{
+    fairing::{
+        Fairing,
+        Info,
+        Kind,
+    },
+    response::Responder,
+    Data,
+    Request,
+    Response,
+};
+use std::{
+    sync::Mutex,
+    time::Instant,
+};
+
+/// The maximum number of tracked clients, above which the least recently seen client is dropped
+const MAX_TRACKED_CLIENTS: usize = 10000;
+
+/// Token bucket rate limiter, which keeps a bucket per client. Clients are identified by
+/// their API key or bearer token if it is valid, or else by their IP address, so made up
+/// tokens do not get buckets of their own.
+pub(crate) struct RateLimiter {
+    config: RateLimitConfig,
+    authenticator: Authenticator,
+    buckets: Mutex<LruCache<String, TokenBucket>>,
+}
+
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    /// Refill the bucket with the tokens accrued since the last update
+    fn refill(&mut self, now: Instant, rate: f64, burst: f64) -> f64 {
+        self.tokens = (self.tokens + now.duration_since(self.updated).as_secs_f64() * rate).min(burst);
+        self.updated = now;
+        self.tokens
+    }
+}
+
+/// The outcome of the rate limiter for a request, kept in the request-local cache.
+/// Holds the number of seconds to wait before retrying if the request exceeded the limit.
+#[derive(Copy, Clone)]
+pub(crate) struct RateLimited(pub Option<u64>);
+
+impl RateLimiter {
+    /// Create a rate limiter from the configured limit, using the authenticator to identify clients
+    pub fn new(config: RateLimitConfig, authenticator: Authenticator) -> Self {
+        Self {
+            config,
+            authenticator,
+            buckets: Mutex::new(LruCache::new(MAX_TRACKED_CLIENTS)),
+        }
+    }
+
+    /// Identify the client of a request
+    fn client(&self, request: &Request<'_>) -> String {
+        request
+            .headers()
+            .get_one("Authorization")
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .and_then(|token| self.authenticator.client_id(token.trim()))
+            .or_else(|| request.client_ip().map(|ip| format!("ip:{}", ip)))
+            .unwrap_or_default()
+    }
+
+    /// Take a token from the bucket of a client, or get the number of seconds until one is available
+    fn take(&self, client: String) -> Result<(), u64> {
+        let now = Instant::now();
+        let rate = self.config.requests_per_second as f64;
+        let burst = self.config.burst as f64;
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.get(&client).is_none() {
+            buckets.put(
+                client.clone(),
+                TokenBucket {
+                    tokens: burst,
+                    updated: now,
+                },
+            );
+        }
+        // This is safe because we inserted the bucket above
+        let bucket = buckets.get_mut(&client).unwrap();
+        if bucket.refill(now, rate, burst) >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err((((1.0 - bucket.tokens) / rate).ceil() as u64).max(1))
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for RateLimiter {
+    fn info(&self) -> Info {
+        Info {
+            name: "Rate Limiter",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    /// Charges the request to its client. The `Auth` request guard rejects requests which
+    /// exceeded the limit before their route does any work.
+    async fn on_request(&self, request: &mut Request<'_>, _: &mut Data) {
+        let retry_after = self.take(self.client(request)).err();
+        request.local_cache(|| RateLimited(retry_after));
+    }
+
+    /// Replaces the response of every request which exceeded the limit, so routes without
+    /// a request guard are limited as well
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        if let RateLimited(Some(retry_after)) = request.local_cache(|| RateLimited(None)) {
+            if let Ok(limited) = ListenerError::TooManyRequests(*retry_after).respond_to(request) {
+                *response = limited;
+            }
+        }
+    }
+}
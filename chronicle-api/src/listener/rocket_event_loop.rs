@@ -6,7 +6,12 @@ use crate::responses::*;
 use anyhow::anyhow;
 use bee_message::{
     milestone::Milestone,
+    output::{
+        Output,
+        SignatureLockedDustAllowanceOutput,
+    },
     prelude::{
+        Address,
         Ed25519Address,
         Message,
         MessageId,
@@ -17,10 +22,16 @@ use bee_message::{
 };
 use chronicle_broker::{
     AnalyticsData,
+    ExtendedAnalyticsData,
+    MilestoneEventSender,
     SyncData,
 };
 use chronicle_common::{
-    config::PartitionConfig,
+    config::{
+        ApiConfig,
+        PartitionConfig,
+        StorageConfig,
+    },
     metrics::{
         prometheus::{
             self,
@@ -33,6 +44,7 @@ use chronicle_common::{
         RESPONSE_TIME_COLLECTOR,
     },
     SyncRange,
+    Synckey,
 };
 use chronicle_storage::{
     access::{
@@ -43,6 +55,7 @@ use chronicle_storage::{
     },
     keyspaces::ChronicleKeyspace,
 };
+use futures::StreamExt;
 use hex::FromHex;
 use mpsc::unbounded_channel;
 use rocket::{
@@ -53,6 +66,7 @@ use rocket::{
     },
     get,
     http::ContentType,
+    post,
     response::{
         Content,
         Responder,
@@ -66,12 +80,15 @@ use rocket_contrib::json::Json;
 use std::{
     borrow::Borrow,
     collections::{
+        BTreeMap,
         HashMap,
         HashSet,
         VecDeque,
     },
     convert::TryInto,
+    future::Future,
     io::Cursor,
+    ops::Range,
     path::PathBuf,
     str::FromStr,
     time::SystemTime,
@@ -94,7 +111,15 @@ impl<H: ChronicleAPIScope> EventLoop<ChronicleAPISender<H>> for Listener<RocketL
                 .map_err(|_| Need::Abort)?;
         }
 
-        let storage_config = get_config_async().await.storage_config;
+        let config = get_config_async().await;
+        if !config.api_config.requires_auth() {
+            warn!("No API keys or JWT secret are configured, the API is publicly accessible!");
+        }
+        let storage_config = config.storage_config;
+        let cursor_key = CursorKey::new(config.api_config.cursor_secret.as_deref()).map_err(|e| {
+            error!("Invalid cursor secret: {}", e);
+            Need::Abort
+        })?;
 
         let keyspaces = storage_config
             .keyspaces
@@ -103,13 +128,23 @@ impl<H: ChronicleAPIScope> EventLoop<ChronicleAPISender<H>> for Listener<RocketL
             .map(|k| k.name)
             .collect::<HashSet<_>>();
 
+        let mut rocket = self.data.rocket.take().ok_or_else(|| Need::Abort)?;
+        if let Some(rate_limit) = config.api_config.rate_limit {
+            rocket = rocket.attach(RateLimiter::new(rate_limit, Authenticator::from(&config.api_config)));
+        }
+        if let Some(hornet_keyspace) = config.api_config.hornet_keyspace.clone() {
+            rocket = rocket.attach(HornetFacade::new(hornet_keyspace));
+        }
+
         construct_rocket(
-            self.data
-                .rocket
-                .take()
-                .ok_or_else(|| Need::Abort)?
+            rocket
                 .manage(storage_config.partition_config.clone())
                 .manage(keyspaces)
+                .manage(storage_config)
+                .manage(cursor_key)
+                .manage(Authenticator::from(&config.api_config))
+                .manage(ResponseCache::new(config.api_config.cache_size))
+                .manage(config.api_config)
                 .register(catchers![internal_error, not_found]),
         )
         .launch()
@@ -125,17 +160,36 @@ fn construct_rocket(rocket: Rocket) -> Rocket {
             routes![
                 options,
                 info,
+                health_live,
+                health_ready,
                 metrics,
                 service,
                 sync,
                 get_message,
+                get_message_raw,
                 get_message_metadata,
+                post_messages_batch,
                 get_message_children,
                 get_message_by_index,
                 get_output,
+                post_outputs_batch,
                 get_ed25519_outputs,
+                get_bech32_outputs,
+                get_ed25519_balance,
+                get_bech32_balance,
+                get_ed25519_transactions,
+                get_bech32_transactions,
                 get_transaction_included_message,
+                get_milestones,
+                get_latest_milestone,
+                get_milestone_by_time,
                 get_milestone,
+                get_milestone_messages,
+                get_milestone_events,
+                get_receipts,
+                get_receipts_by_migrated_at,
+                get_receipts_by_tail_transaction_hash,
+                get_treasury,
                 get_analytics
             ],
         )
@@ -156,7 +210,7 @@ impl Fairing for CORS {
 
     async fn on_response<'r>(&self, _request: &'r Request<'_>, response: &mut Response<'r>) {
         response.set_raw_header("Access-Control-Allow-Origin", "*");
-        response.set_raw_header("Access-Control-Allow-Methods", "GET, OPTIONS");
+        response.set_raw_header("Access-Control-Allow-Methods", "GET, POST, OPTIONS");
         response.set_raw_header("Access-Control-Allow-Headers", "*");
         response.set_raw_header("Access-Control-Allow-Credentials", "true");
     }
@@ -218,17 +272,28 @@ impl Fairing for RequestTimer {
 
 impl<'r> Responder<'r, 'static> for ListenerError {
     fn respond_to(self, _req: &'r Request<'_>) -> rocket::response::Result<'static> {
+        let retry_after = match self {
+            ListenerError::TooManyRequests(retry_after) => Some(retry_after),
+            _ => None,
+        };
         let err = ErrorBody::from(self);
         let string = serde_json::to_string(&err).map_err(|e| {
             error!("JSON failed to serialize: {:?}", e);
             Status::InternalServerError
         })?;
 
-        Response::build()
+        let mut response = Response::build();
+        response
             .sized_body(None, Cursor::new(string))
             .status(err.status)
-            .header(ContentType::JSON)
-            .ok()
+            .header(ContentType::JSON);
+        if err.status == Status::Unauthorized {
+            response.raw_header("WWW-Authenticate", "Bearer");
+        }
+        if let Some(retry_after) = retry_after {
+            response.raw_header("Retry-After", retry_after.to_string());
+        }
+        response.ok()
     }
 }
 
@@ -250,28 +315,98 @@ type ListenerResult = Result<ListenerResponse, ListenerError>;
 async fn options(_path: PathBuf) {}
 
 #[get("/info")]
-async fn info() -> ListenerResult {
+async fn info(
+    storage_config: State<'_, StorageConfig>,
+    api_config: State<'_, ApiConfig>,
+    auth: Auth,
+) -> ListenerResult {
+    auth.public()?;
     let version = std::env!("CARGO_PKG_VERSION").to_string();
-    let service = SERVICE.read().await;
-    let is_healthy = !std::iter::once(&*service)
-        .chain(service.microservices.values())
-        .any(|service| service.is_degraded() || service.is_maintenance() || service.is_stopped());
+    let services_healthy = {
+        let service = SERVICE.read().await;
+        !std::iter::once(&*service)
+            .chain(service.microservices.values())
+            .any(|service| service.is_degraded() || service.is_maintenance() || service.is_stopped())
+    };
+    // Report the keyspace served as the Hornet API, or else the one the broker syncs
+    let keyspace_config = api_config
+        .hornet_keyspace
+        .as_ref()
+        .and_then(|keyspace| storage_config.keyspace_config(keyspace))
+        .or_else(|| storage_config.keyspaces.first())
+        .ok_or_else(|| anyhow!("No keyspace is configured!"))?;
+    let keyspace = ChronicleKeyspace::new(keyspace_config.name.clone());
+    // An unreachable keyspace leaves the instance unhealthy rather than failing the request
+    let (latest_synced, earliest_synced) =
+        futures::future::join(latest_synced(&keyspace), earliest_synced(&keyspace)).await;
+    let (latest_synced, earliest_synced) = match (latest_synced, earliest_synced) {
+        (Ok(latest_synced), Ok(earliest_synced)) => (latest_synced, earliest_synced),
+        (Err(e), _) | (_, Err(e)) => {
+            warn!("Failed to fetch the sync data of {}: {}", keyspace_config.name, e);
+            (None, None)
+        }
+    };
+    let sync_lag = match latest_synced {
+        Some(index) => query::<Milestone, _, _>(keyspace, MilestoneIndex(index), None, None)
+            .await
+            .ok()
+            .map(|milestone| {
+                let now = SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                now.saturating_sub(milestone.timestamp())
+            }),
+        None => None,
+    };
+    let is_healthy = services_healthy && sync_lag.map_or(false, |lag| lag <= api_config.max_sync_lag_secs);
     Ok(ListenerResponse::Info {
         name: "Chronicle".into(),
         version,
         is_healthy,
-        network_id: "network id".into(),
-        bech32_hrp: "bech32 hrp".into(),
-        latest_milestone_index: 0,
-        confirmed_milestone_index: 0,
-        pruning_index: 0,
+        network_id: keyspace_config.network_id.clone(),
+        bech32_hrp: keyspace_config.bech32_hrp.clone(),
+        latest_milestone_index: latest_synced.unwrap_or_default(),
+        confirmed_milestone_index: latest_synced.unwrap_or_default(),
+        pruning_index: earliest_synced.map_or(0, |index| index.saturating_sub(1)),
         features: vec![],
         min_pow_score: 0.0,
     })
 }
 
+#[get("/health/live")]
+async fn health_live() -> Status {
+    Status::Ok
+}
+
+#[get("/health/ready")]
+async fn health_ready(storage_config: State<'_, StorageConfig>, auth: Auth) -> Result<Status, ListenerError> {
+    auth.public()?;
+    for keyspace_config in storage_config.keyspaces.iter() {
+        // Any response from scylla proves that the ring is built and the keyspace is reachable
+        match query::<Milestone, _, _>(
+            ChronicleKeyspace::new(keyspace_config.name.clone()),
+            MilestoneIndex(0),
+            None,
+            None,
+        )
+        .await
+        {
+            Ok(_) | Err(ListenerError::NoResults) => (),
+            Err(e) => {
+                return Err(ListenerError::NotReady(format!(
+                    "Keyspace {} is unreachable: {}",
+                    keyspace_config.name, e
+                )))
+            }
+        }
+    }
+    Ok(Status::Ok)
+}
+
 #[get("/metrics")]
-async fn metrics() -> Result<String, ListenerError> {
+async fn metrics(auth: Auth) -> Result<String, ListenerError> {
+    auth.monitoring()?;
     let encoder = TextEncoder::new();
     let mut buffer = Vec::new();
     encoder
@@ -290,20 +425,69 @@ async fn metrics() -> Result<String, ListenerError> {
 }
 
 #[get("/service")]
-async fn service() -> Json<Service> {
-    Json(SERVICE.read().await.clone())
+async fn service(auth: Auth) -> Result<Json<Service>, ListenerError> {
+    auth.monitoring()?;
+    Ok(Json(SERVICE.read().await.clone()))
 }
 
-#[get("/<keyspace>/sync")]
-async fn sync(keyspaces: State<'_, HashSet<String>>, keyspace: String) -> Result<Json<SyncData>, ListenerError> {
+#[get("/<keyspace>/sync?<from>&<to>&<gaps_only>")]
+async fn sync(
+    keyspaces: State<'_, HashSet<String>>,
+    keyspace: String,
+    from: Option<u32>,
+    to: Option<u32>,
+    gaps_only: Option<bool>,
+    auth: Auth,
+) -> Result<SyncResponse, ListenerError> {
+    auth.keyspace(&keyspace)?;
     if !keyspaces.contains(&keyspace) {
         return Err(ListenerError::InvalidKeyspace(keyspace));
     }
+    // Without any parameter the sync data is returned as it always was
+    if from.is_none() && to.is_none() && gaps_only.is_none() {
+        return SyncData::try_fetch(&ChronicleKeyspace::new(keyspace), &SyncRange::default(), 3)
+            .await
+            .map(SyncResponse::Full)
+            .map_err(|e| ListenerError::Other(e.into()));
+    }
+    let default_range = SyncRange::default();
+    let sync_range = SyncRange {
+        from: from.unwrap_or(default_range.from),
+        to: to.unwrap_or(default_range.to),
+    };
+    if sync_range.from == 0 || sync_range.from >= sync_range.to || sync_range.to > default_range.to {
+        return Err(ListenerError::BadParse(anyhow!(
+            "Invalid sync range! Expected 0 < from < to <= {}",
+            default_range.to
+        )));
+    }
     let keyspace = ChronicleKeyspace::new(keyspace);
-    SyncData::try_fetch(&keyspace, &SyncRange::default(), 3)
+    let sync_data = SyncData::try_fetch(&keyspace, &sync_range, 3)
         .await
-        .map(|s| Json(s))
-        .map_err(|e| ListenerError::Other(e.into()))
+        .map_err(|e| ListenerError::Other(e.into()))?;
+    let gaps_only = gaps_only.unwrap_or(false);
+    Ok(SyncResponse::Range(ListenerResponse::Sync {
+        from: sync_range.from,
+        to: sync_range.to,
+        completed: (!gaps_only).then(|| sync_data.completed().to_vec()),
+        synced_but_unlogged: (!gaps_only).then(|| sync_data.synced_but_unlogged().to_vec()),
+        gaps: sync_data.gaps().to_vec(),
+    }))
+}
+
+/// The sync data of a keyspace, either in full or for the requested range
+enum SyncResponse {
+    Full(SyncData),
+    Range(ListenerResponse),
+}
+
+impl<'r> Responder<'r, 'static> for SyncResponse {
+    fn respond_to(self, req: &'r Request<'_>) -> rocket::response::Result<'static> {
+        match self {
+            SyncResponse::Full(sync_data) => Json(sync_data).respond_to(req),
+            SyncResponse::Range(response) => response.respond_to(req),
+        }
+    }
 }
 
 async fn query<V, S, K>(
@@ -343,12 +527,31 @@ where
     Err(ListenerError::NoResponseError)
 }
 
+/// Get the latest synced milestone index of a keyspace, if anything was synced yet
+async fn latest_synced(keyspace: &ChronicleKeyspace) -> Result<Option<u32>, ListenerError> {
+    match query::<SyncRecord, _, _>(keyspace.clone(), Synckey, None, None).await {
+        Ok(record) => Ok(Some(record.milestone_index.0)),
+        Err(ListenerError::NoResults) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Get the earliest synced milestone index of a keyspace, if anything was synced yet
+async fn earliest_synced(keyspace: &ChronicleKeyspace) -> Result<Option<u32>, ListenerError> {
+    match query::<SyncRecord, _, _>(keyspace.clone(), EarliestSynckey, None, None).await {
+        Ok(record) => Ok(Some(record.milestone_index.0)),
+        Err(ListenerError::NoResults) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
 async fn page<K, V>(
     keyspace: String,
     hint: Hint,
     page_size: usize,
     state: &mut Option<StateData>,
     partition_config: &PartitionConfig,
+    max_partitions: usize,
     key: K,
 ) -> Result<Vec<Partitioned<V>>, ListenerError>
 where
@@ -363,44 +566,42 @@ where
 
     let keyspace = ChronicleKeyspace::new(keyspace);
     // Get the list of partitions which contain records for this request.
-    // These may have been passed in by the client, in which case we do not need
-    // to query for them.
-    let (latest_milestone, partition_ids) = match state {
+    // Cursors do not carry this list, so it is queried for every page.
+    let mut partition_ids =
+        query::<Vec<(MilestoneIndex, PartitionId)>, _, _>(keyspace.clone(), hint.clone(), None, None).await?;
+    if partition_ids.is_empty() {
+        return Err(ListenerError::NoResults);
+    }
+    let (first_partition_id, mut latest_milestone) = partition_ids
+        .iter()
+        .max_by_key(|(index, _)| index)
+        .map(|(index, id)| (*id, index.0))
+        .unwrap();
+    // Reorder the partitions list so we start with the correct partition id
+    let i = partition_ids
+        .iter()
+        .position(|&(_, partition_id)| first_partition_id == partition_id);
+    if let Some(i) = i {
+        partition_ids = partition_ids[i..]
+            .iter()
+            .chain(partition_ids[..i].iter())
+            .cloned()
+            .collect();
+    }
+    let hint_digest = hint_digest(&hint);
+    match state {
         Some(state) => {
-            if state.partition_ids.is_empty() {
-                return Err(ListenerError::InvalidState);
+            if state.hint_digest != hint_digest {
+                return Err(ListenerError::InvalidCursor);
             }
-            let latest_milestone = state
-                .last_milestone_index
-                .unwrap_or_else(|| state.partition_ids.first().map(|(i, _)| i.0).unwrap());
-            (latest_milestone, state.partition_ids.clone())
+            // Continue below the last page, ignoring anything added since paging started
+            latest_milestone = state.last_milestone_index.unwrap_or(state.max_milestone_index);
+            state.partition_ids = partition_ids.clone();
         }
         None => {
-            let mut partition_ids =
-                query::<Vec<(MilestoneIndex, PartitionId)>, _, _>(keyspace.clone(), hint, None, None).await?;
-            if partition_ids.is_empty() {
-                return Err(ListenerError::NoResults);
-            }
-            let (first_partition_id, latest_milestone) = partition_ids
-                .iter()
-                .max_by_key(|(index, _)| index)
-                .map(|(index, id)| (*id, index.0))
-                .unwrap();
-            // Reorder the partitions list so we start with the correct partition id
-            let i = partition_ids
-                .iter()
-                .position(|&(_, partition_id)| first_partition_id == partition_id);
-            if let Some(i) = i {
-                partition_ids = partition_ids[i..]
-                    .iter()
-                    .chain(partition_ids[..i].iter())
-                    .cloned()
-                    .collect();
-            }
-            *state = Some((None, None, None, partition_ids.clone()).into());
-            (latest_milestone, partition_ids)
+            *state = Some(StateData::new(hint_digest, latest_milestone, partition_ids.clone()));
         }
-    };
+    }
 
     // This is safe because we set the value above
     let mut state = state.as_mut().unwrap();
@@ -438,6 +639,8 @@ where
         prev_last_milestone_index.unwrap_or(latest_milestone),
     );
     let mut loop_timings = HashMap::new();
+    // The lowest milestone chunk scanned so far, which counts towards the budget even if it held no results
+    let mut lowest_scanned_chunk: Option<u32> = None;
     for (partition_ind, (index, partition_id)) in partition_ids.iter().enumerate().cycle() {
         if !last_index_map.contains_key(partition_id) {
             last_index_map.insert(*partition_id, index.0);
@@ -455,6 +658,22 @@ where
 
         // Fetch a chunk of results if we need them to fill the page size
         if !list_map.contains_key(partition_id) {
+            // Stop once we scanned as many partitions as we are allowed to, even if we found nothing yet.
+            // The chunks we visited are fully consumed, so the next page continues below the lowest one.
+            if list_map.len() >= max_partitions {
+                let lowest_chunk = results
+                    .iter()
+                    .map(|record| record.milestone_index() / milestone_chunk as u32)
+                    .chain(lowest_scanned_chunk)
+                    .min()
+                    .filter(|chunk| *chunk > 0);
+                if let Some(chunk) = lowest_chunk {
+                    debug!("Query budget exhausted");
+                    state.last_partition_id = Some(*partition_id);
+                    state.last_milestone_index = Some(chunk * milestone_chunk as u32 - 1);
+                    return Ok(results);
+                }
+            }
             start_time = std::time::Instant::now();
             let fetch_ids =
                 (partition_ind..partition_ind + fetch_size).filter_map(|ind| partition_ids.get(ind).map(|v| v.1));
@@ -490,6 +709,8 @@ where
         let list = list_map
             .get_mut(&partition_id)
             .ok_or_else(|| anyhow!("Unexpected error retrieving list by partition!"))?;
+        let scanned_chunk = last_index_map[partition_id] / milestone_chunk as u32;
+        lowest_scanned_chunk = Some(lowest_scanned_chunk.map_or(scanned_chunk, |chunk| chunk.min(scanned_chunk)));
 
         // Iterate the list, pulling records from the front until we hit
         // a milestone in the next chunk or run out
@@ -615,15 +836,95 @@ where
 }
 
 #[get("/<keyspace>/messages/<message_id>")]
-async fn get_message(keyspace: String, message_id: String, keyspaces: State<'_, HashSet<String>>) -> ListenerResult {
+async fn get_message(
+    keyspace: String,
+    message_id: String,
+    keyspaces: State<'_, HashSet<String>>,
+    cache: State<'_, ResponseCache>,
+    auth: Auth,
+) -> Result<Cacheable<ListenerResponse>, ListenerError> {
+    auth.keyspace(&keyspace)?;
     if !keyspaces.contains(&keyspace) {
         return Err(ListenerError::InvalidKeyspace(keyspace));
     }
-    let keyspace = ChronicleKeyspace::new(keyspace);
     let message_id = MessageId::from_str(&message_id).map_err(|e| ListenerError::BadParse(e.into()))?;
-    query::<Message, _, _>(keyspace, message_id, None, None)
-        .await
-        .and_then(|message| message.try_into().map_err(|e: Cow<'static, str>| anyhow!(e).into()))
+    let (message, confirmed) = message(&cache, &keyspace, message_id).await?;
+    Ok(Cacheable::new(
+        message_response(message)?,
+        confirmed.then(|| message_id.to_string()),
+        &auth,
+    ))
+}
+
+/// Get a message and whether it is confirmed. Confirmed messages are immutable, so they are cached.
+async fn message(
+    cache: &ResponseCache,
+    keyspace: &str,
+    message_id: MessageId,
+) -> Result<(Message, bool), ListenerError> {
+    let cache_key = format!("{}/messages/{}", keyspace, message_id);
+    if let Some(message) = cache.get::<Message>(&cache_key) {
+        return Ok((message, true));
+    }
+    let keyspace = ChronicleKeyspace::new(keyspace.to_owned());
+    let (message, metadata) =
+        query::<(Option<Message>, Option<MessageMetadata>), _, _>(keyspace, message_id, None, None).await?;
+    let message = message.ok_or(ListenerError::NoResults)?;
+    let confirmed = metadata
+        .map(|metadata| metadata.referenced_by_milestone_index.is_some())
+        .unwrap_or(false);
+    if confirmed {
+        cache.insert(cache_key, message.clone());
+    }
+    Ok((message, confirmed))
+}
+
+fn message_response(message: Message) -> ListenerResult {
+    message.try_into().map_err(|e: Cow<'static, str>| anyhow!(e).into())
+}
+
+#[get("/<keyspace>/messages/<message_id>/raw")]
+async fn get_message_raw(
+    keyspace: String,
+    message_id: String,
+    keyspaces: State<'_, HashSet<String>>,
+    cache: State<'_, ResponseCache>,
+    auth: Auth,
+) -> Result<Cacheable<Content<Vec<u8>>>, ListenerError> {
+    auth.keyspace(&keyspace)?;
+    if !keyspaces.contains(&keyspace) {
+        return Err(ListenerError::InvalidKeyspace(keyspace));
+    }
+    let message_id = MessageId::from_str(&message_id).map_err(|e| ListenerError::BadParse(e.into()))?;
+    let (bytes, confirmed) = raw_message(&cache, &keyspace, message_id).await?;
+    Ok(Cacheable::new(
+        Content(ContentType::Binary, bytes),
+        confirmed.then(|| message_id.to_string()),
+        &auth,
+    ))
+}
+
+/// Get the stored bytes of a message and whether it is confirmed, without unpacking and repacking them
+async fn raw_message(
+    cache: &ResponseCache,
+    keyspace: &str,
+    message_id: MessageId,
+) -> Result<(Vec<u8>, bool), ListenerError> {
+    let cache_key = format!("{}/messages/{}/raw", keyspace, message_id);
+    if let Some(bytes) = cache.get::<Vec<u8>>(&cache_key) {
+        return Ok((bytes, true));
+    }
+    let keyspace = ChronicleKeyspace::new(keyspace.to_owned());
+    let (message, metadata) =
+        query::<(Option<RawMessage>, Option<MessageMetadata>), _, _>(keyspace, message_id, None, None).await?;
+    let RawMessage(bytes) = message.ok_or(ListenerError::NoResults)?;
+    let confirmed = metadata
+        .map(|metadata| metadata.referenced_by_milestone_index.is_some())
+        .unwrap_or(false);
+    if confirmed {
+        cache.insert(cache_key, bytes.clone());
+    }
+    Ok((bytes, confirmed))
 }
 
 #[get("/<keyspace>/messages/<message_id>/metadata")]
@@ -631,15 +932,105 @@ async fn get_message_metadata(
     keyspace: String,
     message_id: String,
     keyspaces: State<'_, HashSet<String>>,
-) -> ListenerResult {
+    cache: State<'_, ResponseCache>,
+    auth: Auth,
+) -> Result<Cacheable<ListenerResponse>, ListenerError> {
+    auth.keyspace(&keyspace)?;
     if !keyspaces.contains(&keyspace) {
         return Err(ListenerError::InvalidKeyspace(keyspace));
     }
-    let keyspace = ChronicleKeyspace::new(keyspace);
     let message_id = MessageId::from_str(&message_id).map_err(|e| ListenerError::BadParse(e.into()))?;
-    query::<MessageMetadata, _, _>(keyspace, message_id, None, None)
+    let (metadata, referenced) = message_metadata(&cache, &keyspace, message_id).await?;
+    Ok(Cacheable::new(
+        metadata.into(),
+        referenced.then(|| message_id.to_string()),
+        &auth,
+    ))
+}
+
+/// Get the metadata of a message and whether it is referenced by a milestone,
+/// after which it is immutable and therefore cached.
+async fn message_metadata(
+    cache: &ResponseCache,
+    keyspace: &str,
+    message_id: MessageId,
+) -> Result<(MessageMetadata, bool), ListenerError> {
+    let cache_key = format!("{}/messages/{}/metadata", keyspace, message_id);
+    if let Some(metadata) = cache.get::<MessageMetadata>(&cache_key) {
+        return Ok((metadata, true));
+    }
+    let metadata =
+        query::<MessageMetadata, _, _>(ChronicleKeyspace::new(keyspace.to_owned()), message_id, None, None).await?;
+    let referenced = metadata.referenced_by_milestone_index.is_some();
+    if referenced {
+        cache.insert(cache_key, metadata.clone());
+    }
+    Ok((metadata, referenced))
+}
+
+#[post("/<keyspace>/messages/batch?<metadata>", format = "json", data = "<message_ids>")]
+async fn post_messages_batch(
+    keyspace: String,
+    metadata: Option<bool>,
+    message_ids: Json<Vec<String>>,
+    keyspaces: State<'_, HashSet<String>>,
+    cache: State<'_, ResponseCache>,
+    auth: Auth,
+) -> ListenerResult {
+    auth.keyspace(&keyspace)?;
+    if !keyspaces.contains(&keyspace) {
+        return Err(ListenerError::InvalidKeyspace(keyspace));
+    }
+    let cache = cache.inner();
+    let keyspace = &keyspace;
+    let metadata = metadata.unwrap_or(false);
+    batch(message_ids.into_inner(), |message_id| async move {
+        let message_id = MessageId::from_str(&message_id).map_err(|e| ListenerError::BadParse(e.into()))?;
+        if metadata {
+            message_metadata(cache, keyspace, message_id)
+                .await
+                .map(|(metadata, _)| metadata.into())
+        } else {
+            message_response(message(cache, keyspace, message_id).await?.0)
+        }
+    })
+    .await
+}
+
+/// Look up a batch of ids concurrently, collecting either the result or the error of each lookup
+async fn batch<F, Fut>(ids: Vec<String>, lookup: F) -> ListenerResult
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = ListenerResult>,
+{
+    const MAX_BATCH_SIZE: usize = 100;
+    if ids.len() > MAX_BATCH_SIZE {
+        return Err(ListenerError::BatchTooLarge(MAX_BATCH_SIZE));
+    }
+    let lookups = ids.into_iter().map(|id| {
+        let res = lookup(id.clone());
+        async move { (id, res.await) }
+    });
+    let results = futures::future::join_all(lookups)
         .await
-        .map(|metadata| metadata.into())
+        .into_iter()
+        .map(|(id, res)| match res {
+            Ok(data) => BatchRecord {
+                id,
+                data: Some(data),
+                error: None,
+            },
+            Err(e) => BatchRecord {
+                id,
+                data: None,
+                error: Some(e.to_string()),
+            },
+        })
+        .collect::<Vec<_>>();
+    Ok(ListenerResponse::Batch {
+        count: results.len(),
+        results,
+    })
 }
 
 #[get("/<keyspace>/messages/<message_id>/children?<page_size>&<expanded>&<state>")]
@@ -650,21 +1041,19 @@ async fn get_message_children(
     expanded: Option<bool>,
     state: Option<String>,
     partition_config: State<'_, PartitionConfig>,
+    api_config: State<'_, ApiConfig>,
+    cursor_key: State<'_, CursorKey>,
     keyspaces: State<'_, HashSet<String>>,
+    auth: Auth,
 ) -> ListenerResult {
+    auth.keyspace(&keyspace)?;
     if !keyspaces.contains(&keyspace) {
         return Err(ListenerError::InvalidKeyspace(keyspace));
     }
     let message_id = MessageId::from_str(&message_id).map_err(|e| ListenerError::BadParse(e.into()))?;
-    let page_size = page_size.unwrap_or(100);
+    let page_size = page_size.unwrap_or(100).min(api_config.max_page_size);
 
-    let mut state = state
-        .map(|state| {
-            hex::decode(state)
-                .map_err(|_| ListenerError::InvalidState)
-                .and_then(|v| bincode::deserialize::<StateData>(&v).map_err(|_| ListenerError::InvalidState))
-        })
-        .transpose()?;
+    let mut state = state.map(|state| cursor_key.decode::<StateData>(&state)).transpose()?;
 
     let mut messages = page(
         keyspace.clone(),
@@ -672,14 +1061,12 @@ async fn get_message_children(
         page_size,
         &mut state,
         partition_config.borrow(),
+        api_config.max_partitions_per_request,
         message_id,
     )
     .await?;
 
-    let state = state
-        .map(|state| bincode::serialize(&state).map(|v| hex::encode(v)))
-        .transpose()
-        .map_err(|e| anyhow!(e))?;
+    let state = state.map(|state| cursor_key.encode(&state)).transpose()?;
 
     if let Some(true) = expanded {
         Ok(ListenerResponse::MessageChildrenExpanded {
@@ -709,8 +1096,12 @@ async fn get_message_by_index(
     expanded: Option<bool>,
     state: Option<String>,
     partition_config: State<'_, PartitionConfig>,
+    api_config: State<'_, ApiConfig>,
+    cursor_key: State<'_, CursorKey>,
     keyspaces: State<'_, HashSet<String>>,
+    auth: Auth,
 ) -> ListenerResult {
+    auth.keyspace(&keyspace)?;
     if !keyspaces.contains(&keyspace) {
         return Err(ListenerError::InvalidKeyspace(keyspace));
     }
@@ -725,16 +1116,10 @@ async fn get_message_by_index(
         return Err(ListenerError::IndexTooLarge);
     }
 
-    let mut state = state
-        .map(|state| {
-            hex::decode(state)
-                .map_err(|_| ListenerError::InvalidState)
-                .and_then(|v| bincode::deserialize::<StateData>(&v).map_err(|_| ListenerError::InvalidState))
-        })
-        .transpose()?;
+    let mut state = state.map(|state| cursor_key.decode::<StateData>(&state)).transpose()?;
 
     let indexation = Indexation(index.clone());
-    let page_size = page_size.unwrap_or(1000);
+    let page_size = page_size.unwrap_or(1000).min(api_config.max_page_size);
 
     let mut messages = page(
         keyspace.clone(),
@@ -742,14 +1127,12 @@ async fn get_message_by_index(
         page_size,
         &mut state,
         partition_config.borrow(),
+        api_config.max_partitions_per_request,
         indexation,
     )
     .await?;
 
-    let state = state
-        .map(|state| bincode::serialize(&state).map(|v| hex::encode(v)))
-        .transpose()
-        .map_err(|e| anyhow!(e))?;
+    let state = state.map(|state| cursor_key.encode(&state)).transpose()?;
 
     if let Some(true) = expanded {
         Ok(ListenerResponse::MessagesForIndexExpanded {
@@ -778,36 +1161,106 @@ async fn get_ed25519_outputs(
     expanded: Option<bool>,
     state: Option<String>,
     partition_config: State<'_, PartitionConfig>,
+    api_config: State<'_, ApiConfig>,
+    cursor_key: State<'_, CursorKey>,
     keyspaces: State<'_, HashSet<String>>,
+    auth: Auth,
 ) -> ListenerResult {
+    auth.keyspace(&keyspace)?;
     if !keyspaces.contains(&keyspace) {
         return Err(ListenerError::InvalidKeyspace(keyspace));
     }
-    let mut state = state
-        .map(|state| {
-            hex::decode(state)
-                .map_err(|_| ListenerError::InvalidState)
-                .and_then(|v| bincode::deserialize::<StateData>(&v).map_err(|_| ListenerError::InvalidState))
-        })
-        .transpose()?;
-
     let ed25519_address = Ed25519Address::from_str(&address).map_err(|e| ListenerError::BadParse(e.into()))?;
-    let page_size = page_size.unwrap_or(100);
+    address_outputs(
+        keyspace,
+        address,
+        ed25519_address,
+        page_size,
+        expanded,
+        state,
+        partition_config.borrow(),
+        api_config.borrow(),
+        cursor_key.borrow(),
+    )
+    .await
+}
+
+#[get("/<keyspace>/addresses/<address>/outputs?<page_size>&<expanded>&<state>")]
+async fn get_bech32_outputs(
+    keyspace: String,
+    address: String,
+    page_size: Option<usize>,
+    expanded: Option<bool>,
+    state: Option<String>,
+    partition_config: State<'_, PartitionConfig>,
+    api_config: State<'_, ApiConfig>,
+    cursor_key: State<'_, CursorKey>,
+    storage_config: State<'_, StorageConfig>,
+    auth: Auth,
+) -> ListenerResult {
+    auth.keyspace(&keyspace)?;
+    let ed25519_address = parse_bech32(&storage_config, &keyspace, &address)?;
+    address_outputs(
+        keyspace,
+        address,
+        ed25519_address,
+        page_size,
+        expanded,
+        state,
+        partition_config.borrow(),
+        api_config.borrow(),
+        cursor_key.borrow(),
+    )
+    .await
+}
+
+/// Parse a bech32 address, verifying that its HRP is the one expected for the keyspace
+fn parse_bech32(
+    storage_config: &StorageConfig,
+    keyspace: &str,
+    address: &str,
+) -> Result<Ed25519Address, ListenerError> {
+    let expected_hrp = storage_config
+        .keyspace_config(keyspace)
+        .map(|keyspace_config| keyspace_config.bech32_hrp.clone())
+        .ok_or_else(|| ListenerError::InvalidKeyspace(keyspace.to_string()))?;
+    let (hrp, _, _) = bech32::decode(address).map_err(|e| ListenerError::BadParse(e.into()))?;
+    if hrp != expected_hrp {
+        return Err(ListenerError::InvalidHrp(expected_hrp, hrp));
+    }
+    let Address::Ed25519(ed25519_address) =
+        Address::try_from_bech32(address).map_err(|e| ListenerError::BadParse(anyhow!("{:?}", e)))?;
+    Ok(ed25519_address)
+}
+
+/// Page through the outputs of an ed25519 address, echoing the address as it was requested
+async fn address_outputs(
+    keyspace: String,
+    address: String,
+    ed25519_address: Ed25519Address,
+    page_size: Option<usize>,
+    expanded: Option<bool>,
+    state: Option<String>,
+    partition_config: &PartitionConfig,
+    api_config: &ApiConfig,
+    cursor_key: &CursorKey,
+) -> ListenerResult {
+    let mut state = state.map(|state| cursor_key.decode::<StateData>(&state)).transpose()?;
+
+    let page_size = page_size.unwrap_or(100).min(api_config.max_page_size);
 
     let mut outputs = page(
         keyspace.clone(),
         Hint::address(ed25519_address.to_string()),
         page_size,
         &mut state,
-        partition_config.borrow(),
+        partition_config,
+        api_config.max_partitions_per_request,
         ed25519_address,
     )
     .await?;
 
-    let state = state
-        .map(|state| bincode::serialize(&state).map(|v| hex::encode(v)))
-        .transpose()
-        .map_err(|e| anyhow!(e))?;
+    let state = state.map(|state| cursor_key.encode(&state)).transpose()?;
 
     if let Some(true) = expanded {
         Ok(ListenerResponse::OutputsForAddressExpanded {
@@ -838,81 +1291,817 @@ async fn get_ed25519_outputs(
     }
 }
 
-#[get("/<keyspace>/outputs/<output_id>")]
-async fn get_output(keyspace: String, output_id: String, keyspaces: State<'_, HashSet<String>>) -> ListenerResult {
+#[get("/<keyspace>/addresses/ed25519/<address>/balance?<milestone_index>")]
+async fn get_ed25519_balance(
+    keyspace: String,
+    address: String,
+    milestone_index: Option<u32>,
+    partition_config: State<'_, PartitionConfig>,
+    api_config: State<'_, ApiConfig>,
+    keyspaces: State<'_, HashSet<String>>,
+    auth: Auth,
+) -> ListenerResult {
+    auth.keyspace(&keyspace)?;
     if !keyspaces.contains(&keyspace) {
         return Err(ListenerError::InvalidKeyspace(keyspace));
     }
-    let output_id = OutputId::from_str(&output_id).map_err(|e| ListenerError::BadParse(e.into()))?;
+    let ed25519_address = Ed25519Address::from_str(&address).map_err(|e| ListenerError::BadParse(e.into()))?;
+    address_balance(
+        keyspace,
+        address,
+        ed25519_address,
+        milestone_index,
+        partition_config.borrow(),
+        api_config.borrow(),
+    )
+    .await
+}
 
-    let output_data = query::<OutputRes, _, _>(ChronicleKeyspace::new(keyspace.clone()), output_id, None, None).await?;
-    let is_spent = if output_data.unlock_blocks.is_empty() {
-        false
-    } else {
-        let mut is_spent = false;
-        let mut query_message_ids = HashSet::new();
-        for UnlockRes {
-            message_id,
-            block: _,
-            inclusion_state,
-        } in output_data.unlock_blocks.iter()
+#[get("/<keyspace>/addresses/<address>/balance?<milestone_index>")]
+async fn get_bech32_balance(
+    keyspace: String,
+    address: String,
+    milestone_index: Option<u32>,
+    partition_config: State<'_, PartitionConfig>,
+    api_config: State<'_, ApiConfig>,
+    storage_config: State<'_, StorageConfig>,
+    auth: Auth,
+) -> ListenerResult {
+    auth.keyspace(&keyspace)?;
+    let ed25519_address = parse_bech32(&storage_config, &keyspace, &address)?;
+    address_balance(
+        keyspace,
+        address,
+        ed25519_address,
+        milestone_index,
+        partition_config.borrow(),
+        api_config.borrow(),
+    )
+    .await
+}
+
+/// The number of queries a single request runs at a time when it resolves many rows
+const MAX_CONCURRENT_QUERIES: usize = 16;
+
+/// Compute the balance of an ed25519 address, either at the requested milestone index or at the
+/// latest milestone known to the keyspace.
+/// The balance is read from the ledger whenever it is maintained in the keyspace and covers the
+/// milestone index. Otherwise it is computed from the stored outputs, which are resolved one query
+/// at a time, so addresses which span more partitions than a single request may scan, or whose
+/// outputs need more queries than a single request may run, are rejected rather than scanned in full.
+async fn address_balance(
+    keyspace: String,
+    address: String,
+    ed25519_address: Ed25519Address,
+    milestone_index: Option<u32>,
+    partition_config: &PartitionConfig,
+    api_config: &ApiConfig,
+) -> ListenerResult {
+    let page_size = api_config.max_page_size;
+    let max_partitions = api_config.max_partitions_per_request;
+    let max_queries = api_config.max_queries_per_balance;
+    if let Some((ledger_index, tally)) = ledger_balance(
+        &ChronicleKeyspace::new(keyspace.clone()),
+        ed25519_address,
+        milestone_index,
+    )
+    .await?
+    {
+        return Ok(ListenerResponse::BalanceForAddress {
+            address_type: 1,
+            address,
+            balance: tally.balance,
+            dust_allowance_balance: tally.dust_allowance_balance,
+            output_count: tally.output_count,
+            dust_allowed: tally.dust_allowed(),
+            ledger_index,
+        });
+    }
+    let ledger_index = match milestone_index {
+        Some(index) => Some(index),
+        None => latest_synced(&ChronicleKeyspace::new(keyspace.clone())).await?,
+    };
+    let mut state = None;
+    let mut tally = BalanceTally::default();
+    let mut seen = HashSet::new();
+    let mut queries = 0;
+    loop {
+        let outputs = match page::<_, AddressRecord>(
+            keyspace.clone(),
+            Hint::address(ed25519_address.to_string()),
+            page_size,
+            &mut state,
+            partition_config,
+            max_partitions,
+            ed25519_address,
+        )
+        .await
         {
-            if *inclusion_state == Some(LedgerInclusionState::Included) {
-                is_spent = true;
-                break;
-            } else {
-                query_message_ids.insert(message_id);
+            Ok(outputs) => outputs,
+            // An address without any stored output has no balance
+            Err(ListenerError::NoResults) => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        if state
+            .as_ref()
+            .map_or(false, |state| state.partition_ids.len() > max_partitions)
+        {
+            return Err(ListenerError::TooManyPartitions(max_partitions));
+        }
+        // Only outputs created by a transaction included at (or before) the ledger index count
+        let created = outputs
+            .into_iter()
+            .filter(|record| {
+                record.ledger_inclusion_state == Some(LedgerInclusionState::Included)
+                    && ledger_index.map_or(true, |index| record.milestone_index() <= index)
+            })
+            .filter_map(|record| {
+                OutputId::new(record.transaction_id, record.index)
+                    .ok()
+                    .filter(|output_id| seen.insert(*output_id))
+                    .map(|output_id| (output_id, record))
+            })
+            .collect::<Vec<_>>();
+        // Every output costs a query, and so does every message which spends it
+        queries += created.len();
+        if queries > max_queries {
+            return Err(ListenerError::TooManyQueries(max_queries));
+        }
+        let outputs = futures::stream::iter(created.into_iter().map(|(output_id, record)| {
+            let keyspace = ChronicleKeyspace::new(keyspace.clone());
+            async move {
+                let output_data = query::<OutputRes, _, _>(keyspace, output_id, None, None).await?;
+                Ok::<_, ListenerError>((record, spending_message_ids(&output_data.unlock_blocks)))
             }
+        }))
+        .buffer_unordered(MAX_CONCURRENT_QUERIES)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+        queries += outputs.iter().map(|(_, message_ids)| message_ids.len()).sum::<usize>();
+        if queries > max_queries {
+            return Err(ListenerError::TooManyQueries(max_queries));
         }
-        if !query_message_ids.is_empty() {
-            let queries = query_message_ids.drain().map(|&message_id| {
-                query::<MessageMetadata, _, _>(ChronicleKeyspace::new(keyspace.clone()), message_id.clone(), None, None)
-            });
-            is_spent = futures::future::join_all(queries)
-                .await
-                .drain(..)
-                .filter_map(|res| res.ok())
-                .any(|metadata| metadata.ledger_inclusion_state == Some(LedgerInclusionState::Included));
+        let spent = futures::stream::iter(outputs.into_iter().map(|(record, message_ids)| {
+            let keyspace = ChronicleKeyspace::new(keyspace.clone());
+            async move { (record, spent_at(&keyspace, message_ids).await) }
+        }))
+        .buffer_unordered(MAX_CONCURRENT_QUERIES)
+        .collect::<Vec<_>>()
+        .await;
+        for (record, spent_at) in spent {
+            tally.add(record.amount, record.output_type, spent_at, ledger_index);
         }
-        is_spent
-    };
-    Ok(ListenerResponse::Output {
-        message_id: output_data.message_id.to_string(),
-        transaction_id: output_id.transaction_id().to_string(),
-        output_index: output_id.index(),
-        is_spent,
-        output: output_data.output.borrow().into(),
+        // The paging state is consumed once every partition is depleted
+        if state
+            .as_ref()
+            .map(|state| state.last_partition_id.is_none())
+            .unwrap_or(true)
+        {
+            break;
+        }
+    }
+    Ok(ListenerResponse::BalanceForAddress {
+        address_type: 1,
+        address,
+        balance: tally.balance,
+        dust_allowance_balance: tally.dust_allowance_balance,
+        output_count: tally.output_count,
+        dust_allowed: tally.dust_allowed(),
+        ledger_index: ledger_index.unwrap_or_default(),
     })
 }
 
-#[get("/<keyspace>/transactions/<transaction_id>/included-message")]
-async fn get_transaction_included_message(
-    keyspace: String,
-    transaction_id: String,
-    keyspaces: State<'_, HashSet<String>>,
-) -> ListenerResult {
-    if !keyspaces.contains(&keyspace) {
-        return Err(ListenerError::InvalidKeyspace(keyspace));
+/// Read the balance of an address from the ledger, along with the milestone index it is valid at.
+/// Nothing is returned if the keyspace holds no ledger, or if the ledger can't answer for the
+/// requested milestone index, which is either not applied yet or older than the ledger itself.
+async fn ledger_balance(
+    keyspace: &ChronicleKeyspace,
+    address: Ed25519Address,
+    milestone_index: Option<u32>,
+) -> Result<Option<(u32, BalanceTally)>, ListenerError> {
+    let checkpoint = match query::<LedgerCheckpoint, _, _>(keyspace.clone(), Synckey, None, None).await {
+        Ok(checkpoint) => checkpoint,
+        Err(ListenerError::NoResults) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let ledger_index = match milestone_index {
+        Some(index) if index > checkpoint.milestone_index => return Ok(None),
+        Some(index) => index,
+        None => checkpoint.milestone_index,
+    };
+    match query::<BalanceRecord, _, _>(keyspace.clone(), (address, MilestoneIndex(ledger_index)), None, None).await {
+        Ok(record) => Ok(Some((ledger_index, record.into()))),
+        // The ledger holds every balance as of its checkpoint, so the address has never held any outputs
+        Err(ListenerError::NoResults) if milestone_index.is_none() => Ok(Some((ledger_index, BalanceTally::default()))),
+        // The requested milestone index may predate the ledger
+        Err(ListenerError::NoResults) => Ok(None),
+        Err(e) => Err(e),
     }
-    let keyspace = ChronicleKeyspace::new(keyspace);
+}
 
-    let transaction_id = TransactionId::from_str(&transaction_id).map_err(|e| ListenerError::Other(anyhow!(e)))?;
+/// Outputs holding fewer tokens than this are dust, which requires a dust allowance
+const DUST_THRESHOLD: u64 = 1_000_000;
+/// The dust allowance needed per dust output of an address
+const DUST_ALLOWANCE_DIVISOR: u64 = 100_000;
+/// The maximum number of dust outputs of an address
+const DUST_OUTPUTS_MAX: u64 = 100;
+
+/// The running balance of an address, accumulated from its outputs
+#[derive(Debug, Default, PartialEq)]
+struct BalanceTally {
+    balance: u64,
+    dust_allowance_balance: u64,
+    output_count: usize,
+    dust_output_count: usize,
+}
 
-    let message_id = query::<MessageId, _, _>(keyspace.clone(), transaction_id, None, None).await?;
-    query::<Message, _, _>(keyspace, message_id, None, None)
-        .await
-        .and_then(|message| message.try_into().map_err(|e: Cow<'static, str>| anyhow!(e).into()))
+impl BalanceTally {
+    /// Add an output to the balance unless it was spent at (or before) the ledger index
+    fn add(&mut self, amount: u64, output_type: u8, spent_at: Option<u32>, ledger_index: Option<u32>) {
+        let is_spent = match (spent_at, ledger_index) {
+            (Some(spent_at), Some(index)) => spent_at <= index,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        if !is_spent {
+            self.balance += amount;
+            if output_type == SignatureLockedDustAllowanceOutput::KIND {
+                self.dust_allowance_balance += amount;
+            } else if amount < DUST_THRESHOLD {
+                self.dust_output_count += 1;
+            }
+            self.output_count += 1;
+        }
+    }
+
+    /// Whether the address may receive another dust output, i.e. it holds fewer dust outputs
+    /// than its dust allowance allows (one per 100_000 tokens, up to 100)
+    fn dust_allowed(&self) -> bool {
+        self.dust_output_count < (self.dust_allowance_balance / DUST_ALLOWANCE_DIVISOR).min(DUST_OUTPUTS_MAX) as usize
+    }
 }
 
-#[get("/<keyspace>/milestones/<index>")]
-async fn get_milestone(keyspace: String, index: u32, keyspaces: State<'_, HashSet<String>>) -> ListenerResult {
-    if !keyspaces.contains(&keyspace) {
-        return Err(ListenerError::InvalidKeyspace(keyspace));
+impl From<BalanceRecord> for BalanceTally {
+    fn from(record: BalanceRecord) -> Self {
+        Self {
+            balance: record.balance,
+            dust_allowance_balance: record.dust_allowance,
+            output_count: record.output_count as usize,
+            dust_output_count: record.dust_output_count as usize,
+        }
     }
-    let keyspace = ChronicleKeyspace::new(keyspace);
+}
 
-    query::<Milestone, _, _>(keyspace, MilestoneIndex::from(index), None, None)
-        .await
+/// The messages which may have spent an output, according to its unlock blocks
+fn spending_message_ids(unlock_blocks: &[UnlockRes]) -> HashSet<MessageId> {
+    unlock_blocks
+        .iter()
+        .filter(|unlock| unlock.inclusion_state != Some(LedgerInclusionState::Conflicting))
+        .map(|unlock| unlock.message_id)
+        .collect()
+}
+
+/// Resolve the milestone index at which an output was spent using the messages spending it, if it was spent
+async fn spent_at(keyspace: &ChronicleKeyspace, message_ids: HashSet<MessageId>) -> Option<u32> {
+    let queries = message_ids
+        .into_iter()
+        .map(|message_id| query::<MessageMetadata, _, _>(keyspace.clone(), message_id, None, None));
+    futures::future::join_all(queries)
+        .await
+        .into_iter()
+        .filter_map(|res| res.ok())
+        .filter(|metadata| metadata.ledger_inclusion_state == Some(LedgerInclusionState::Included))
+        .filter_map(|metadata| metadata.referenced_by_milestone_index)
+        .min()
+}
+
+#[get("/<keyspace>/addresses/ed25519/<address>/transactions?<page_size>&<state>")]
+async fn get_ed25519_transactions(
+    keyspace: String,
+    address: String,
+    page_size: Option<usize>,
+    state: Option<String>,
+    partition_config: State<'_, PartitionConfig>,
+    api_config: State<'_, ApiConfig>,
+    cursor_key: State<'_, CursorKey>,
+    keyspaces: State<'_, HashSet<String>>,
+    auth: Auth,
+) -> ListenerResult {
+    auth.keyspace(&keyspace)?;
+    if !keyspaces.contains(&keyspace) {
+        return Err(ListenerError::InvalidKeyspace(keyspace));
+    }
+    let ed25519_address = Ed25519Address::from_str(&address).map_err(|e| ListenerError::BadParse(e.into()))?;
+    address_transactions(
+        keyspace,
+        address,
+        ed25519_address,
+        page_size,
+        state,
+        partition_config.borrow(),
+        api_config.borrow(),
+        cursor_key.borrow(),
+    )
+    .await
+}
+
+#[get("/<keyspace>/addresses/<address>/transactions?<page_size>&<state>")]
+async fn get_bech32_transactions(
+    keyspace: String,
+    address: String,
+    page_size: Option<usize>,
+    state: Option<String>,
+    partition_config: State<'_, PartitionConfig>,
+    api_config: State<'_, ApiConfig>,
+    cursor_key: State<'_, CursorKey>,
+    storage_config: State<'_, StorageConfig>,
+    auth: Auth,
+) -> ListenerResult {
+    auth.keyspace(&keyspace)?;
+    let ed25519_address = parse_bech32(&storage_config, &keyspace, &address)?;
+    address_transactions(
+        keyspace,
+        address,
+        ed25519_address,
+        page_size,
+        state,
+        partition_config.borrow(),
+        api_config.borrow(),
+        cursor_key.borrow(),
+    )
+    .await
+}
+
+/// Every paged output of an address lists at most the transaction which created it
+/// and the transaction which spent it
+const TRANSACTIONS_PER_OUTPUT: usize = 2;
+
+/// List the included transactions which moved funds to or from an address, paged by the outputs of the address.
+/// Pages are ordered by the milestone of those outputs: a transaction which only spends from the address
+/// is listed along with the most recent output it spends, so it may be more recent than the rest of its page.
+/// Transactions missing from the keyspace are left out of their page rather than failing it.
+#[allow(clippy::too_many_arguments)]
+async fn address_transactions(
+    keyspace: String,
+    address: String,
+    ed25519_address: Ed25519Address,
+    page_size: Option<usize>,
+    state: Option<String>,
+    partition_config: &PartitionConfig,
+    api_config: &ApiConfig,
+    cursor_key: &CursorKey,
+) -> ListenerResult {
+    let mut state = state.map(|state| cursor_key.decode::<StateData>(&state)).transpose()?;
+    let page_size = page_size.unwrap_or(100).min(api_config.max_page_size);
+
+    let outputs = page(
+        keyspace.clone(),
+        Hint::address(ed25519_address.to_string()),
+        page_size,
+        &mut state,
+        partition_config,
+        api_config.max_partitions_per_request,
+        ed25519_address,
+    )
+    .await?
+    .into_iter()
+    .filter(|record| record.ledger_inclusion_state == Some(LedgerInclusionState::Included))
+    .collect::<Vec<Partitioned<AddressRecord>>>();
+
+    let state = state.map(|state| cursor_key.encode(&state)).transpose()?;
+
+    let keyspace = ChronicleKeyspace::new(keyspace);
+    let mut transactions = HashMap::new();
+    // The transactions which created the outputs of this page
+    let creating_ids = outputs
+        .iter()
+        .map(|record| record.transaction_id)
+        .collect::<HashSet<_>>();
+    fetch_transactions(&keyspace, creating_ids.iter().cloned(), &mut transactions).await?;
+    // The transactions which spent them, found through the unlock rows of the outputs
+    let mut spending_ids = HashSet::new();
+    for record in outputs.iter() {
+        let spent_by = transaction_rows(&transactions, &record.transaction_id)
+            .iter()
+            .filter(|(index, row)| {
+                *index == record.index && row.inclusion_state == Some(LedgerInclusionState::Included)
+            })
+            .filter_map(|(_, row)| match &row.data {
+                TransactionData::Unlock(unlock) => Some(unlock.input_tx_id),
+                _ => None,
+            })
+            .filter(|transaction_id| !creating_ids.contains(transaction_id));
+        spending_ids.extend(spent_by);
+    }
+    fetch_transactions(&keyspace, spending_ids.iter().cloned(), &mut transactions).await?;
+    // The transactions which created the inputs of all of the above, so we can tell which inputs
+    // belong to this address
+    let mut input_ids = HashSet::new();
+    for transaction_id in creating_ids.iter().chain(spending_ids.iter()) {
+        let inputs = transaction_rows(&transactions, transaction_id)
+            .iter()
+            .filter_map(|(_, row)| match &row.data {
+                TransactionData::Input(InputData::Utxo(utxo_input, _)) => {
+                    Some(*utxo_input.output_id().transaction_id())
+                }
+                _ => None,
+            });
+        input_ids.extend(inputs);
+    }
+    fetch_transactions(&keyspace, input_ids.into_iter(), &mut transactions).await?;
+
+    let page_milestones = outputs
+        .iter()
+        .map(|record| record.milestone_index())
+        .collect::<HashSet<_>>();
+    let entries = address_entries(
+        &creating_ids,
+        &spending_ids,
+        &page_milestones,
+        &transactions,
+        &ed25519_address,
+    );
+
+    let milestone_indexes = entries
+        .iter()
+        .map(|summary| summary.milestone_index)
+        .collect::<HashSet<_>>();
+    let timestamps = futures::future::join_all(milestone_indexes.into_iter().map(|index| {
+        let keyspace = keyspace.clone();
+        async move {
+            query::<Milestone, _, _>(keyspace, MilestoneIndex(index), None, None)
+                .await
+                .ok()
+                .map(|milestone| (index, milestone.timestamp()))
+        }
+    }))
+    .await
+    .into_iter()
+    .flatten()
+    .collect::<HashMap<_, _>>();
+
+    Ok(ListenerResponse::TransactionsForAddress {
+        address_type: 1,
+        address,
+        // A page may hold more outputs than its size when they share a milestone
+        max_results: TRANSACTIONS_PER_OUTPUT * outputs.len().max(page_size),
+        count: entries.len(),
+        transactions: entries
+            .into_iter()
+            .map(|summary| AddressTransaction {
+                message_id: summary.message_id.to_string(),
+                milestone_index: summary.milestone_index,
+                milestone_timestamp: timestamps.get(&summary.milestone_index).cloned(),
+                amount: summary.amount,
+            })
+            .collect(),
+        state,
+    })
+}
+
+/// Get the fetched `transactions` rows of a transaction, a transaction missing from the keyspace has none
+fn transaction_rows<'a>(
+    transactions: &'a HashMap<TransactionId, Vec<(Index, TransactionRecord)>>,
+    transaction_id: &TransactionId,
+) -> &'a [(Index, TransactionRecord)] {
+    transactions.get(transaction_id).map(Vec::as_slice).unwrap_or_default()
+}
+
+/// Summarize the transactions of a page of address outputs, ordered by the milestone of the output
+/// which placed them on the page, most recent first.
+/// A transaction which only spends from the address belongs to the page holding the most recent
+/// output it spends, so it is never listed twice across pages.
+fn address_entries(
+    creating_ids: &HashSet<TransactionId>,
+    spending_ids: &HashSet<TransactionId>,
+    page_milestones: &HashSet<u32>,
+    transactions: &HashMap<TransactionId, Vec<(Index, TransactionRecord)>>,
+    address: &Ed25519Address,
+) -> Vec<TransactionSummary> {
+    let mut entries = creating_ids
+        .iter()
+        .filter_map(|transaction_id| summarize_transaction(transaction_id, transactions, address))
+        .chain(
+            spending_ids
+                .iter()
+                .filter_map(|transaction_id| summarize_transaction(transaction_id, transactions, address))
+                .filter(|summary| {
+                    summary
+                        .latest_input_milestone
+                        .map(|index| page_milestones.contains(&index))
+                        .unwrap_or(false)
+                }),
+        )
+        .collect::<Vec<_>>();
+    entries.sort_by_key(|summary| {
+        std::cmp::Reverse((
+            summary.page_milestone(),
+            summary.milestone_index,
+            summary.message_id.to_string(),
+        ))
+    });
+    entries
+}
+
+/// Fetch the `transactions` rows of the given transactions which were not fetched yet,
+/// skipping the transactions missing from the keyspace
+async fn fetch_transactions(
+    keyspace: &ChronicleKeyspace,
+    transaction_ids: impl Iterator<Item = TransactionId>,
+    transactions: &mut HashMap<TransactionId, Vec<(Index, TransactionRecord)>>,
+) -> Result<(), ListenerError> {
+    let transaction_ids = transaction_ids
+        .filter(|transaction_id| !transactions.contains_key(transaction_id))
+        .collect::<HashSet<_>>();
+    let queries = transaction_ids.into_iter().map(|transaction_id| {
+        let keyspace = keyspace.clone();
+        async move {
+            query::<Vec<(Index, TransactionRecord)>, _, _>(keyspace, transaction_id, None, None)
+                .await
+                .map(|rows| (transaction_id, rows))
+        }
+    });
+    for res in futures::future::join_all(queries).await {
+        match res {
+            Ok((transaction_id, rows)) => {
+                transactions.insert(transaction_id, rows);
+            }
+            Err(ListenerError::NoResults) => (),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+/// An included transaction, seen from the point of view of an address
+#[derive(Debug, PartialEq)]
+struct TransactionSummary {
+    message_id: MessageId,
+    milestone_index: u32,
+    /// The amount received by the address minus the amount spent from it
+    amount: i64,
+    /// The milestone which included the most recent output of the address spent by this transaction,
+    /// only set if the transaction sends nothing back to the address
+    latest_input_milestone: Option<u32>,
+}
+
+impl TransactionSummary {
+    /// The milestone of the address output which placed this transaction on its page
+    fn page_milestone(&self) -> u32 {
+        self.latest_input_milestone.unwrap_or(self.milestone_index)
+    }
+}
+
+/// Compute the net amount an included transaction moved to or from an address, using the
+/// already fetched `transactions` rows
+fn summarize_transaction(
+    transaction_id: &TransactionId,
+    transactions: &HashMap<TransactionId, Vec<(Index, TransactionRecord)>>,
+    address: &Ed25519Address,
+) -> Option<TransactionSummary> {
+    let mut message_id = None;
+    let mut milestone_index = None;
+    let mut received = 0;
+    let mut spent = 0;
+    let mut credited = false;
+    let mut latest_input_milestone = None;
+    let included = transactions
+        .get(transaction_id)?
+        .iter()
+        .filter(|(_, row)| row.inclusion_state == Some(LedgerInclusionState::Included));
+    for (_, row) in included {
+        match &row.data {
+            TransactionData::Output(output) => {
+                if let Some(amount) = amount_for(output, address) {
+                    received += amount;
+                    credited = true;
+                }
+            }
+            TransactionData::Input(input) => {
+                if let InputData::Utxo(utxo_input, _) = input {
+                    let output_id = utxo_input.output_id();
+                    let created = transactions.get(output_id.transaction_id()).and_then(|rows| {
+                        rows.iter().find(|(index, row)| {
+                            *index == output_id.index() && row.inclusion_state == Some(LedgerInclusionState::Included)
+                        })
+                    });
+                    if let Some((_, created)) = created {
+                        if let TransactionData::Output(output) = &created.data {
+                            if let Some(amount) = amount_for(output, address) {
+                                spent += amount;
+                                latest_input_milestone =
+                                    latest_input_milestone.max(created.milestone_index.map(|index| index.0));
+                            }
+                        }
+                    }
+                }
+            }
+            // Unlock rows are stored under the transaction which created the unlocked output,
+            // but belong to the spending transaction
+            TransactionData::Unlock(_) => continue,
+        }
+        message_id = Some(row.message_id);
+        milestone_index = row.milestone_index.map(|index| index.0);
+    }
+    Some(TransactionSummary {
+        message_id: message_id?,
+        milestone_index: milestone_index?,
+        amount: received as i64 - spent as i64,
+        latest_input_milestone: if credited { None } else { latest_input_milestone },
+    })
+}
+
+/// Get the amount of an output if it is locked to the given address
+fn amount_for(output: &Output, address: &Ed25519Address) -> Option<u64> {
+    let (Address::Ed25519(output_address), amount) = match output {
+        Output::SignatureLockedSingle(sls) => (sls.address(), sls.amount()),
+        Output::SignatureLockedDustAllowance(slda) => (slda.address(), slda.amount()),
+        _ => return None,
+    };
+    if output_address == address {
+        Some(amount)
+    } else {
+        None
+    }
+}
+
+#[get("/<keyspace>/outputs/<output_id>")]
+async fn get_output(
+    keyspace: String,
+    output_id: String,
+    keyspaces: State<'_, HashSet<String>>,
+    auth: Auth,
+) -> ListenerResult {
+    auth.keyspace(&keyspace)?;
+    if !keyspaces.contains(&keyspace) {
+        return Err(ListenerError::InvalidKeyspace(keyspace));
+    }
+    let output_id = OutputId::from_str(&output_id).map_err(|e| ListenerError::BadParse(e.into()))?;
+    output(keyspace, output_id).await
+}
+
+#[post("/<keyspace>/outputs/batch", format = "json", data = "<output_ids>")]
+async fn post_outputs_batch(
+    keyspace: String,
+    output_ids: Json<Vec<String>>,
+    keyspaces: State<'_, HashSet<String>>,
+    auth: Auth,
+) -> ListenerResult {
+    auth.keyspace(&keyspace)?;
+    if !keyspaces.contains(&keyspace) {
+        return Err(ListenerError::InvalidKeyspace(keyspace));
+    }
+    batch(output_ids.into_inner(), |output_id| {
+        let keyspace = keyspace.clone();
+        async move {
+            let output_id = OutputId::from_str(&output_id).map_err(|e| ListenerError::BadParse(e.into()))?;
+            output(keyspace, output_id).await
+        }
+    })
+    .await
+}
+
+async fn output(keyspace: String, output_id: OutputId) -> ListenerResult {
+    let output_data = query::<OutputRes, _, _>(ChronicleKeyspace::new(keyspace.clone()), output_id, None, None).await?;
+    // Outputs spent by included transactions carry a spent marker
+    let spent = match query::<SpentRecord, _, _>(ChronicleKeyspace::new(keyspace.clone()), output_id, None, None).await
+    {
+        Ok(spent) => Some(spent),
+        Err(ListenerError::NoResults) => None,
+        Err(e) => return Err(e),
+    };
+    // Otherwise fall back to the inclusion state of the messages which unlocked it
+    let is_spent = if spent.is_some() {
+        true
+    } else if output_data.unlock_blocks.is_empty() {
+        false
+    } else {
+        let mut is_spent = false;
+        let mut query_message_ids = HashSet::new();
+        for UnlockRes {
+            message_id,
+            block: _,
+            inclusion_state,
+        } in output_data.unlock_blocks.iter()
+        {
+            if *inclusion_state == Some(LedgerInclusionState::Included) {
+                is_spent = true;
+                break;
+            } else {
+                query_message_ids.insert(message_id);
+            }
+        }
+        if !query_message_ids.is_empty() {
+            let queries = query_message_ids.drain().map(|&message_id| {
+                query::<MessageMetadata, _, _>(ChronicleKeyspace::new(keyspace.clone()), message_id.clone(), None, None)
+            });
+            is_spent = futures::future::join_all(queries)
+                .await
+                .drain(..)
+                .filter_map(|res| res.ok())
+                .any(|metadata| metadata.ledger_inclusion_state == Some(LedgerInclusionState::Included));
+        }
+        is_spent
+    };
+    Ok(ListenerResponse::Output {
+        message_id: output_data.message_id.to_string(),
+        transaction_id: output_id.transaction_id().to_string(),
+        output_index: output_id.index(),
+        is_spent,
+        spent_by: spent.as_ref().map(|spent| spent.transaction_id.to_string()),
+        milestone_index_spent: spent.map(|spent| spent.milestone_index.0),
+        output: output_data.output.borrow().into(),
+    })
+}
+
+#[get("/<keyspace>/transactions/<transaction_id>/included-message")]
+async fn get_transaction_included_message(
+    keyspace: String,
+    transaction_id: String,
+    keyspaces: State<'_, HashSet<String>>,
+    auth: Auth,
+) -> ListenerResult {
+    auth.keyspace(&keyspace)?;
+    if !keyspaces.contains(&keyspace) {
+        return Err(ListenerError::InvalidKeyspace(keyspace));
+    }
+    let keyspace = ChronicleKeyspace::new(keyspace);
+
+    let transaction_id = TransactionId::from_str(&transaction_id).map_err(|e| ListenerError::Other(anyhow!(e)))?;
+
+    let message_id = query::<MessageId, _, _>(keyspace.clone(), transaction_id, None, None).await?;
+    query::<Message, _, _>(keyspace, message_id, None, None)
+        .await
+        .and_then(|message| message.try_into().map_err(|e: Cow<'static, str>| anyhow!(e).into()))
+}
+
+#[get("/<keyspace>/milestones?<start>&<end>&<page_size>")]
+async fn get_milestones(
+    keyspace: String,
+    start: Option<u32>,
+    end: Option<u32>,
+    page_size: Option<u32>,
+    keyspaces: State<'_, HashSet<String>>,
+    api_config: State<'_, ApiConfig>,
+    auth: Auth,
+) -> ListenerResult {
+    auth.keyspace(&keyspace)?;
+    if !keyspaces.contains(&keyspace) {
+        return Err(ListenerError::InvalidKeyspace(keyspace));
+    }
+    let keyspace = ChronicleKeyspace::new(keyspace);
+
+    let page_size = page_size.unwrap_or(100).min(1000).min(api_config.max_page_size as u32);
+    let start = start.unwrap_or(1);
+    let end = end.unwrap_or(i32::MAX as u32);
+    let page_end = end.min(start.saturating_add(page_size));
+
+    let queries = (start..page_end).map(|index| {
+        let keyspace = keyspace.clone();
+        async move {
+            match query::<Milestone, _, _>(keyspace, MilestoneIndex(index), None, None).await {
+                Ok(milestone) => Ok(Some(MilestoneRecord {
+                    milestone_index: index,
+                    message_id: milestone.message_id().to_string(),
+                    timestamp: milestone.timestamp(),
+                })),
+                // Milestones which are not stored (yet) are skipped
+                Err(ListenerError::NoResults) => Ok(None),
+                Err(e) => Err(e),
+            }
+        }
+    });
+    // The page is resolved a few milestones at a time, in order
+    let milestones = futures::stream::iter(queries)
+        .buffered(MAX_CONCURRENT_QUERIES)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .filter_map(Result::transpose)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ListenerResponse::Milestones {
+        max_results: page_size as usize,
+        count: milestones.len(),
+        milestones,
+        next_start: if page_end < end { Some(page_end) } else { None },
+    })
+}
+
+#[get("/<keyspace>/milestones/latest")]
+async fn get_latest_milestone(keyspace: String, keyspaces: State<'_, HashSet<String>>, auth: Auth) -> ListenerResult {
+    auth.keyspace(&keyspace)?;
+    if !keyspaces.contains(&keyspace) {
+        return Err(ListenerError::InvalidKeyspace(keyspace));
+    }
+    let keyspace = ChronicleKeyspace::new(keyspace);
+
+    let index = latest_synced(&keyspace).await?.ok_or(ListenerError::NoResults)?;
+
+    query::<Milestone, _, _>(keyspace, MilestoneIndex::from(index), None, None)
+        .await
         .map(|milestone| ListenerResponse::Milestone {
             milestone_index: index,
             message_id: milestone.message_id().to_string(),
@@ -920,118 +2109,1777 @@ async fn get_milestone(keyspace: String, index: u32, keyspaces: State<'_, HashSe
         })
 }
 
-#[get("/<keyspace>/analytics?<start>&<end>")]
-async fn get_analytics(
-    keyspace: String,
-    start: Option<u32>,
-    end: Option<u32>,
-    keyspaces: State<'_, HashSet<String>>,
-) -> ListenerResult {
-    if !keyspaces.contains(&keyspace) {
-        return Err(ListenerError::InvalidKeyspace(keyspace));
+#[get("/<keyspace>/milestones/by-time?<timestamp>")]
+async fn get_milestone_by_time(
+    keyspace: String,
+    timestamp: u64,
+    keyspaces: State<'_, HashSet<String>>,
+    auth: Auth,
+) -> ListenerResult {
+    auth.keyspace(&keyspace)?;
+    if !keyspaces.contains(&keyspace) {
+        return Err(ListenerError::InvalidKeyspace(keyspace));
+    }
+    let timestamp = MilestoneTimestamp(timestamp);
+    if timestamp.bucket().is_none() {
+        return Err(ListenerError::BadParse(anyhow!(
+            "Timestamp {} is too far in the future!",
+            timestamp.0
+        )));
+    }
+    let keyspace = ChronicleKeyspace::new(keyspace);
+
+    // Milestones are never searched for before the earliest synced one, nor after the latest synced one
+    let earliest = match earliest_synced(&keyspace).await? {
+        Some(index) => milestone_timestamp(&keyspace, index).await,
+        None => return Err(ListenerError::NoResults),
+    };
+    let latest = match latest_synced(&keyspace).await? {
+        Some(index) => milestone_timestamp(&keyspace, index).await,
+        None => None,
+    }
+    .unwrap_or_else(|| {
+        MilestoneTimestamp(
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(|now| now.as_secs())
+                .unwrap_or_default(),
+        )
+    });
+    for current in time_buckets(timestamp, earliest, latest) {
+        match query::<(MilestoneIndex, Milestone), _, _>(keyspace.clone(), current, None, None).await {
+            Ok((milestone_index, milestone)) => {
+                return Ok(ListenerResponse::Milestone {
+                    milestone_index: milestone_index.0,
+                    message_id: milestone.message_id().to_string(),
+                    timestamp: milestone.timestamp(),
+                })
+            }
+            // Nothing confirmed in this bucket at or before the timestamp, so look into the previous one
+            Err(ListenerError::NoResults) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Err(ListenerError::NoResults)
+}
+
+/// Get the timestamp of a stored milestone, if it can be found
+async fn milestone_timestamp(keyspace: &ChronicleKeyspace, index: u32) -> Option<MilestoneTimestamp> {
+    query::<Milestone, _, _>(keyspace.clone(), MilestoneIndex(index), None, None)
+        .await
+        .ok()
+        .map(|milestone| MilestoneTimestamp(milestone.timestamp()))
+}
+
+/// The time buckets to look into for the latest milestone at or before a timestamp, most recent first.
+/// The search starts at the latest timestamp if the requested one is later, and stops at the bucket of the
+/// earliest synced milestone, or after `MAX_BUCKETS` (daily) buckets if its timestamp is unknown.
+fn time_buckets(
+    timestamp: MilestoneTimestamp,
+    earliest: Option<MilestoneTimestamp>,
+    latest: MilestoneTimestamp,
+) -> impl Iterator<Item = MilestoneTimestamp> {
+    const MAX_BUCKETS: usize = 30;
+    let (earliest_bucket, max_buckets) = match earliest.and_then(|earliest| earliest.bucket()) {
+        Some(earliest_bucket) => (earliest_bucket, usize::MAX),
+        None => (0, MAX_BUCKETS),
+    };
+    std::iter::successors(
+        Some(MilestoneTimestamp(timestamp.0.min(latest.0))),
+        MilestoneTimestamp::previous_bucket,
+    )
+    .take_while(move |current| current.bucket().map_or(false, |bucket| bucket >= earliest_bucket))
+    .take(max_buckets)
+}
+
+// Ranked below the static milestone routes, i.e. `/milestones/latest`
+#[get("/<keyspace>/milestones/<index>", rank = 2)]
+async fn get_milestone(
+    keyspace: String,
+    index: u32,
+    keyspaces: State<'_, HashSet<String>>,
+    cache: State<'_, ResponseCache>,
+    auth: Auth,
+) -> Result<Cacheable<ListenerResponse>, ListenerError> {
+    auth.keyspace(&keyspace)?;
+    if !keyspaces.contains(&keyspace) {
+        return Err(ListenerError::InvalidKeyspace(keyspace));
+    }
+    // Milestones are immutable once stored
+    let cache_key = format!("{}/milestones/{}", keyspace, index);
+    let milestone = match cache.get::<Milestone>(&cache_key) {
+        Some(milestone) => milestone,
+        None => {
+            let milestone = query::<Milestone, _, _>(
+                ChronicleKeyspace::new(keyspace),
+                MilestoneIndex::from(index),
+                None,
+                None,
+            )
+            .await?;
+            cache.insert(cache_key, milestone.clone());
+            milestone
+        }
+    };
+    let message_id = milestone.message_id().to_string();
+    Ok(Cacheable::new(
+        ListenerResponse::Milestone {
+            milestone_index: index,
+            message_id: message_id.clone(),
+            timestamp: milestone.timestamp(),
+        },
+        Some(message_id),
+        &auth,
+    ))
+}
+
+#[get("/<keyspace>/milestones/<index>/messages?<page_size>&<state>")]
+async fn get_milestone_messages(
+    keyspace: String,
+    index: u32,
+    page_size: Option<usize>,
+    state: Option<String>,
+    partition_config: State<'_, PartitionConfig>,
+    api_config: State<'_, ApiConfig>,
+    cursor_key: State<'_, CursorKey>,
+    keyspaces: State<'_, HashSet<String>>,
+    auth: Auth,
+) -> ListenerResult {
+    auth.keyspace(&keyspace)?;
+    if !keyspaces.contains(&keyspace) {
+        return Err(ListenerError::InvalidKeyspace(keyspace));
+    }
+    let keyspace = ChronicleKeyspace::new(keyspace);
+
+    // Cursors are bound to the milestone they page through
+    let paging_state = match state
+        .map(|state| cursor_key.decode::<(u32, Vec<u8>)>(&state))
+        .transpose()?
+    {
+        Some((milestone_index, paging_state)) if milestone_index == index => Some(paging_state),
+        Some(_) => return Err(ListenerError::InvalidCursor),
+        None => None,
+    };
+    let page_size = page_size.unwrap_or(1000).min(api_config.max_page_size);
+    let partition_id = partition_config.partition_id(index);
+
+    let messages = query::<Paged<Vec<MessageId>>, _, _>(
+        keyspace,
+        Partitioned::new(MilestoneIndex(index), partition_id, index),
+        Some(page_size as i32),
+        paging_state,
+    )
+    .await?;
+
+    Ok(ListenerResponse::MessagesForMilestone {
+        milestone_index: index,
+        max_results: page_size,
+        count: messages.len(),
+        message_ids: messages.iter().map(|message_id| message_id.to_string()).collect(),
+        state: messages
+            .paging_state
+            .as_ref()
+            .map(|paging_state| cursor_key.encode(&(index, paging_state)))
+            .transpose()?,
+    })
+}
+
+#[get("/<keyspace>/events/milestones?<index>&<utf8>&<address>")]
+async fn get_milestone_events(
+    keyspace: String,
+    index: Option<String>,
+    utf8: Option<bool>,
+    address: Option<String>,
+    milestone_events: State<'_, MilestoneEventSender>,
+    storage_config: State<'_, StorageConfig>,
+    keyspaces: State<'_, HashSet<String>>,
+    auth: Auth,
+) -> Result<MilestoneEventStream, ListenerError> {
+    auth.keyspace(&keyspace)?;
+    if !keyspaces.contains(&keyspace) {
+        return Err(ListenerError::InvalidKeyspace(keyspace));
+    }
+    // Normalize the index to the lowercase hex encoding it is stored with
+    let index = index
+        .map(|index| {
+            let index = if let Some(true) = utf8 {
+                index.into_bytes()
+            } else {
+                Vec::<u8>::from_hex(index).map_err(|_| ListenerError::InvalidHex)?
+            };
+            if index.len() > 64 {
+                return Err(ListenerError::IndexTooLarge);
+            }
+            Ok(hex::encode(index))
+        })
+        .transpose()?;
+    // The address may be either hex encoded or bech32
+    let address = address
+        .map(|address| {
+            Ed25519Address::from_str(&address).or_else(|_| parse_bech32(&storage_config, &keyspace, &address))
+        })
+        .transpose()?;
+    Ok(MilestoneEventStream::new(
+        milestone_events.subscribe(),
+        MilestoneEventFilter {
+            keyspace,
+            index,
+            address,
+        },
+    ))
+}
+
+#[get("/<keyspace>/receipts?<page_size>&<state>")]
+async fn get_receipts(
+    keyspace: String,
+    page_size: Option<usize>,
+    state: Option<String>,
+    api_config: State<'_, ApiConfig>,
+    cursor_key: State<'_, CursorKey>,
+    keyspaces: State<'_, HashSet<String>>,
+    auth: Auth,
+) -> ListenerResult {
+    auth.keyspace(&keyspace)?;
+    if !keyspaces.contains(&keyspace) {
+        return Err(ListenerError::InvalidKeyspace(keyspace));
+    }
+    receipts(
+        ChronicleKeyspace::new(keyspace),
+        Synckey,
+        None,
+        page_size,
+        state,
+        &api_config,
+        &cursor_key,
+    )
+    .await
+}
+
+#[get("/<keyspace>/receipts/<migrated_at>?<page_size>&<state>")]
+async fn get_receipts_by_migrated_at(
+    keyspace: String,
+    migrated_at: u32,
+    page_size: Option<usize>,
+    state: Option<String>,
+    api_config: State<'_, ApiConfig>,
+    cursor_key: State<'_, CursorKey>,
+    keyspaces: State<'_, HashSet<String>>,
+    auth: Auth,
+) -> ListenerResult {
+    auth.keyspace(&keyspace)?;
+    if !keyspaces.contains(&keyspace) {
+        return Err(ListenerError::InvalidKeyspace(keyspace));
+    }
+    receipts(
+        ChronicleKeyspace::new(keyspace),
+        MigratedAt(migrated_at),
+        Some(migrated_at),
+        page_size,
+        state,
+        &api_config,
+        &cursor_key,
+    )
+    .await
+}
+
+/// Get a page of the receipts of the given key. The cursors are bound to the legacy milestone index the
+/// receipts are filtered by, if any.
+async fn receipts<K>(
+    keyspace: ChronicleKeyspace,
+    key: K,
+    migrated_at: Option<u32>,
+    page_size: Option<usize>,
+    state: Option<String>,
+    api_config: &ApiConfig,
+    cursor_key: &CursorKey,
+) -> ListenerResult
+where
+    ChronicleKeyspace: Select<K, Paged<Vec<ReceiptRecord>>>,
+    K: 'static + Send + Clone,
+{
+    let paging_state = match state
+        .map(|state| cursor_key.decode::<(Option<u32>, Vec<u8>)>(&state))
+        .transpose()?
+    {
+        Some((cursor_migrated_at, paging_state)) if cursor_migrated_at == migrated_at => Some(paging_state),
+        Some(_) => return Err(ListenerError::InvalidCursor),
+        None => None,
+    };
+    let page_size = page_size.unwrap_or(1000).min(api_config.max_page_size);
+    let mut records =
+        query::<Paged<Vec<ReceiptRecord>>, _, _>(keyspace, key, Some(page_size as i32), paging_state).await?;
+    Ok(ListenerResponse::Receipts {
+        max_results: page_size,
+        count: records.len(),
+        state: records
+            .paging_state
+            .as_ref()
+            .map(|paging_state| cursor_key.encode(&(migrated_at, paging_state)))
+            .transpose()?,
+        receipts: records.drain(..).map(Into::into).collect(),
+    })
+}
+
+#[get("/<keyspace>/receipts/tail-transactions/<tail_transaction_hash>")]
+async fn get_receipts_by_tail_transaction_hash(
+    keyspace: String,
+    tail_transaction_hash: String,
+    keyspaces: State<'_, HashSet<String>>,
+    auth: Auth,
+) -> ListenerResult {
+    auth.keyspace(&keyspace)?;
+    if !keyspaces.contains(&keyspace) {
+        return Err(ListenerError::InvalidKeyspace(keyspace));
+    }
+    // Legacy transaction hashes are 81 trytes
+    if tail_transaction_hash.len() != 81
+        || !tail_transaction_hash
+            .chars()
+            .all(|c| c == '9' || c.is_ascii_uppercase())
+    {
+        return Err(ListenerError::BadParse(anyhow!(
+            "Invalid tail transaction hash {}",
+            tail_transaction_hash
+        )));
+    }
+    let records = query::<Vec<MigratedFundsRecord>, _, _>(
+        ChronicleKeyspace::new(keyspace),
+        LegacyTailTransactionHash(tail_transaction_hash.clone()),
+        None,
+        None,
+    )
+    .await?;
+    Ok(ListenerResponse::MigratedFunds {
+        tail_transaction_hash,
+        entries: records.into_iter().map(Into::into).collect(),
+    })
+}
+
+#[get("/<keyspace>/treasury")]
+async fn get_treasury(keyspace: String, keyspaces: State<'_, HashSet<String>>, auth: Auth) -> ListenerResult {
+    auth.keyspace(&keyspace)?;
+    if !keyspaces.contains(&keyspace) {
+        return Err(ListenerError::InvalidKeyspace(keyspace));
+    }
+    let treasury = query::<TreasuryRecord, _, _>(ChronicleKeyspace::new(keyspace), Synckey, None, None).await?;
+    Ok(ListenerResponse::Treasury {
+        milestone_id: treasury.milestone_id.to_string(),
+        amount: treasury.amount,
+    })
+}
+
+#[get("/<keyspace>/analytics?<start>&<end>&<bucket>")]
+async fn get_analytics(
+    keyspace: String,
+    start: Option<u32>,
+    end: Option<u32>,
+    bucket: Option<String>,
+    keyspaces: State<'_, HashSet<String>>,
+    auth: Auth,
+) -> ListenerResult {
+    auth.keyspace(&keyspace)?;
+    if !keyspaces.contains(&keyspace) {
+        return Err(ListenerError::InvalidKeyspace(keyspace));
+    }
+    let bucket_secs = match bucket.as_deref() {
+        None => None,
+        Some("hour") => Some(60 * 60),
+        Some("day") => Some(24 * 60 * 60),
+        Some("week") => Some(7 * 24 * 60 * 60),
+        Some(bucket) => {
+            return Err(ListenerError::BadParse(anyhow!(
+                "Invalid bucket {}, expected hour, day or week",
+                bucket
+            )))
+        }
+    };
+    let keyspace = ChronicleKeyspace::new(keyspace);
+
+    let range = start.unwrap_or(1)..end.unwrap_or(i32::MAX as u32);
+
+    if let (Some(bucket), Some(bucket_secs)) = (bucket, bucket_secs) {
+        // Every milestone of the range is read at once, so the range must be bounded
+        let range = match (start, end) {
+            (Some(start), Some(end)) if start < end && end - start <= MAX_BUCKETED_MILESTONES => start..end,
+            _ => {
+                return Err(ListenerError::BadParse(anyhow!(
+                    "Bucketed analytics require a start and an end at most {} milestones apart",
+                    MAX_BUCKETED_MILESTONES
+                )))
+            }
+        };
+        let sync_range: SyncRange = range.into();
+        let (records, extended) = futures::try_join!(
+            AnalyticsData::try_fetch_records(&keyspace, &sync_range, 1, 5000),
+            ExtendedAnalyticsData::try_fetch_records(&keyspace, &sync_range, 1, 5000)
+        )?;
+        let (buckets, missing) = analytic_buckets(keyspace, records, extended, bucket_secs).await?;
+        return Ok(ListenerResponse::AnalyticsBuckets {
+            bucket,
+            buckets,
+            missing,
+        });
+    }
+
+    let sync_range: SyncRange = range.into();
+    let ranges = AnalyticsData::try_fetch(&keyspace, &sync_range, 1, 5000)
+        .await?
+        .analytics;
+    let extended = ExtendedAnalyticsData::try_fetch(&keyspace, &sync_range, 1, 5000).await?;
+
+    Ok(ListenerResponse::Analytics { ranges, extended })
+}
+
+/// The maximum number of milestones aggregated into time buckets by a single request
+const MAX_BUCKETED_MILESTONES: u32 = 100_000;
+
+/// The number of records tried from either end of the range to find the time span it covers
+const MAX_TIMESTAMP_ATTEMPTS: usize = 16;
+
+/// Aggregate the analytic and extended analytic records of milestones into time buckets of the given
+/// length (in seconds), along with the ranges of milestones whose timestamps are unknown
+async fn analytic_buckets(
+    keyspace: ChronicleKeyspace,
+    mut records: Vec<AnalyticRecord>,
+    extended: Vec<ExtendedAnalyticRecord>,
+    bucket_secs: u64,
+) -> Result<(Vec<AnalyticBucket>, Vec<Range<u32>>), ListenerError> {
+    records.sort_by_key(|record| **record.milestone_index());
+    // Find the time span of the records, skipping milestones which are not stored
+    let indexes = records
+        .iter()
+        .map(|record| **record.milestone_index())
+        .collect::<Vec<_>>();
+    let first_timestamp = first_timestamp(&keyspace, indexes.iter().take(MAX_TIMESTAMP_ATTEMPTS)).await?;
+    let last_timestamp = first_timestamp(&keyspace, indexes.iter().rev().take(MAX_TIMESTAMP_ATTEMPTS)).await?;
+    let timestamps = match (first_timestamp, last_timestamp) {
+        (Some(first_timestamp), Some(last_timestamp)) => {
+            // Look up the timestamps of the milestones in every (daily) time bucket of the table
+            let day_buckets = (first_timestamp / MilestoneTimestamp::BUCKET_SECS)
+                ..=(last_timestamp / MilestoneTimestamp::BUCKET_SECS);
+            futures::future::try_join_all(day_buckets.map(|day| {
+                let end_of_day = MilestoneTimestamp((day + 1) * MilestoneTimestamp::BUCKET_SECS - 1);
+                query::<Vec<(MilestoneIndex, MilestoneTimestamp)>, _, _>(keyspace.clone(), end_of_day, None, None)
+            }))
+            .await?
+            .into_iter()
+            .flatten()
+            .map(|(index, timestamp)| (index.0, timestamp.0))
+            .collect::<HashMap<u32, u64>>()
+        }
+        _ => HashMap::new(),
+    };
+    Ok(aggregate_buckets(records, extended, &timestamps, bucket_secs))
+}
+
+/// Get the timestamp of the first of the given milestones which is stored
+async fn first_timestamp(
+    keyspace: &ChronicleKeyspace,
+    indexes: impl Iterator<Item = &u32>,
+) -> Result<Option<u64>, ListenerError> {
+    for index in indexes {
+        match query::<Milestone, _, _>(keyspace.clone(), MilestoneIndex(*index), None, None).await {
+            Ok(milestone) => return Ok(Some(milestone.timestamp())),
+            Err(ListenerError::NoResults) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(None)
+}
+
+/// Aggregate the analytic records of milestones, sorted by index, and their extended analytic records
+/// into time buckets of the given length (in seconds). Milestones without a known timestamp are left
+/// out of the buckets and reported as ranges instead.
+fn aggregate_buckets(
+    records: Vec<AnalyticRecord>,
+    extended: Vec<ExtendedAnalyticRecord>,
+    timestamps: &HashMap<u32, u64>,
+    bucket_secs: u64,
+) -> (Vec<AnalyticBucket>, Vec<Range<u32>>) {
+    let mut extended = extended
+        .into_iter()
+        .map(|record| (record.milestone_index.0, record))
+        .collect::<HashMap<_, _>>();
+    let mut buckets = BTreeMap::<u64, (AnalyticBucket, u64, u32)>::new();
+    let mut missing: Vec<Range<u32>> = Vec::new();
+    let mut previous: Option<(u32, u64)> = None;
+    for record in records {
+        let index = **record.milestone_index();
+        let timestamp = match timestamps.get(&index) {
+            Some(timestamp) => *timestamp,
+            None => {
+                match missing.last_mut() {
+                    Some(range) if range.end == index => range.end += 1,
+                    _ => missing.push(index..index + 1),
+                }
+                previous = None;
+                continue;
+            }
+        };
+        let start = timestamp / bucket_secs * bucket_secs;
+        let (bucket, interval_sum, interval_count) = buckets.entry(start).or_insert_with(|| {
+            (
+                AnalyticBucket {
+                    start,
+                    ..Default::default()
+                },
+                0,
+                0,
+            )
+        });
+        bucket.milestone_count += 1;
+        bucket.message_count += **record.message_count() as u128;
+        bucket.transaction_count += **record.transaction_count() as u128;
+        bucket.transferred_tokens += **record.transferred_tokens() as u128;
+        if let Some(extended) = extended.remove(&index) {
+            bucket.extended.add(extended);
+        }
+        // Only consecutive milestones within the same bucket count towards its intervals
+        if let Some((previous_index, previous_timestamp)) = previous {
+            if previous_index + 1 == index && previous_timestamp >= start {
+                let interval = timestamp.saturating_sub(previous_timestamp);
+                bucket.min_milestone_interval =
+                    Some(bucket.min_milestone_interval.map_or(interval, |min| min.min(interval)));
+                bucket.max_milestone_interval =
+                    Some(bucket.max_milestone_interval.map_or(interval, |max| max.max(interval)));
+                *interval_sum += interval;
+                *interval_count += 1;
+            }
+        }
+        previous = Some((index, timestamp));
+    }
+    let buckets = buckets
+        .into_iter()
+        .map(|(_, (mut bucket, interval_sum, interval_count))| {
+            if interval_count > 0 {
+                bucket.avg_milestone_interval = Some(interval_sum as f64 / interval_count as f64);
+            }
+            bucket
+        })
+        .collect();
+    (buckets, missing)
+}
+
+#[catch(500)]
+fn internal_error() -> ListenerError {
+    ListenerError::Other(anyhow!("Internal server error!"))
+}
+
+#[catch(404)]
+fn not_found() -> ListenerError {
+    ListenerError::NotFound
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chronicle_common::config::{
+        ApiKeyConfig,
+        ApiScope,
+        KeyspaceConfig,
+        RateLimitConfig,
+        StorageConfig,
+    };
+    use rocket::{
+        http::{
+            ContentType,
+            Header,
+            Status,
+        },
+        local::asynchronous::{
+            Client,
+            LocalResponse,
+        },
+    };
+    use serde_json::Value;
+
+    fn check_cors_headers(res: &LocalResponse) {
+        assert_eq!(
+            res.headers().get_one("Access-Control-Allow-Origin"),
+            Some(Header::new("Access-Control-Allow-Origin", "*").value())
+        );
+        assert_eq!(
+            res.headers().get_one("Access-Control-Allow-Methods"),
+            Some(Header::new("Access-Control-Allow-Methods", "GET, POST, OPTIONS").value())
+        );
+        assert_eq!(
+            res.headers().get_one("Access-Control-Allow-Headers"),
+            Some(Header::new("Access-Control-Allow-Headers", "*").value())
+        );
+        assert_eq!(
+            res.headers().get_one("Access-Control-Allow-Credentials"),
+            Some(Header::new("Access-Control-Allow-Credentials", "true").value())
+        );
+    }
+
+    #[rocket::async_test]
+    async fn options() {
+        let rocket = construct_rocket(rocket::ignite());
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
+
+        let res = client.options("/api/anything").dispatch().await;
+        assert_eq!(res.status(), Status::Ok);
+        assert_eq!(res.content_type(), None);
+        check_cors_headers(&res);
+        assert!(res.into_string().await.is_none());
+    }
+
+    #[rocket::async_test]
+    async fn info() {
+        let rocket = construct_rocket(
+            rocket::ignite()
+                .manage(StorageConfig::default())
+                .manage(ApiConfig::default()),
+        );
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
+
+        let res = client.get("/api/info").dispatch().await;
+        assert_eq!(res.status(), Status::Ok);
+        assert_eq!(res.content_type(), Some(ContentType::JSON));
+        check_cors_headers(&res);
+        let body: SuccessBody<ListenerResponse> =
+            serde_json::from_str(&res.into_string().await.expect("No body returned!"))
+                .expect("Failed to deserialize Info Response!");
+        match *body {
+            ListenerResponse::Info {
+                is_healthy,
+                ref network_id,
+                ref bech32_hrp,
+                ..
+            } => {
+                // Nothing can be synced without a scylla ring
+                assert!(!is_healthy);
+                assert_eq!(network_id, "chrysalis-mainnet");
+                assert_eq!(bech32_hrp, "iota");
+            }
+            _ => panic!("Did not receive an info response!"),
+        }
+    }
+
+    #[get("/cacheable?<public>")]
+    fn cacheable(public: bool) -> Cacheable<&'static str> {
+        let auth = if public {
+            Auth::Public
+        } else {
+            Auth::Scopes(HashSet::new())
+        };
+        Cacheable::new("immutable", Some("tag".to_owned()), &auth)
+    }
+
+    #[rocket::async_test]
+    async fn cacheable_responses() {
+        let rocket = rocket::ignite().mount("/", routes![cacheable]);
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
+
+        let res = client.get("/cacheable?public=true").dispatch().await;
+        assert_eq!(res.status(), Status::Ok);
+        assert_eq!(res.headers().get_one("ETag"), Some("\"tag\""));
+        assert_eq!(
+            res.headers().get_one("Cache-Control"),
+            Some("public, max-age=31536000, immutable")
+        );
+        assert_eq!(res.headers().get_one("Vary"), None);
+        assert_eq!(res.into_string().await.as_deref(), Some("immutable"));
+
+        // Responses which required authentication must not be shared by caches
+        let res = client.get("/cacheable?public=false").dispatch().await;
+        assert_eq!(res.status(), Status::Ok);
+        assert_eq!(
+            res.headers().get_one("Cache-Control"),
+            Some("private, max-age=31536000, immutable")
+        );
+        assert_eq!(res.headers().get_one("Vary"), Some("Authorization"));
+
+        let res = client
+            .get("/cacheable?public=true")
+            .header(Header::new("If-None-Match", "\"other\", \"tag\""))
+            .dispatch()
+            .await;
+        assert_eq!(res.status(), Status::NotModified);
+        assert_eq!(res.headers().get_one("ETag"), Some("\"tag\""));
+        assert!(res.into_string().await.is_none());
+
+        let res = client
+            .get("/cacheable?public=true")
+            .header(Header::new("If-None-Match", "\"other\""))
+            .dispatch()
+            .await;
+        assert_eq!(res.status(), Status::Ok);
+        assert_eq!(res.into_string().await.as_deref(), Some("immutable"));
+    }
+
+    #[rocket::async_test]
+    async fn info_of_the_hornet_keyspace() {
+        let mut storage_config = StorageConfig::default();
+        storage_config.keyspaces.push(KeyspaceConfig {
+            name: "testnet".to_owned(),
+            bech32_hrp: "atoi".to_owned(),
+            network_id: "testnet7".to_owned(),
+            ..Default::default()
+        });
+        let rocket = construct_rocket(rocket::ignite().manage(storage_config).manage(ApiConfig {
+            hornet_keyspace: Some("testnet".to_owned()),
+            ..Default::default()
+        }));
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
+
+        let res = client.get("/api/info").dispatch().await;
+        assert_eq!(res.status(), Status::Ok);
+        let body: SuccessBody<ListenerResponse> =
+            serde_json::from_str(&res.into_string().await.expect("No body returned!"))
+                .expect("Failed to deserialize Info Response!");
+        match *body {
+            ListenerResponse::Info {
+                is_healthy,
+                ref network_id,
+                ref bech32_hrp,
+                latest_milestone_index,
+                pruning_index,
+                ..
+            } => {
+                // An unreachable keyspace reports nothing synced rather than failing
+                assert!(!is_healthy);
+                assert_eq!(network_id, "testnet7");
+                assert_eq!(bech32_hrp, "atoi");
+                assert_eq!(latest_milestone_index, 0);
+                assert_eq!(pruning_index, 0);
+            }
+            _ => panic!("Did not receive an info response!"),
+        }
+    }
+
+    #[rocket::async_test]
+    async fn health() {
+        let rocket = construct_rocket(rocket::ignite().manage(StorageConfig::default()));
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
+
+        let res = client.get("/api/health/live").dispatch().await;
+        assert_eq!(res.status(), Status::Ok);
+
+        let res = client.get("/api/health/ready").dispatch().await;
+        assert_eq!(res.status(), Status::ServiceUnavailable);
+        assert_eq!(res.content_type(), Some(ContentType::JSON));
+        check_cors_headers(&res);
+    }
+
+    #[rocket::async_test]
+    async fn service() {
+        let rocket = construct_rocket(rocket::ignite().manage(Authenticator::default()));
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
+
+        let res = client.get("/api/service").dispatch().await;
+        assert_eq!(res.status(), Status::Ok);
+        assert_eq!(res.content_type(), Some(ContentType::JSON));
+        check_cors_headers(&res);
+        let _body: Service = serde_json::from_str(&res.into_string().await.expect("No body returned!"))
+            .expect("Failed to deserialize Service Response!");
+    }
+
+    fn authenticator(scopes: HashSet<ApiScope>) -> Authenticator {
+        Authenticator::from(&ApiConfig {
+            api_keys: vec![ApiKeyConfig {
+                key: "secret".to_owned(),
+                scopes,
+            }],
+            ..Default::default()
+        })
+    }
+
+    #[rocket::async_test]
+    async fn service_unauthorized() {
+        let scopes = vec![ApiScope::Keyspace("permanode".to_owned())].into_iter().collect();
+        let rocket = construct_rocket(rocket::ignite().manage(authenticator(scopes)));
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
+
+        let res = client.get("/api/service").dispatch().await;
+        assert_eq!(res.status(), Status::Unauthorized);
+        assert_eq!(res.content_type(), Some(ContentType::JSON));
+        assert_eq!(res.headers().get_one("WWW-Authenticate"), Some("Bearer"));
+        check_cors_headers(&res);
+
+        let res = client
+            .get("/api/service")
+            .header(Header::new("Authorization", "Bearer wrong"))
+            .dispatch()
+            .await;
+        assert_eq!(res.status(), Status::Unauthorized);
+
+        let res = client
+            .get("/api/service")
+            .header(Header::new("Authorization", "Bearer secret"))
+            .dispatch()
+            .await;
+        assert_eq!(res.status(), Status::Forbidden);
+        assert_eq!(res.content_type(), Some(ContentType::JSON));
+        check_cors_headers(&res);
+    }
+
+    #[rocket::async_test]
+    async fn service_rate_limited() {
+        let rocket = construct_rocket(
+            rocket::ignite()
+                .attach(RateLimiter::new(
+                    RateLimitConfig {
+                        requests_per_second: 1,
+                        burst: 1,
+                    },
+                    Authenticator::default(),
+                ))
+                .manage(Authenticator::default()),
+        );
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
+
+        let res = client.get("/api/service").dispatch().await;
+        assert_eq!(res.status(), Status::Ok);
+
+        let res = client.get("/api/service").dispatch().await;
+        assert_eq!(res.status(), Status::TooManyRequests);
+        assert_eq!(res.content_type(), Some(ContentType::JSON));
+        assert_eq!(res.headers().get_one("Retry-After"), Some("1"));
+        check_cors_headers(&res);
+    }
+
+    #[rocket::async_test]
+    async fn public_routes_rate_limited() {
+        let rocket = construct_rocket(
+            rocket::ignite()
+                .attach(RateLimiter::new(
+                    RateLimitConfig {
+                        requests_per_second: 1,
+                        burst: 1,
+                    },
+                    Authenticator::default(),
+                ))
+                .manage(StorageConfig::default())
+                .manage(ApiConfig::default()),
+        );
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
+
+        let res = client.get("/api/health/live").dispatch().await;
+        assert_eq!(res.status(), Status::Ok);
+
+        for path in ["/api/health/live", "/api/health/ready", "/api/info"].iter() {
+            let res = client.get(*path).dispatch().await;
+            assert_eq!(res.status(), Status::TooManyRequests);
+            assert_eq!(res.content_type(), Some(ContentType::JSON));
+            assert_eq!(res.headers().get_one("Retry-After"), Some("1"));
+            check_cors_headers(&res);
+        }
+    }
+
+    #[rocket::async_test]
+    async fn rate_limit_by_api_key() {
+        let scopes: HashSet<ApiScope> = vec![ApiScope::Monitoring].into_iter().collect();
+        let rocket = construct_rocket(
+            rocket::ignite()
+                .attach(RateLimiter::new(
+                    RateLimitConfig {
+                        requests_per_second: 1,
+                        burst: 1,
+                    },
+                    authenticator(scopes.clone()),
+                ))
+                .manage(authenticator(scopes)),
+        );
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
+
+        // Made up tokens do not get buckets of their own, so they share the bucket of the client address
+        let res = client
+            .get("/api/service")
+            .header(Header::new("Authorization", "Bearer made-up"))
+            .dispatch()
+            .await;
+        assert_eq!(res.status(), Status::Unauthorized);
+        let res = client
+            .get("/api/service")
+            .header(Header::new("Authorization", "Bearer another-made-up"))
+            .dispatch()
+            .await;
+        assert_eq!(res.status(), Status::TooManyRequests);
+
+        // While a valid key has a bucket of its own
+        let res = client
+            .get("/api/service")
+            .header(Header::new("Authorization", "Bearer secret"))
+            .dispatch()
+            .await;
+        assert_eq!(res.status(), Status::Ok);
+        let res = client
+            .get("/api/service")
+            .header(Header::new("Authorization", "Bearer secret"))
+            .dispatch()
+            .await;
+        assert_eq!(res.status(), Status::TooManyRequests);
+    }
+
+    #[rocket::async_test]
+    async fn get_message_forbidden() {
+        let storage_config = StorageConfig::default();
+        let keyspaces = storage_config
+            .keyspaces
+            .iter()
+            .cloned()
+            .map(|k| k.name)
+            .collect::<HashSet<_>>();
+        let scopes = vec![ApiScope::Monitoring].into_iter().collect();
+        let rocket = construct_rocket(
+            rocket::ignite()
+                .manage(storage_config.partition_config.clone())
+                .manage(keyspaces)
+                .manage(authenticator(scopes))
+                .manage(ResponseCache::new(0)),
+        );
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
+
+        let res = client
+            .get("/api/service")
+            .header(Header::new("Authorization", "Bearer secret"))
+            .dispatch()
+            .await;
+        assert_eq!(res.status(), Status::Ok);
+
+        let res = client
+            .get("/api/permanode/messages/91515c13d2025f79ded3758abe5dc640591c3b6d58b1c52cd51d1fa0585774bc")
+            .header(Header::new("Authorization", "Bearer secret"))
+            .dispatch()
+            .await;
+        assert_eq!(res.status(), Status::Forbidden);
+        assert_eq!(res.content_type(), Some(ContentType::JSON));
+        check_cors_headers(&res);
+        let body: Value = serde_json::from_str(&res.into_string().await.expect("No body returned!"))
+            .expect("Failed to deserialize response!");
+        assert_eq!(
+            body.get("message").and_then(Value::as_str),
+            Some("The provided API key or bearer token does not grant access to this resource!")
+        );
+    }
+
+    #[rocket::async_test]
+    async fn get_message() {
+        let storage_config = StorageConfig::default();
+        let keyspaces = storage_config
+            .keyspaces
+            .iter()
+            .cloned()
+            .map(|k| k.name)
+            .collect::<HashSet<_>>();
+        let rocket = construct_rocket(
+            rocket::ignite()
+                .manage(storage_config.partition_config.clone())
+                .manage(keyspaces)
+                .manage(Authenticator::default())
+                .manage(ResponseCache::new(0)),
+        );
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
+
+        let res = client
+            .get("/api/permanode/messages/91515c13d2025f79ded3758abe5dc640591c3b6d58b1c52cd51d1fa0585774bc")
+            .dispatch()
+            .await;
+        assert_eq!(res.status(), Status::InternalServerError);
+        assert_eq!(res.content_type(), Some(ContentType::JSON));
+        assert_eq!(res.headers().get_one("ETag"), None);
+        assert_eq!(res.headers().get_one("Cache-Control"), None);
+        check_cors_headers(&res);
+        let body: Value = serde_json::from_str(&res.into_string().await.expect("No body returned!"))
+            .expect("Failed to deserialize response!");
+        assert_eq!(body.get("message").and_then(Value::as_str), Some("Worker NoRing"));
+    }
+
+    #[rocket::async_test]
+    async fn get_message_raw_invalid_id() {
+        let storage_config = StorageConfig::default();
+        let keyspaces = storage_config
+            .keyspaces
+            .iter()
+            .cloned()
+            .map(|k| k.name)
+            .collect::<HashSet<_>>();
+        let rocket = construct_rocket(
+            rocket::ignite()
+                .manage(storage_config.partition_config.clone())
+                .manage(keyspaces)
+                .manage(Authenticator::default())
+                .manage(ResponseCache::new(0)),
+        );
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
+
+        let res = client
+            .get("/api/permanode/messages/not_a_message_id/raw")
+            .dispatch()
+            .await;
+        assert_eq!(res.status(), Status::BadRequest);
+        assert_eq!(res.content_type(), Some(ContentType::JSON));
+        check_cors_headers(&res);
+    }
+
+    #[rocket::async_test]
+    async fn get_milestone_events_invalid_index() {
+        let storage_config = StorageConfig::default();
+        let keyspaces = storage_config
+            .keyspaces
+            .iter()
+            .cloned()
+            .map(|k| k.name)
+            .collect::<HashSet<_>>();
+        let (milestone_events, _) = tokio::sync::broadcast::channel(1);
+        let rocket = construct_rocket(
+            rocket::ignite()
+                .manage(keyspaces)
+                .manage(storage_config)
+                .manage::<MilestoneEventSender>(milestone_events)
+                .manage(Authenticator::default()),
+        );
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
+
+        let res = client
+            .get("/api/permanode/events/milestones?index=not_hex")
+            .dispatch()
+            .await;
+        assert_eq!(res.status(), Status::BadRequest);
+        assert_eq!(res.content_type(), Some(ContentType::JSON));
+        check_cors_headers(&res);
+        let body: Value = serde_json::from_str(&res.into_string().await.expect("No body returned!"))
+            .expect("Failed to deserialize response!");
+        assert_eq!(
+            body.get("message").and_then(Value::as_str),
+            Some("Invalid hexidecimal encoding!")
+        );
+    }
+
+    #[rocket::async_test]
+    async fn get_hornet_message_raw_invalid_id() {
+        let storage_config = StorageConfig::default();
+        let keyspaces = storage_config
+            .keyspaces
+            .iter()
+            .cloned()
+            .map(|k| k.name)
+            .collect::<HashSet<_>>();
+        let rocket = construct_rocket(
+            rocket::ignite()
+                .attach(HornetFacade::new("permanode".to_owned()))
+                .manage(storage_config.partition_config.clone())
+                .manage(keyspaces)
+                .manage(storage_config)
+                .manage(ApiConfig::default())
+                .manage(Authenticator::default())
+                .manage(ResponseCache::new(0)),
+        );
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
+
+        let res = client.get("/api/v1/messages/not_a_message_id/raw").dispatch().await;
+        assert_eq!(res.status(), Status::BadRequest);
+        assert_eq!(res.content_type(), Some(ContentType::JSON));
+        check_cors_headers(&res);
+
+        let res = client.get("/api/v1/info").dispatch().await;
+        assert_eq!(res.status(), Status::Ok);
+    }
+
+    #[test]
+    fn hornet_responses() {
+        let balance = serde_json::json!({
+            "addressType": 1,
+            "address": "iota1qq",
+            "balance": 10,
+            "dustAllowanceBalance": 0,
+            "outputCount": 1,
+            "dustAllowed": false,
+            "ledgerIndex": 5,
+        });
+        assert_eq!(
+            HornetResponse::Balance.convert(balance),
+            serde_json::json!({
+                "addressType": 0,
+                "address": "iota1qq",
+                "balance": 10,
+                "dustAllowed": false,
+                "ledgerIndex": 5,
+            })
+        );
+        let outputs = serde_json::json!({
+            "addressType": 1,
+            "address": "iota1qq",
+            "maxResults": 200,
+            "count": 0,
+            "outputIds": [],
+            "state": null,
+        });
+        assert_eq!(
+            HornetResponse::Outputs.convert(outputs.clone())["addressType"],
+            serde_json::json!(0)
+        );
+        let children = serde_json::json!({
+            "messageId": "00",
+            "maxResults": 200,
+            "count": 0,
+            "childrenMessageIds": [],
+            "state": null,
+        });
+        assert_eq!(
+            HornetResponse::Children.convert(children),
+            serde_json::json!({
+                "messageId": "00",
+                "maxResults": 200,
+                "count": 0,
+                "childrenMessageIds": [],
+            })
+        );
+        assert_eq!(HornetResponse::Unchanged.convert(outputs.clone()), outputs);
+    }
+
+    #[rocket::async_test]
+    async fn get_sync_invalid_range() {
+        let storage_config = StorageConfig::default();
+        let keyspaces = storage_config
+            .keyspaces
+            .iter()
+            .cloned()
+            .map(|k| k.name)
+            .collect::<HashSet<_>>();
+        let rocket = construct_rocket(rocket::ignite().manage(keyspaces).manage(Authenticator::default()));
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
+
+        let res = client.get("/api/permanode/sync?from=100&to=50").dispatch().await;
+        assert_eq!(res.status(), Status::BadRequest);
+        assert_eq!(res.content_type(), Some(ContentType::JSON));
+        check_cors_headers(&res);
+
+        let res = client.get("/api/permanode/sync?from=0&gaps_only=true").dispatch().await;
+        assert_eq!(res.status(), Status::BadRequest);
+    }
+
+    #[get("/sync")]
+    fn full_sync() -> SyncResponse {
+        SyncResponse::Full(SyncData::default())
+    }
+
+    #[rocket::async_test]
+    async fn get_sync_without_parameters() {
+        let rocket = rocket::ignite().mount("/", routes![full_sync]);
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
+
+        // The full sync data keeps its bare shape
+        let res = client.get("/sync").dispatch().await;
+        assert_eq!(res.status(), Status::Ok);
+        let body: Value = serde_json::from_str(&res.into_string().await.expect("No body returned!"))
+            .expect("Failed to deserialize Sync Response!");
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "completed": [],
+                "synced_but_unlogged": [],
+                "gaps": [],
+            })
+        );
+    }
+
+    #[rocket::async_test]
+    async fn get_analytics_invalid_bucket() {
+        let storage_config = StorageConfig::default();
+        let keyspaces = storage_config
+            .keyspaces
+            .iter()
+            .cloned()
+            .map(|k| k.name)
+            .collect::<HashSet<_>>();
+        let rocket = construct_rocket(rocket::ignite().manage(keyspaces).manage(Authenticator::default()));
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
+
+        let res = client.get("/api/permanode/analytics?bucket=month").dispatch().await;
+        assert_eq!(res.status(), Status::BadRequest);
+        assert_eq!(res.content_type(), Some(ContentType::JSON));
+        check_cors_headers(&res);
+    }
+
+    #[rocket::async_test]
+    async fn get_analytics_unbounded_buckets() {
+        let storage_config = StorageConfig::default();
+        let keyspaces = storage_config
+            .keyspaces
+            .iter()
+            .cloned()
+            .map(|k| k.name)
+            .collect::<HashSet<_>>();
+        let rocket = construct_rocket(rocket::ignite().manage(keyspaces).manage(Authenticator::default()));
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
+
+        for query in [
+            "bucket=day",
+            "bucket=day&start=1",
+            "bucket=day&start=10&end=5",
+            "bucket=day&start=1&end=1000000",
+        ]
+        .iter()
+        {
+            let res = client
+                .get(format!("/api/permanode/analytics?{}", query))
+                .dispatch()
+                .await;
+            assert_eq!(res.status(), Status::BadRequest);
+            assert_eq!(res.content_type(), Some(ContentType::JSON));
+        }
+    }
+
+    fn analytic_record(index: u32, message_count: u32) -> AnalyticRecord {
+        AnalyticRecord::new(
+            MilestoneIndex(index),
+            MessageCount(message_count),
+            TransactionCount(1),
+            TransferredTokens(10),
+        )
+    }
+
+    fn extended_analytic_record(index: u32, transaction_payloads: u32) -> ExtendedAnalyticRecord {
+        let mut record = ExtendedAnalyticRecord::new(MilestoneIndex(index));
+        record.transaction_payloads = transaction_payloads;
+        record.sending_addresses = 1;
+        record.conflicts.count(Some(1));
+        record
+    }
+
+    #[test]
+    fn aggregate_analytic_buckets() {
+        let records = (1..=6).map(|index| analytic_record(index, index)).collect();
+        // Milestone 4 has no known timestamp, milestone 5 opens the next bucket
+        let timestamps = vec![(1, 100), (2, 110), (3, 125), (5, 3600), (6, 3610)]
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+        let extended = vec![
+            extended_analytic_record(2, 3),
+            extended_analytic_record(3, 1),
+            extended_analytic_record(5, 2),
+        ];
+        let (buckets, missing) = aggregate_buckets(records, extended, &timestamps, 3600);
+        assert_eq!(missing, vec![4..5]);
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[0].start, 0);
+        assert_eq!(buckets[0].milestone_count, 3);
+        assert_eq!(buckets[0].message_count, 6);
+        assert_eq!(buckets[0].transaction_count, 3);
+        assert_eq!(buckets[0].transferred_tokens, 30);
+        assert_eq!(buckets[0].min_milestone_interval, Some(10));
+        assert_eq!(buckets[0].max_milestone_interval, Some(15));
+        assert_eq!(buckets[0].avg_milestone_interval, Some(12.5));
+        let extended = serde_json::to_value(&buckets[0].extended).unwrap();
+        assert_eq!(extended["transaction_payloads"], 4);
+        assert_eq!(extended["sending_addresses_per_milestone_sum"], 2);
+        assert_eq!(extended["conflicts"]["input_utxo_already_spent"], 2);
+        assert_eq!(buckets[1].start, 3600);
+        assert_eq!(buckets[1].milestone_count, 2);
+        assert_eq!(buckets[1].message_count, 11);
+        // The interval across the missing milestone and into the bucket is not counted
+        assert_eq!(buckets[1].min_milestone_interval, Some(10));
+        assert_eq!(buckets[1].max_milestone_interval, Some(10));
+        let extended = serde_json::to_value(&buckets[1].extended).unwrap();
+        assert_eq!(extended["transaction_payloads"], 2);
+        assert_eq!(extended["receiving_addresses_per_milestone_sum"], 0);
+
+        // Without any timestamp every milestone is reported missing
+        let records = vec![analytic_record(1, 1), analytic_record(2, 1), analytic_record(7, 1)];
+        let (buckets, missing) = aggregate_buckets(records, Vec::new(), &HashMap::new(), 3600);
+        assert!(buckets.is_empty());
+        assert_eq!(missing, vec![1..3, 7..8]);
+    }
+
+    #[rocket::async_test]
+    async fn get_receipts_invalid_tail_transaction_hash() {
+        let storage_config = StorageConfig::default();
+        let keyspaces = storage_config
+            .keyspaces
+            .iter()
+            .cloned()
+            .map(|k| k.name)
+            .collect::<HashSet<_>>();
+        let rocket = construct_rocket(rocket::ignite().manage(keyspaces).manage(Authenticator::default()));
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
+
+        let res = client
+            .get("/api/permanode/receipts/tail-transactions/not-a-hash")
+            .dispatch()
+            .await;
+        assert_eq!(res.status(), Status::BadRequest);
+        assert_eq!(res.content_type(), Some(ContentType::JSON));
+        check_cors_headers(&res);
     }
-    let keyspace = ChronicleKeyspace::new(keyspace);
 
-    let range = start.unwrap_or(1)..end.unwrap_or(i32::MAX as u32);
+    #[rocket::async_test]
+    async fn get_receipts_cursor_of_another_migrated_at() {
+        let storage_config = StorageConfig::default();
+        let keyspaces = storage_config
+            .keyspaces
+            .iter()
+            .cloned()
+            .map(|k| k.name)
+            .collect::<HashSet<_>>();
+        let cursor_key = CursorKey::new(None).unwrap();
+        let cursor = cursor_key.encode(&(Some(1u32), vec![0u8; 8])).unwrap();
+        let rocket = construct_rocket(
+            rocket::ignite()
+                .manage(keyspaces)
+                .manage(cursor_key)
+                .manage(ApiConfig::default())
+                .manage(Authenticator::default()),
+        );
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
 
-    let ranges = AnalyticsData::try_fetch(&keyspace, &range.into(), 1, 5000)
-        .await?
-        .analytics;
+        // A cursor only pages through the receipts it was issued for
+        for path in &["/api/permanode/receipts/2", "/api/permanode/receipts"] {
+            let res = client.get(format!("{}?state={}", path, cursor)).dispatch().await;
+            assert_eq!(res.status(), Status::BadRequest);
+            assert_eq!(res.content_type(), Some(ContentType::JSON));
+            check_cors_headers(&res);
+            let body: Value = serde_json::from_str(&res.into_string().await.expect("No body returned!"))
+                .expect("Failed to deserialize response!");
+            assert_eq!(
+                body.get("message").and_then(Value::as_str),
+                Some("Invalid cursor provided! It is malformed or was tampered with")
+            );
+        }
+    }
 
-    Ok(ListenerResponse::Analytics { ranges })
-}
+    #[rocket::async_test]
+    async fn post_messages_batch_too_large() {
+        let storage_config = StorageConfig::default();
+        let keyspaces = storage_config
+            .keyspaces
+            .iter()
+            .cloned()
+            .map(|k| k.name)
+            .collect::<HashSet<_>>();
+        let rocket = construct_rocket(
+            rocket::ignite()
+                .manage(storage_config.partition_config.clone())
+                .manage(keyspaces)
+                .manage(Authenticator::default())
+                .manage(ResponseCache::new(0)),
+        );
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
 
-#[catch(500)]
-fn internal_error() -> ListenerError {
-    ListenerError::Other(anyhow!("Internal server error!"))
-}
+        let message_ids = vec!["91515c13d2025f79ded3758abe5dc640591c3b6d58b1c52cd51d1fa0585774bc"; 101];
+        let res = client
+            .post("/api/permanode/messages/batch")
+            .header(ContentType::JSON)
+            .body(serde_json::to_string(&message_ids).unwrap())
+            .dispatch()
+            .await;
+        assert_eq!(res.status(), Status::BadRequest);
+        assert_eq!(res.content_type(), Some(ContentType::JSON));
+        check_cors_headers(&res);
+        let body: Value = serde_json::from_str(&res.into_string().await.expect("No body returned!"))
+            .expect("Failed to deserialize response!");
+        assert_eq!(
+            body.get("message").and_then(Value::as_str),
+            Some("Too many ids provided! (Max 100)")
+        );
+    }
 
-#[catch(404)]
-fn not_found() -> ListenerError {
-    ListenerError::NotFound
-}
+    #[rocket::async_test]
+    async fn get_message_children_tampered_cursor() {
+        let storage_config = StorageConfig::default();
+        let keyspaces = storage_config
+            .keyspaces
+            .iter()
+            .cloned()
+            .map(|k| k.name)
+            .collect::<HashSet<_>>();
+        let cursor_key = CursorKey::new(None).unwrap();
+        let mut cursor = base64::decode_config(
+            cursor_key.encode(&StateData::new(0, 0, Vec::new())).unwrap(),
+            base64::URL_SAFE_NO_PAD,
+        )
+        .unwrap();
+        cursor[1] ^= 1;
+        let cursor = base64::encode_config(cursor, base64::URL_SAFE_NO_PAD);
+        let rocket = construct_rocket(
+            rocket::ignite()
+                .manage(storage_config.partition_config.clone())
+                .manage(keyspaces)
+                .manage(cursor_key)
+                .manage(ApiConfig::default())
+                .manage(Authenticator::default()),
+        );
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use chronicle_common::config::StorageConfig;
-    use rocket::{
-        http::{
-            ContentType,
-            Header,
-            Status,
-        },
-        local::asynchronous::{
-            Client,
-            LocalResponse,
-        },
-    };
-    use serde_json::Value;
+        let res = client
+            .get(format!(
+                "/api/permanode/messages/91515c13d2025f79ded3758abe5dc640591c3b6d58b1c52cd51d1fa0585774bc/children?state={}",
+                cursor
+            ))
+            .dispatch()
+            .await;
+        assert_eq!(res.status(), Status::BadRequest);
+        assert_eq!(res.content_type(), Some(ContentType::JSON));
+        check_cors_headers(&res);
+        let body: Value = serde_json::from_str(&res.into_string().await.expect("No body returned!"))
+            .expect("Failed to deserialize response!");
+        assert_eq!(
+            body.get("message").and_then(Value::as_str),
+            Some("Invalid cursor provided! It is malformed or was tampered with")
+        );
+    }
 
-    fn check_cors_headers(res: &LocalResponse) {
+    #[rocket::async_test]
+    async fn get_bech32_outputs_invalid_hrp() {
+        let storage_config = StorageConfig::default();
+        let rocket = construct_rocket(
+            rocket::ignite()
+                .manage(storage_config.partition_config.clone())
+                .manage(storage_config)
+                .manage(CursorKey::new(None).unwrap())
+                .manage(ApiConfig::default())
+                .manage(Authenticator::default()),
+        );
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
+
+        let res = client
+            .get("/api/permanode/addresses/atoi1qpf0mlq8yxpx2nck8a0slxnzr4ef2ek8f5gqxlzd0wasgp73utryjjl77h3/outputs")
+            .dispatch()
+            .await;
+        assert_eq!(res.status(), Status::BadRequest);
+        assert_eq!(res.content_type(), Some(ContentType::JSON));
+        check_cors_headers(&res);
+        let body: Value = serde_json::from_str(&res.into_string().await.expect("No body returned!"))
+            .expect("Failed to deserialize response!");
         assert_eq!(
-            res.headers().get_one("Access-Control-Allow-Origin"),
-            Some(Header::new("Access-Control-Allow-Origin", "*").value())
+            body.get("message").and_then(Value::as_str),
+            Some("Invalid bech32 address HRP! Expected: iota, found: atoi")
+        );
+    }
+
+    #[test]
+    fn balance_tally_at_ledger_index() {
+        let dust_allowance = SignatureLockedDustAllowanceOutput::KIND;
+        let mut tally = BalanceTally::default();
+        // Unspent outputs count
+        tally.add(100, 0, None, Some(10));
+        tally.add(1_000_000, dust_allowance, None, Some(10));
+        // Outputs spent after the ledger index still count
+        tally.add(5, 0, Some(11), Some(10));
+        // Outputs spent at or before the ledger index do not
+        tally.add(7, 0, Some(10), Some(10));
+        tally.add(9, dust_allowance, Some(3), Some(10));
+        assert_eq!(
+            tally,
+            BalanceTally {
+                balance: 1_000_105,
+                dust_allowance_balance: 1_000_000,
+                output_count: 3,
+                dust_output_count: 2,
+            }
         );
+        assert!(tally.dust_allowed());
+        // Without a ledger index every spent output is excluded
+        let mut tally = BalanceTally::default();
+        tally.add(5, 0, Some(11), None);
+        tally.add(3, 0, None, None);
         assert_eq!(
-            res.headers().get_one("Access-Control-Allow-Methods"),
-            Some(Header::new("Access-Control-Allow-Methods", "GET, OPTIONS").value())
+            tally,
+            BalanceTally {
+                balance: 3,
+                dust_allowance_balance: 0,
+                output_count: 1,
+                dust_output_count: 1,
+            }
         );
+        // Dust needs an allowance
+        assert!(!tally.dust_allowed());
+        // Which covers one dust output per 100_000 tokens
+        tally.add(100_000, SignatureLockedDustAllowanceOutput::KIND, None, None);
+        assert!(!tally.dust_allowed());
+        tally.add(100_000, SignatureLockedDustAllowanceOutput::KIND, None, None);
+        assert!(tally.dust_allowed());
+    }
+
+    #[test]
+    fn balance_tally_of_a_ledger_balance() {
+        let tally = BalanceTally::from(BalanceRecord::new(MilestoneIndex(10), 1_000_105, 1_000_000, 3, 2));
         assert_eq!(
-            res.headers().get_one("Access-Control-Allow-Headers"),
-            Some(Header::new("Access-Control-Allow-Headers", "*").value())
+            tally,
+            BalanceTally {
+                balance: 1_000_105,
+                dust_allowance_balance: 1_000_000,
+                output_count: 3,
+                dust_output_count: 2,
+            }
         );
+        assert!(tally.dust_allowed());
+    }
+
+    #[test]
+    fn spending_messages_of_an_output() {
+        use bee_message::prelude::{
+            ReferenceUnlock,
+            UnlockBlock,
+        };
+        let unlock = |message_id: u8, inclusion_state| UnlockRes {
+            message_id: MessageId::new([message_id; 32]),
+            block: UnlockBlock::Reference(ReferenceUnlock::new(0).unwrap()),
+            inclusion_state,
+        };
+        // Conflicting spends never spent the output, so they cost no query
+        let message_ids = spending_message_ids(&[
+            unlock(1, Some(LedgerInclusionState::Conflicting)),
+            unlock(2, Some(LedgerInclusionState::Included)),
+            unlock(2, Some(LedgerInclusionState::Included)),
+            unlock(3, None),
+        ]);
         assert_eq!(
-            res.headers().get_one("Access-Control-Allow-Credentials"),
-            Some(Header::new("Access-Control-Allow-Credentials", "true").value())
+            message_ids,
+            vec![MessageId::new([2; 32]), MessageId::new([3; 32])]
+                .into_iter()
+                .collect::<HashSet<_>>()
         );
+        assert!(spending_message_ids(&[]).is_empty());
     }
 
     #[rocket::async_test]
-    async fn options() {
-        let rocket = construct_rocket(rocket::ignite());
+    async fn get_ed25519_balance_invalid_address() {
+        let storage_config = StorageConfig::default();
+        let keyspaces = storage_config
+            .keyspaces
+            .iter()
+            .cloned()
+            .map(|k| k.name)
+            .collect::<HashSet<_>>();
+        let rocket = construct_rocket(
+            rocket::ignite()
+                .manage(storage_config.partition_config.clone())
+                .manage(keyspaces)
+                .manage(storage_config)
+                .manage(ApiConfig::default())
+                .manage(Authenticator::default()),
+        );
         let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
 
-        let res = client.options("/api/anything").dispatch().await;
-        assert_eq!(res.status(), Status::Ok);
-        assert_eq!(res.content_type(), None);
+        let res = client
+            .get("/api/permanode/addresses/ed25519/not-an-address/balance")
+            .dispatch()
+            .await;
+        assert_eq!(res.status(), Status::BadRequest);
+        assert_eq!(res.content_type(), Some(ContentType::JSON));
         check_cors_headers(&res);
-        assert!(res.into_string().await.is_none());
+    }
+
+    fn included(milestone_index: u32) -> (Option<LedgerInclusionState>, Option<MilestoneIndex>) {
+        (
+            Some(LedgerInclusionState::Included),
+            Some(MilestoneIndex(milestone_index)),
+        )
+    }
+
+    fn output_row(index: Index, address: [u8; 32], amount: u64, milestone_index: u32) -> (Index, TransactionRecord) {
+        use bee_message::prelude::SignatureLockedSingleOutput;
+        let output = Output::SignatureLockedSingle(
+            SignatureLockedSingleOutput::new(Address::Ed25519(Ed25519Address::new(address)), amount).unwrap(),
+        );
+        let (inclusion_state, milestone_index) = included(milestone_index);
+        (
+            index,
+            TransactionRecord::output(
+                MessageId::new([index as u8; 32]),
+                output,
+                inclusion_state,
+                milestone_index,
+            ),
+        )
+    }
+
+    fn input_row(spent: TransactionId, spent_index: Index, milestone_index: u32) -> (Index, TransactionRecord) {
+        use bee_message::prelude::{
+            Ed25519Signature,
+            SignatureUnlock,
+            UnlockBlock,
+            UtxoInput,
+        };
+        let unlock_block = UnlockBlock::Signature(SignatureUnlock::Ed25519(Ed25519Signature::new(
+            [0; 32],
+            Box::new([0; 64]),
+        )));
+        let input = InputData::utxo(UtxoInput::new(spent, spent_index).unwrap(), unlock_block);
+        let (inclusion_state, milestone_index) = included(milestone_index);
+        (
+            0,
+            TransactionRecord::input(
+                MessageId::new([milestone_index.unwrap().0 as u8; 32]),
+                input,
+                inclusion_state,
+                milestone_index,
+            ),
+        )
+    }
+
+    #[test]
+    fn address_entries_of_a_page() {
+        const ADDRESS: [u8; 32] = [1; 32];
+        const OTHER: [u8; 32] = [2; 32];
+        let address = Ed25519Address::new(ADDRESS);
+        let (tx0, tx1, tx2, tx3, tx4) = (
+            TransactionId::new([10; 32]),
+            TransactionId::new([11; 32]),
+            TransactionId::new([12; 32]),
+            TransactionId::new([13; 32]),
+            TransactionId::new([14; 32]),
+        );
+        let mut transactions = HashMap::new();
+        // An older output of the address, which is not on this page
+        transactions.insert(tx0, vec![output_row(0, ADDRESS, 7, 5)]);
+        // Receives 100
+        transactions.insert(tx1, vec![output_row(0, ADDRESS, 100, 10)]);
+        // Spends the 100 and sends 40 back to the address
+        transactions.insert(
+            tx2,
+            vec![
+                input_row(tx1, 0, 12),
+                output_row(0, OTHER, 60, 12),
+                output_row(1, ADDRESS, 40, 12),
+            ],
+        );
+        // Only spends the 40 of this page
+        transactions.insert(tx3, vec![input_row(tx2, 1, 15), output_row(0, OTHER, 40, 15)]);
+        // Only spends the older output, so it belongs to an older page
+        transactions.insert(tx4, vec![input_row(tx0, 0, 20), output_row(0, OTHER, 7, 20)]);
+
+        // Missing from the keyspace
+        let tx5 = TransactionId::new([15; 32]);
+
+        let creating_ids = vec![tx1, tx2, tx5].into_iter().collect();
+        let spending_ids = vec![tx3, tx4].into_iter().collect();
+        let page_milestones = vec![10, 12].into_iter().collect();
+        let entries = address_entries(&creating_ids, &spending_ids, &page_milestones, &transactions, &address);
+        assert_eq!(
+            entries
+                .iter()
+                .map(|summary| (summary.milestone_index, summary.amount))
+                .collect::<Vec<_>>(),
+            vec![(15, -40), (12, -60), (10, 100)]
+        );
+        assert_eq!(entries[0].latest_input_milestone, Some(12));
+        assert_eq!(entries[1].latest_input_milestone, None);
+
+        // Missing transactions have no rows, so they are left out of the page
+        assert!(transaction_rows(&transactions, &tx5).is_empty());
     }
 
     #[rocket::async_test]
-    async fn info() {
-        let rocket = construct_rocket(rocket::ignite());
+    async fn get_bech32_transactions_invalid_hrp() {
+        let storage_config = StorageConfig::default();
+        let rocket = construct_rocket(
+            rocket::ignite()
+                .manage(storage_config.partition_config.clone())
+                .manage(storage_config)
+                .manage(CursorKey::new(None).unwrap())
+                .manage(ApiConfig::default())
+                .manage(Authenticator::default()),
+        );
         let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
 
-        let res = client.get("/api/info").dispatch().await;
-        assert_eq!(res.status(), Status::Ok);
+        let res = client
+            .get("/api/permanode/addresses/atoi1qpf0mlq8yxpx2nck8a0slxnzr4ef2ek8f5gqxlzd0wasgp73utryjjl77h3/transactions")
+            .dispatch()
+            .await;
+        assert_eq!(res.status(), Status::BadRequest);
         assert_eq!(res.content_type(), Some(ContentType::JSON));
         check_cors_headers(&res);
-        let body: SuccessBody<ListenerResponse> =
-            serde_json::from_str(&res.into_string().await.expect("No body returned!"))
-                .expect("Failed to deserialize Info Response!");
-        match *body {
-            ListenerResponse::Info { .. } => (),
-            _ => panic!("Did not receive an info response!"),
-        }
+        let body: Value = serde_json::from_str(&res.into_string().await.expect("No body returned!"))
+            .expect("Failed to deserialize response!");
+        assert_eq!(
+            body.get("message").and_then(Value::as_str),
+            Some("Invalid bech32 address HRP! Expected: iota, found: atoi")
+        );
     }
 
     #[rocket::async_test]
-    async fn service() {
-        let rocket = construct_rocket(rocket::ignite());
+    async fn get_latest_milestone_invalid_keyspace() {
+        let storage_config = StorageConfig::default();
+        let keyspaces = storage_config
+            .keyspaces
+            .iter()
+            .cloned()
+            .map(|k| k.name)
+            .collect::<HashSet<_>>();
+        let rocket = construct_rocket(rocket::ignite().manage(keyspaces).manage(Authenticator::default()));
         let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
 
-        let res = client.get("/api/service").dispatch().await;
-        assert_eq!(res.status(), Status::Ok);
+        let res = client.get("/api/unknown/milestones/latest").dispatch().await;
+        assert_eq!(res.status(), Status::NotFound);
+        assert_eq!(res.content_type(), Some(ContentType::JSON));
+        check_cors_headers(&res);
+
+        // The latest synced milestone is looked up with a single query, which fails without a scylla ring
+        let res = client.get("/api/permanode/milestones/latest").dispatch().await;
+        assert_eq!(res.status(), Status::InternalServerError);
         assert_eq!(res.content_type(), Some(ContentType::JSON));
         check_cors_headers(&res);
-        let _body: Service = serde_json::from_str(&res.into_string().await.expect("No body returned!"))
-            .expect("Failed to deserialize Service Response!");
+    }
+
+    #[test]
+    fn time_buckets_stop_at_the_earliest_milestone() {
+        const DAY: u64 = MilestoneTimestamp::BUCKET_SECS;
+        let buckets = |timestamp, earliest| {
+            time_buckets(MilestoneTimestamp(timestamp), earliest, MilestoneTimestamp(2000 * DAY))
+                .map(|timestamp| (timestamp.bucket().unwrap(), timestamp.0))
+                .collect::<Vec<_>>()
+        };
+        // The first bucket is searched up to the timestamp, the previous ones up to their end
+        assert_eq!(
+            buckets(100 * DAY + 5, Some(MilestoneTimestamp(98 * DAY + 7))),
+            vec![(100, 100 * DAY + 5), (99, 100 * DAY - 1), (98, 99 * DAY - 1)]
+        );
+        // Older timestamps than the earliest milestone are not searched at all
+        assert!(buckets(97 * DAY, Some(MilestoneTimestamp(98 * DAY))).is_empty());
+        // Long outages are searched through
+        assert_eq!(buckets(1000 * DAY, Some(MilestoneTimestamp(DAY))).len(), 1000);
+        // Without an earliest milestone the search is capped
+        assert_eq!(buckets(1000 * DAY, None).len(), 30);
+        assert_eq!(buckets(DAY, None).len(), 2);
+    }
+
+    #[test]
+    fn time_buckets_start_at_the_latest_milestone() {
+        const DAY: u64 = MilestoneTimestamp::BUCKET_SECS;
+        let buckets = |timestamp| {
+            time_buckets(
+                MilestoneTimestamp(timestamp),
+                Some(MilestoneTimestamp(98 * DAY + 7)),
+                MilestoneTimestamp(100 * DAY + 5),
+            )
+            .map(|timestamp| (timestamp.bucket().unwrap(), timestamp.0))
+            .collect::<Vec<_>>()
+        };
+        let expected = vec![(100, 100 * DAY + 5), (99, 100 * DAY - 1), (98, 99 * DAY - 1)];
+        // A future timestamp is searched from the latest milestone on
+        assert_eq!(buckets(100_000 * DAY), expected);
+        // Even if its bucket doesn't fit
+        assert_eq!(MilestoneTimestamp(u64::MAX).bucket(), None);
+        assert_eq!(buckets(u64::MAX), expected);
     }
 
     #[rocket::async_test]
-    async fn get_message() {
+    async fn get_milestone_by_time_too_far_in_the_future() {
+        let storage_config = StorageConfig::default();
+        let keyspaces = storage_config
+            .keyspaces
+            .iter()
+            .cloned()
+            .map(|k| k.name)
+            .collect::<HashSet<_>>();
+        let rocket = construct_rocket(rocket::ignite().manage(keyspaces).manage(Authenticator::default()));
+        let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
+
+        // Rejected before anything is queried, as its time bucket doesn't fit
+        let res = client
+            .get(format!("/api/permanode/milestones/by-time?timestamp={}", u64::MAX))
+            .dispatch()
+            .await;
+        assert_eq!(res.status(), Status::BadRequest);
+        assert_eq!(res.content_type(), Some(ContentType::JSON));
+        check_cors_headers(&res);
+    }
+
+    #[rocket::async_test]
+    async fn get_milestone_messages_cursor_of_another_milestone() {
         let storage_config = StorageConfig::default();
         let keyspaces = storage_config
             .keyspaces
@@ -1039,22 +3887,39 @@ mod tests {
             .cloned()
             .map(|k| k.name)
             .collect::<HashSet<_>>();
+        let cursor_key = CursorKey::new(None).unwrap();
+        let cursor = cursor_key.encode(&(1u32, vec![0u8; 8])).unwrap();
         let rocket = construct_rocket(
             rocket::ignite()
                 .manage(storage_config.partition_config.clone())
-                .manage(keyspaces),
+                .manage(keyspaces)
+                .manage(cursor_key)
+                .manage(ApiConfig::default())
+                .manage(Authenticator::default()),
         );
         let client = Client::tracked(rocket).await.expect("Invalid rocket instance!");
 
         let res = client
-            .get("/api/permanode/messages/91515c13d2025f79ded3758abe5dc640591c3b6d58b1c52cd51d1fa0585774bc")
+            .get(format!("/api/permanode/milestones/2/messages?state={}", cursor))
             .dispatch()
             .await;
-        assert_eq!(res.status(), Status::InternalServerError);
+        assert_eq!(res.status(), Status::BadRequest);
         assert_eq!(res.content_type(), Some(ContentType::JSON));
         check_cors_headers(&res);
         let body: Value = serde_json::from_str(&res.into_string().await.expect("No body returned!"))
             .expect("Failed to deserialize response!");
-        assert_eq!(body.get("message").and_then(Value::as_str), Some("Worker NoRing"));
+        assert_eq!(
+            body.get("message").and_then(Value::as_str),
+            Some("Invalid cursor provided! It is malformed or was tampered with")
+        );
+    }
+
+    #[test]
+    fn hint_digest_ignores_the_partition_list() {
+        let address = Hint::address("a".repeat(64));
+        // The digest only depends on the hint, so new records never invalidate a cursor
+        assert_eq!(hint_digest(&address), hint_digest(&Hint::address("a".repeat(64))));
+        assert_ne!(hint_digest(&address), hint_digest(&Hint::index("a".repeat(64))));
+        assert_ne!(hint_digest(&address), hint_digest(&Hint::address("b".repeat(64))));
     }
 }
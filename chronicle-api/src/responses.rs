@@ -4,7 +4,9 @@
 use bee_message::{
     prelude::{
         MilestoneIndex,
+        Output,
         OutputId,
+        Payload,
     },
     Message,
 };
@@ -12,14 +14,20 @@ use bee_rest_api::types::dtos::{
     OutputDto,
     PayloadDto,
 };
-use chronicle_broker::AnalyticData;
+use chronicle_broker::{
+    AnalyticData,
+    ExtendedAnalyticsData,
+    MessageEvent,
+};
 use chronicle_storage::access::{
     AddressRecord,
     IndexationRecord,
     LedgerInclusionState,
     MessageMetadata,
+    MigratedFundsRecord,
     ParentRecord,
     Partitioned,
+    ReceiptRecord,
 };
 use serde::{
     Deserialize,
@@ -28,6 +36,7 @@ use serde::{
 use std::{
     borrow::Cow,
     convert::TryFrom,
+    ops::Range,
 };
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -83,6 +92,9 @@ pub(crate) enum ListenerResponse {
         #[serde(skip_serializing_if = "Option::is_none")]
         #[serde(rename = "shouldReattach")]
         should_reattach: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(rename = "conflictReason")]
+        conflict_reason: Option<u8>,
     },
     /// Response of GET /api/<keyspace>/messages/<message_id>/children
     MessageChildren {
@@ -152,6 +164,35 @@ pub(crate) enum ListenerResponse {
         output_ids: Vec<Record>,
         state: Option<String>,
     },
+    /// Response of GET /api/<keyspace>/addresses/<address>/balance[?milestone_index=<u32>]
+    BalanceForAddress {
+        // The type of the address (1=Ed25519).
+        #[serde(rename = "addressType")]
+        address_type: u8,
+        address: String,
+        balance: u64,
+        #[serde(rename = "dustAllowanceBalance")]
+        dust_allowance_balance: u64,
+        #[serde(rename = "outputCount")]
+        output_count: usize,
+        /// Whether the address may receive another dust output
+        #[serde(rename = "dustAllowed")]
+        dust_allowed: bool,
+        #[serde(rename = "ledgerIndex")]
+        ledger_index: u32,
+    },
+    /// Response of GET /api/<keyspace>/addresses/[ed25519/]<address>/transactions
+    TransactionsForAddress {
+        // The type of the address (1=Ed25519).
+        #[serde(rename = "addressType")]
+        address_type: u8,
+        address: String,
+        #[serde(rename = "maxResults")]
+        max_results: usize,
+        count: usize,
+        transactions: Vec<AddressTransaction>,
+        state: Option<String>,
+    },
     /// Response of GET /api/<keyspace>/outputs/<output_id>
     Output {
         #[serde(rename = "messageId")]
@@ -162,6 +203,13 @@ pub(crate) enum ListenerResponse {
         output_index: u16,
         #[serde(rename = "isSpent")]
         is_spent: bool,
+        /// The id of the transaction which spent the output
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(rename = "spentBy")]
+        spent_by: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(rename = "milestoneIndexSpent")]
+        milestone_index_spent: Option<u32>,
         output: OutputDto,
     },
     /// Response of GET /api/<keyspace>/milestone/<index>
@@ -172,8 +220,82 @@ pub(crate) enum ListenerResponse {
         message_id: String,
         timestamp: u64,
     },
+    /// Response of GET /api/<keyspace>/milestones/<index>/messages
+    MessagesForMilestone {
+        #[serde(rename = "index")]
+        milestone_index: u32,
+        #[serde(rename = "maxResults")]
+        max_results: usize,
+        count: usize,
+        #[serde(rename = "messageIds")]
+        message_ids: Vec<String>,
+        state: Option<String>,
+    },
+    /// Response of GET /api/<keyspace>/milestones?<start>&<end>[&<page_size>]
+    Milestones {
+        #[serde(rename = "maxResults")]
+        max_results: usize,
+        count: usize,
+        milestones: Vec<MilestoneRecord>,
+        /// The start index of the next page, if the requested range is not exhausted
+        #[serde(rename = "nextStart")]
+        next_start: Option<u32>,
+    },
+    /// Response of GET /api/<keyspace>/receipts
+    /// and GET /api/<keyspace>/receipts/<migrated_at>
+    Receipts {
+        receipts: Vec<MigrationReceipt>,
+        #[serde(rename = "maxResults")]
+        max_results: usize,
+        count: usize,
+        state: Option<String>,
+    },
+    /// Response of GET /api/<keyspace>/receipts/tail-transactions/<tail_transaction_hash>
+    MigratedFunds {
+        #[serde(rename = "tailTransactionHash")]
+        tail_transaction_hash: String,
+        entries: Vec<MigratedFundsEntryRecord>,
+    },
+    /// Response of GET /api/<keyspace>/treasury
+    Treasury {
+        #[serde(rename = "milestoneId")]
+        milestone_id: String,
+        amount: u64,
+    },
     /// Response of GET /api/<keyspace>/analytics[?start=<u32>&end=<u32>]
-    Analytics { ranges: Vec<AnalyticData> },
+    Analytics {
+        ranges: Vec<AnalyticData>,
+        extended: ExtendedAnalyticsData,
+    },
+    /// Response of GET /api/<keyspace>/analytics?<bucket>[&<start>&<end>]
+    AnalyticsBuckets {
+        bucket: String,
+        buckets: Vec<AnalyticBucket>,
+        /// The milestones which have analytics but no known timestamp, so they are left out of the buckets
+        missing: Vec<Range<u32>>,
+    },
+    /// Response of POST /api/<keyspace>/messages/batch
+    /// and POST /api/<keyspace>/outputs/batch
+    Batch { count: usize, results: Vec<BatchRecord> },
+    /// Response of GET /api/<keyspace>/sync[?<from>&<to>&<gaps_only>]
+    Sync {
+        from: u32,
+        to: u32,
+        /// The synced and logged ranges, omitted in gaps only mode
+        #[serde(skip_serializing_if = "Option::is_none")]
+        completed: Option<Vec<Range<u32>>>,
+        /// The synced but unlogged ranges, omitted in gaps only mode
+        #[serde(rename = "syncedButUnlogged", skip_serializing_if = "Option::is_none")]
+        synced_but_unlogged: Option<Vec<Range<u32>>>,
+        gaps: Vec<Range<u32>>,
+    },
+    /// Event of GET /api/<keyspace>/events/milestones
+    MilestoneEvent {
+        #[serde(rename = "index")]
+        milestone_index: u32,
+        timestamp: u64,
+        messages: Vec<MessageEventRecord>,
+    },
 }
 
 impl TryFrom<Message> for ListenerResponse {
@@ -203,6 +325,7 @@ impl From<MessageMetadata> for ListenerResponse {
             ledger_inclusion_state: metadata.ledger_inclusion_state,
             should_promote: metadata.should_promote,
             should_reattach: metadata.should_reattach,
+            conflict_reason: metadata.conflict_reason,
         }
     }
 }
@@ -246,27 +369,141 @@ impl From<Partitioned<ParentRecord>> for Record {
     }
 }
 
+/// A milestone of a milestone range
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct MilestoneRecord {
+    #[serde(rename = "index")]
+    pub milestone_index: u32,
+    #[serde(rename = "messageId")]
+    pub message_id: String,
+    pub timestamp: u64,
+}
+
+/// A transaction which credited or debited an address
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct AddressTransaction {
+    #[serde(rename = "messageId")]
+    pub message_id: String,
+    #[serde(rename = "milestoneIndex")]
+    pub milestone_index: u32,
+    #[serde(rename = "milestoneTimestamp")]
+    pub milestone_timestamp: Option<u64>,
+    /// The net amount received by the address (negative if it was debited)
+    pub amount: i64,
+}
+
+/// The analytics of the milestones confirmed within a time bucket
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct AnalyticBucket {
+    /// The unix timestamp the bucket starts at
+    pub start: u64,
+    pub milestone_count: u32,
+    pub message_count: u128,
+    pub transaction_count: u128,
+    pub transferred_tokens: u128,
+    /// The shortest time (in seconds) between two consecutive milestones of the bucket
+    pub min_milestone_interval: Option<u64>,
+    /// The longest time (in seconds) between two consecutive milestones of the bucket
+    pub max_milestone_interval: Option<u64>,
+    /// The average time (in seconds) between two consecutive milestones of the bucket
+    pub avg_milestone_interval: Option<f64>,
+    /// The extended analytics of the milestones of the bucket, summed
+    pub extended: ExtendedAnalyticsData,
+}
+
+/// A receipt and the milestone which carried it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct MigrationReceipt {
+    pub receipt: PayloadDto,
+    #[serde(rename = "milestoneIndex")]
+    pub milestone_index: u32,
+}
+
+impl From<ReceiptRecord> for MigrationReceipt {
+    fn from(record: ReceiptRecord) -> Self {
+        MigrationReceipt {
+            receipt: (&Payload::Receipt(Box::new(record.receipt))).into(),
+            milestone_index: record.milestone_index.0,
+        }
+    }
+}
+
+/// The funds a legacy bundle migrated
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct MigratedFundsEntryRecord {
+    #[serde(rename = "milestoneIndex")]
+    pub milestone_index: u32,
+    #[serde(rename = "migratedAt")]
+    pub migrated_at: u32,
+    pub output: OutputDto,
+}
+
+impl From<MigratedFundsRecord> for MigratedFundsEntryRecord {
+    fn from(record: MigratedFundsRecord) -> Self {
+        MigratedFundsEntryRecord {
+            milestone_index: record.milestone_index.0,
+            migrated_at: record.migrated_at.0,
+            output: (&Output::SignatureLockedSingle(record.output)).into(),
+        }
+    }
+}
+
+/// A message of a milestone event
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct MessageEventRecord {
+    #[serde(rename = "messageId")]
+    pub message_id: String,
+    #[serde(rename = "ledgerInclusionState")]
+    pub ledger_inclusion_state: Option<LedgerInclusionState>,
+    pub index: Option<String>,
+    /// The ed25519 addresses of the message's transaction outputs
+    pub addresses: Vec<String>,
+}
+
+impl From<&MessageEvent> for MessageEventRecord {
+    fn from(message: &MessageEvent) -> Self {
+        MessageEventRecord {
+            message_id: message.message_id.to_string(),
+            ledger_inclusion_state: message.ledger_inclusion_state,
+            index: message.index.clone(),
+            addresses: message.addresses.iter().map(|address| address.to_string()).collect(),
+        }
+    }
+}
+
+/// The result of a single lookup of a batch request
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct BatchRecord {
+    pub id: String,
+    /// The looked up record, if it was found
+    pub data: Option<ListenerResponse>,
+    /// The reason the lookup failed, i.e. the record was not found
+    pub error: Option<String>,
+}
+
+/// The paging state carried by a cursor
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) struct StateData {
     pub paging_state: Option<Vec<u8>>,
     pub last_partition_id: Option<u16>,
     pub last_milestone_index: Option<u32>,
+    /// The digest of the hint being paged through
+    pub hint_digest: u64,
+    /// The latest milestone index of the hint when paging started, records added afterwards are not paged
+    pub max_milestone_index: u32,
+    /// The partition list being paged through, which is not part of the cursor
+    #[serde(skip)]
     pub partition_ids: Vec<(MilestoneIndex, u16)>,
 }
 
-impl From<(Option<Vec<u8>>, Option<u16>, Option<u32>, Vec<(MilestoneIndex, u16)>)> for StateData {
-    fn from(
-        (paging_state, last_partition_id, last_milestone_index, partition_ids): (
-            Option<Vec<u8>>,
-            Option<u16>,
-            Option<u32>,
-            Vec<(MilestoneIndex, u16)>,
-        ),
-    ) -> Self {
+impl StateData {
+    pub fn new(hint_digest: u64, max_milestone_index: u32, partition_ids: Vec<(MilestoneIndex, u16)>) -> Self {
         Self {
-            paging_state,
-            last_partition_id,
-            last_milestone_index,
+            paging_state: None,
+            last_partition_id: None,
+            last_milestone_index: None,
+            hint_digest,
+            max_milestone_index,
             partition_ids,
         }
     }
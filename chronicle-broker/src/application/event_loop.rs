@@ -166,6 +166,21 @@ impl<H: ChronicleBrokerScope> EventLoop<H> for ChronicleBroker<H> {
                                 }
                                 self.service.update_microservice(service.get_name(), service.clone());
                             }
+                            BrokerChild::Ledger(service, ledger_status) => {
+                                // Handle abort
+                                if let Err(Need::Abort) = ledger_status {
+                                    if service.is_stopped() {
+                                        // update status only if is not restarting
+                                        if let Err(Need::Restart) = status.as_ref() {
+                                        } else {
+                                            // Abort broker app
+                                            self.shutdown(supervisor, true).await;
+                                            status = ledger_status;
+                                        }
+                                    }
+                                }
+                                self.service.update_microservice(service.get_name(), service.clone());
+                            }
                             BrokerChild::Archiver(service, archiver_status) => {
                                 // Handle abort
                                 if let Err(Need::Abort) = archiver_status {
@@ -354,7 +369,8 @@ impl<H: ChronicleBrokerScope> ChronicleBroker<H> {
                 self.pending_imports.push(import_topology);
                 return ();
             }
-            if path.is_file() {
+            // a snapshot importer imports the full and delta snapshots of a directory in order
+            if path.is_file() || (path.is_dir() && matches!(import_type, ImportType::Snapshot)) {
                 // build importer
                 self.spawn_importer(
                     path.clone(),
@@ -408,6 +424,19 @@ impl<H: ChronicleBrokerScope> ChronicleBroker<H> {
         self.service.update_microservice(importer.get_name(), service);
         tokio::spawn(importer.start(self.handle.clone()));
     }
+    fn build_and_start_snapshot_importer(&mut self, file_path: PathBuf, parallelism: u8) {
+        let importer = ImporterBuilder::<Snapshot>::new()
+            .file_path(file_path)
+            .parallelism(parallelism)
+            .retries_per_query(50) // TODO get it from config
+            .chronicle_id(0) // TODO get it from config
+            .build();
+        let handle = importer.clone_handle().expect("Expected existing importer handle");
+        self.importer_handles.insert(importer.get_name(), handle);
+        let service = Service::new();
+        self.service.update_microservice(importer.get_name(), service);
+        tokio::spawn(importer.start(self.handle.clone()));
+    }
     async fn spawn_importer(
         &mut self,
         file_path: PathBuf,
@@ -431,6 +460,9 @@ impl<H: ChronicleBrokerScope> ChronicleBroker<H> {
                 ImportType::Analytics => {
                     self.build_and_start_importer::<Analytics>(file_path, resume, import_range, parallelism);
                 }
+                ImportType::Snapshot => {
+                    self.build_and_start_snapshot_importer(file_path, parallelism);
+                }
             }
             self.in_progress_importers += 1;
             self.parallelism_points -= parallelism;
@@ -47,6 +47,30 @@ impl<H: ChronicleBrokerScope> Init<H> for ChronicleBroker<H> {
                 info!("Initializing Broker without Archiver");
                 archiver_handle = None;
             }
+            let ledger_handle;
+            if config.broker_config.ledger {
+                // create ledger
+                let ledger_builder = LedgerBuilder::new()
+                    .keyspace(self.default_keyspace.clone())
+                    .partition_config(config.storage_config.partition_config.clone())
+                    .retries_per_query(config.broker_config.retries_per_query);
+                // The filter spreads the cone messages over the keyspaces of its store rules
+                #[cfg(feature = "filter")]
+                let ledger_builder = ledger_builder.message_keyspaces(
+                    config
+                        .filter_config
+                        .keyspaces(self.default_keyspace.name())
+                        .into_iter()
+                        .map(|keyspace| ChronicleKeyspace::new(keyspace.to_owned()))
+                        .collect(),
+                );
+                let mut ledger = ledger_builder.build();
+                ledger_handle = ledger.take_handle();
+                // start ledger
+                tokio::spawn(ledger.start(self.handle.clone()));
+            } else {
+                ledger_handle = None;
+            }
             let mut collector_builders: Vec<CollectorBuilder> = Vec::new();
             let mut solidifier_builders: Vec<SolidifierBuilder> = Vec::new();
             let reqwest_client = reqwest::Client::builder()
@@ -74,6 +98,7 @@ impl<H: ChronicleBrokerScope> Init<H> for ChronicleBroker<H> {
                     .inbox(collector_inbox)
                     .api_endpoints(config.broker_config.api_endpoints.iter().cloned().collect())
                     .storage_config(config.storage_config.clone())
+                    .filter_config(config.filter_config.clone())
                     .reqwest_client(reqwest_client.clone())
                     .retries_per_query(config.broker_config.retries_per_query)
                     .retries_per_endpoint(config.broker_config.retries_per_endpoint)
@@ -88,10 +113,21 @@ impl<H: ChronicleBrokerScope> Init<H> for ChronicleBroker<H> {
                 self.solidifier_handles.insert(partition_id, solidifier_handle.clone());
                 let mut solidifier_builder = SolidifierBuilder::new()
                     .collector_count(self.collector_count)
+                    .partition_config(config.storage_config.partition_config.clone())
+                    .filter_config(config.filter_config.clone())
                     .syncer_handle(syncer_handle.clone());
                 if let Some(archiver_handle) = archiver_handle.clone().take() {
                     solidifier_builder = solidifier_builder.archiver_handle(archiver_handle);
                 }
+                if let Some(ledger_handle) = ledger_handle.clone() {
+                    solidifier_builder = solidifier_builder.ledger_handle(ledger_handle);
+                }
+                if let Some(coordinator) = config.broker_config.coordinator.clone() {
+                    solidifier_builder = solidifier_builder.coordinator(coordinator);
+                }
+                if let Some(milestone_events) = self.milestone_events.clone() {
+                    solidifier_builder = solidifier_builder.milestone_events(milestone_events);
+                }
                 solidifier_builder = solidifier_builder
                     .gap_start(gap_start)
                     .keyspace(self.default_keyspace.clone())
@@ -5,6 +5,7 @@ use crate::{
     archiver::*,
     collector::*,
     importer::*,
+    ledger::*,
     listener::*,
     mqtt::*,
     solidifier::*,
@@ -35,7 +36,8 @@ builder!(
         listener_handle: ListenerHandle,
         complete_gaps_interval_secs: u64,
         parallelism: u8,
-        collector_count: u8
+        collector_count: u8,
+        milestone_events: MilestoneEventSender
 });
 
 /// BrokerHandle to be passed to the children
@@ -76,6 +78,7 @@ pub struct ChronicleBroker<H: ChronicleBrokerScope> {
     sync_range: SyncRange,
     sync_data: SyncData,
     syncer_handle: Option<SyncerHandle>,
+    milestone_events: Option<MilestoneEventSender>,
 }
 
 /// SubEvent type, indicates the children
@@ -92,6 +95,8 @@ pub enum BrokerChild {
     Archiver(Service, Result<(), Need>),
     /// Used by Syncer to keep Broker up to date with its service
     Syncer(Service, Result<(), Need>),
+    /// Used by Ledger to keep Broker up to date with its service
+    Ledger(Service, Result<(), Need>),
     /// Used by Importer to keep Broker up to date with its service, u8 is parallelism
     Importer(Service, Result<(), Need>, u8),
     /// Used by Websocket to keep Broker up to date with its service
@@ -172,6 +177,7 @@ impl<H: ChronicleBrokerScope> Builder for ChronicleBrokerBuilder<H> {
             default_keyspace,
             sync_range,
             sync_data,
+            milestone_events: self.milestone_events,
             complete_gaps_interval: Duration::from_secs(self.complete_gaps_interval_secs.unwrap()),
         }
         .set_name()
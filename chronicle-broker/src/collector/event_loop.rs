@@ -7,8 +7,13 @@ use bee_message::{
     input::Input,
     parents::Parents,
     payload::Payload,
-    prelude::TransactionId,
+    prelude::{
+        MilestonePayload,
+        OutputId,
+        TransactionId,
+    },
 };
+use chronicle_common::Synckey;
 use std::sync::Arc;
 
 #[async_trait::async_trait]
@@ -176,7 +181,9 @@ impl<H: ChronicleBrokerScope> EventLoop<BrokerHandle<H>> for Collector {
                                     error!("{}", e);
                                 });
                         } else {
-                            // store it as metadata
+                            // store it as metadata, unless the filter is on, then the keyspace can only be picked once
+                            // the message arrives, and `insert_message` stores the cached metadata along with it
+                            #[cfg(not(feature = "filter"))]
                             self.insert_message_metadata(metadata).unwrap_or_else(|e| {
                                 error!("{}", e);
                             });
@@ -351,16 +358,25 @@ impl Collector {
         message: &Message,
         wrong_est_ms: MilestoneIndex,
     ) -> anyhow::Result<()> {
-        self.delete_parents(message_id, message.parents(), wrong_est_ms)?;
+        // the message was stored before its metadata was known, so it was filtered without a milestone index
+        #[cfg(feature = "filter")]
+        let keyspace = match self.get_keyspace_for_message(message, None) {
+            Some((keyspace, _)) => keyspace,
+            // the filter dropped it, so there is nothing to clean up
+            None => return Ok(()),
+        };
+        #[cfg(not(feature = "filter"))]
+        let keyspace = self.get_keyspace();
+        self.delete_parents(&keyspace, message_id, message.parents(), wrong_est_ms)?;
         match message.payload() {
             // delete indexation if any
             Some(Payload::Indexation(indexation)) => {
                 let index_key = Indexation(hex::encode(indexation.index()));
-                self.delete_indexation(&message_id, index_key, wrong_est_ms)?;
+                self.delete_indexation(&keyspace, &message_id, index_key, wrong_est_ms)?;
             }
             // delete transactiion partitioned rows if any
             Some(Payload::Transaction(transaction_payload)) => {
-                self.delete_transaction_partitioned_rows(message_id, transaction_payload, wrong_est_ms)?;
+                self.delete_transaction_partitioned_rows(&keyspace, message_id, transaction_payload, wrong_est_ms)?;
             }
             _ => {}
         }
@@ -382,16 +398,46 @@ impl Collector {
             let _ = solidifier_handle.send(full_msg_event);
         };
     }
-    /// Get the `Chronicle` keyspace of a message
+    /// Get the `Chronicle` keyspace and the records time-to-live of a message, or None if the message is dropped
     #[cfg(feature = "filter")]
-    fn get_keyspace_for_message(&self, message: &mut Message) -> ChronicleKeyspace {
-        let res = futures::executor::block_on(chronicle_filter::filter_messages(message));
-        ChronicleKeyspace::new(res.keyspace.into_owned())
+    fn get_keyspace_for_message(
+        &self,
+        message: &Message,
+        milestone_index: Option<u32>,
+    ) -> Option<(ChronicleKeyspace, u32)> {
+        let res = chronicle_filter::filter_with(
+            &self.filter_config,
+            self.default_keyspace.name(),
+            message,
+            milestone_index,
+        )?;
+        let keyspace = ChronicleKeyspace::new(res.keyspace.into_owned());
+        let ttl = self.get_ttl(&keyspace, res.ttl);
+        Some((keyspace, ttl))
+    }
+    /// Skip a message which is dropped by the filter. The solidifier still expects it to be persisted once it's
+    /// referenced by a milestone, so it gets told right away.
+    #[cfg(feature = "filter")]
+    fn drop_message(&self, message_id: &MessageId, milestone_index: Option<u32>) {
+        if let Some(milestone_index) = milestone_index {
+            let solidifier_handle = self.clone_solidifier_handle(milestone_index);
+            let cql_result = CqlResult::PersistedMsg(*message_id, milestone_index);
+            let _ = solidifier_handle.send(SolidifierEvent::CqlResult(Ok(cql_result)));
+        }
     }
     /// Get the Chronicle keyspace
     fn get_keyspace(&self) -> ChronicleKeyspace {
         self.default_keyspace.clone()
     }
+    /// Get the records time-to-live in a given keyspace, the filter time-to-live (if any) overrides the keyspace
+    /// default one. Zero means the records never expire.
+    fn get_ttl(&self, keyspace: &ChronicleKeyspace, filter_ttl: Option<usize>) -> u32 {
+        filter_ttl
+            .map(|ttl| ttl.min(MAX_TTL as usize) as u32)
+            .or_else(|| self.storage_config.keyspace_ttl(keyspace.name()))
+            .unwrap_or(0)
+            .min(MAX_TTL)
+    }
     /// Get the partition id of a given milestone index
     fn get_partition_id(&self, milestone_index: MilestoneIndex) -> u16 {
         self.partition_config.partition_id(milestone_index.0)
@@ -402,9 +448,21 @@ impl Collector {
         let ledger_inclusion_state;
 
         #[cfg(feature = "filter")]
-        let keyspace = self.get_keyspace_for_message(message);
+        let (keyspace, ttl) = {
+            let milestone_index = self
+                .lru_msg_ref
+                .peek(message_id)
+                .and_then(|meta| meta.referenced_by_milestone_index);
+            match self.get_keyspace_for_message(message, milestone_index) {
+                Some(keyspace_and_ttl) => keyspace_and_ttl,
+                None => {
+                    self.drop_message(message_id, milestone_index);
+                    return Ok(());
+                }
+            }
+        };
         #[cfg(not(feature = "filter"))]
-        let keyspace = self.get_keyspace();
+        let (keyspace, ttl) = (self.get_keyspace(), self.get_ttl(&self.default_keyspace, None));
         let metadata;
         if let Some(meta) = self.lru_msg_ref.get(message_id) {
             metadata = Some(meta.clone());
@@ -416,7 +474,7 @@ impl Collector {
                 AtomicWorker::new(solidifier_handle, *milestone_index, *message_id, self.retries_per_query);
             let message_tuple = (message.clone(), meta.clone());
             // store message and metadata
-            self.insert(&inherent_worker, &keyspace, *message_id, message_tuple)?;
+            self.insert(&inherent_worker, &keyspace, *message_id, message_tuple, ttl)?;
             // Insert parents/children
             self.insert_parents(
                 &inherent_worker,
@@ -424,6 +482,8 @@ impl Collector {
                 &message.parents(),
                 milestone_index,
                 ledger_inclusion_state.clone(),
+                &keyspace,
+                ttl,
             )?;
             // insert payload (if any)
             if let Some(payload) = message.payload() {
@@ -435,6 +495,8 @@ impl Collector {
                     milestone_index,
                     ledger_inclusion_state,
                     metadata,
+                    &keyspace,
+                    ttl,
                 )?;
             }
         } else {
@@ -444,7 +506,7 @@ impl Collector {
                 retries: self.retries_per_query,
             };
             // store message only
-            self.insert(&inherent_worker, &keyspace, *message_id, message.clone())?;
+            self.insert(&inherent_worker, &keyspace, *message_id, message.clone(), ttl)?;
             // Insert parents/children
             self.insert_parents(
                 &inherent_worker,
@@ -452,6 +514,8 @@ impl Collector {
                 &message.parents(),
                 self.est_ms,
                 ledger_inclusion_state.clone(),
+                &keyspace,
+                ttl,
             )?;
             // insert payload (if any)
             if let Some(payload) = message.payload() {
@@ -463,6 +527,8 @@ impl Collector {
                     self.est_ms,
                     ledger_inclusion_state,
                     metadata,
+                    &keyspace,
+                    ttl,
                 )?;
             }
         };
@@ -476,16 +542,18 @@ impl Collector {
         parents: &[MessageId],
         milestone_index: MilestoneIndex,
         inclusion_state: Option<LedgerInclusionState>,
+        keyspace: &ChronicleKeyspace,
+        ttl: u32,
     ) -> anyhow::Result<()> {
         let partition_id = self.get_partition_id(milestone_index);
         for parent_id in parents {
             let partitioned = Partitioned::new(*parent_id, partition_id, milestone_index.0);
             let parent_record = ParentRecord::new(*message_id, inclusion_state);
-            self.insert(inherent_worker, &self.get_keyspace(), partitioned, parent_record)?;
+            self.insert(inherent_worker, keyspace, partitioned, parent_record, ttl)?;
             // insert hint record
             let hint = Hint::parent(parent_id.to_string());
             let partition = Partition::new(partition_id, *milestone_index);
-            self.insert(inherent_worker, &self.get_keyspace(), hint, partition)?
+            self.insert(inherent_worker, keyspace, hint, partition, ttl)?
         }
         Ok(())
     }
@@ -499,6 +567,8 @@ impl Collector {
         milestone_index: MilestoneIndex,
         inclusion_state: Option<LedgerInclusionState>,
         metadata: Option<MessageMetadata>,
+        keyspace: &ChronicleKeyspace,
+        ttl: u32,
     ) -> anyhow::Result<()> {
         match payload {
             Payload::Indexation(indexation) => {
@@ -508,6 +578,8 @@ impl Collector {
                     Indexation(hex::encode(indexation.index())),
                     milestone_index,
                     inclusion_state,
+                    keyspace,
+                    ttl,
                 )?;
             }
             Payload::Transaction(transaction) => self.insert_transaction(
@@ -518,6 +590,8 @@ impl Collector {
                 inclusion_state,
                 milestone_index,
                 metadata,
+                keyspace,
+                ttl,
             )?,
             Payload::Milestone(milestone) => {
                 let ms_index = milestone.essence().index();
@@ -532,10 +606,20 @@ impl Collector {
                     };
                     self.insert(
                         inherent_worker,
-                        &self.get_keyspace(),
+                        keyspace,
                         ms_index,
                         (*message_id, milestone.clone()),
-                    )?
+                        ttl,
+                    )?;
+                    // index the milestone by its timestamp
+                    self.insert(
+                        inherent_worker,
+                        keyspace,
+                        MilestoneTimestamp(milestone.essence().timestamp()),
+                        (ms_index, *message_id),
+                        ttl,
+                    )?;
+                    self.insert_receipt(inherent_worker, milestone, keyspace)?;
                 }
             }
             // remaining payload types
@@ -545,6 +629,25 @@ impl Collector {
         }
         Ok(())
     }
+    /// Insert the receipt carried by a milestone, along with its migrated funds and the treasury output it created.
+    /// They are written without a TTL, so the current treasury and the migration history are never lost.
+    fn insert_receipt<I: Inherent>(
+        &self,
+        inherent_worker: &I,
+        milestone: &MilestonePayload,
+        keyspace: &ChronicleKeyspace,
+    ) -> anyhow::Result<()> {
+        if let Some(rows) = ReceiptRows::new(milestone) {
+            for (tail_transaction_hash, migrated_funds) in rows.migrated_funds {
+                self.insert(inherent_worker, keyspace, tail_transaction_hash, migrated_funds, 0)?;
+            }
+            if let Some(treasury) = rows.treasury {
+                self.insert(inherent_worker, keyspace, Synckey, treasury, 0)?;
+            }
+            self.insert(inherent_worker, keyspace, Synckey, rows.receipt, 0)?;
+        }
+        Ok(())
+    }
     /// Insert the `Indexation` of a given message id to the table
     fn insert_index<I: Inherent>(
         &self,
@@ -553,24 +656,29 @@ impl Collector {
         index: Indexation,
         milestone_index: MilestoneIndex,
         inclusion_state: Option<LedgerInclusionState>,
+        keyspace: &ChronicleKeyspace,
+        ttl: u32,
     ) -> anyhow::Result<()> {
         let partition_id = self.get_partition_id(milestone_index);
         let partitioned = Partitioned::new(index.clone(), partition_id, milestone_index.0);
         let index_record = IndexationRecord::new(*message_id, inclusion_state);
-        self.insert(inherent_worker, &self.get_keyspace(), partitioned, index_record)?;
+        self.insert(inherent_worker, keyspace, partitioned, index_record, ttl)?;
         // insert hint record
         let hint = Hint::index(index.0);
         let partition = Partition::new(partition_id, *milestone_index);
-        self.insert(inherent_worker, &self.get_keyspace(), hint, partition)
+        self.insert(inherent_worker, keyspace, hint, partition, ttl)
     }
     /// Insert the message metadata to the table
+    #[cfg(not(feature = "filter"))]
     fn insert_message_metadata(&self, metadata: MessageMetadata) -> anyhow::Result<()> {
         let message_id = metadata.message_id;
         let inherent_worker = SimpleWorker {
             retries: self.retries_per_query,
         };
+        let keyspace = self.get_keyspace();
+        let ttl = self.get_ttl(&keyspace, None);
         // store message and metadata
-        self.insert(&inherent_worker, &self.get_keyspace(), message_id, metadata.clone())?;
+        self.insert(&inherent_worker, &keyspace, message_id, metadata.clone(), ttl)?;
         // Insert parents/children
         let parents = metadata.parent_message_ids;
         self.insert_parents(
@@ -579,20 +687,27 @@ impl Collector {
             &parents.as_slice(),
             self.ref_ms,
             metadata.ledger_inclusion_state.clone(),
+            &keyspace,
+            ttl,
         )
     }
     /// Insert the message with the associated metadata of a given message id to the table
-    #[allow(unused_mut)]
     fn insert_message_with_metadata(
         &mut self,
         message_id: MessageId,
-        mut message: Message,
+        message: Message,
         metadata: MessageMetadata,
     ) -> anyhow::Result<()> {
         #[cfg(feature = "filter")]
-        let keyspace = self.get_keyspace_for_message(&mut message);
+        let (keyspace, ttl) = match self.get_keyspace_for_message(&message, metadata.referenced_by_milestone_index) {
+            Some(keyspace_and_ttl) => keyspace_and_ttl,
+            None => {
+                self.drop_message(&message_id, Some(*self.ref_ms));
+                return Ok(());
+            }
+        };
         #[cfg(not(feature = "filter"))]
-        let keyspace = self.get_keyspace();
+        let (keyspace, ttl) = (self.get_keyspace(), self.get_ttl(&self.default_keyspace, None));
         let solidifier_handle = self.clone_solidifier_handle(*self.ref_ms);
         let inherent_worker = AtomicWorker::new(solidifier_handle, *self.ref_ms, message_id, self.retries_per_query);
         // Insert parents/children
@@ -602,6 +717,8 @@ impl Collector {
             &message.parents(),
             self.ref_ms,
             metadata.ledger_inclusion_state.clone(),
+            &keyspace,
+            ttl,
         )?;
         // insert payload (if any)
         if let Some(payload) = message.payload() {
@@ -613,11 +730,13 @@ impl Collector {
                 self.ref_ms,
                 metadata.ledger_inclusion_state.clone(),
                 Some(metadata.clone()),
+                &keyspace,
+                ttl,
             )?;
         }
         let message_tuple = (message, metadata);
         // store message and metadata
-        self.insert(&inherent_worker, &keyspace, message_id, message_tuple)
+        self.insert(&inherent_worker, &keyspace, message_id, message_tuple, ttl)
     }
     /// Insert the transaction to the table
     fn insert_transaction<I: Inherent>(
@@ -629,6 +748,8 @@ impl Collector {
         ledger_inclusion_state: Option<LedgerInclusionState>,
         milestone_index: MilestoneIndex,
         metadata: Option<MessageMetadata>,
+        keyspace: &ChronicleKeyspace,
+        ttl: u32,
     ) -> anyhow::Result<()> {
         let transaction_id = transaction.id();
         let unlock_blocks = transaction.unlock_blocks();
@@ -654,6 +775,8 @@ impl Collector {
                         input_data,
                         ledger_inclusion_state,
                         confirmed_milestone_index,
+                        keyspace,
+                        ttl,
                     )?;
                     // this is the spent_output which the input is spending from
                     let output_id = utxo_input.output_id();
@@ -667,6 +790,8 @@ impl Collector {
                         unlock_data,
                         ledger_inclusion_state,
                         confirmed_milestone_index,
+                        keyspace,
+                        ttl,
                     )?;
                 } else if let Input::Treasury(treasury_input) = input {
                     let input_data = InputData::treasury(treasury_input.clone());
@@ -679,6 +804,8 @@ impl Collector {
                         input_data,
                         ledger_inclusion_state,
                         confirmed_milestone_index,
+                        keyspace,
+                        ttl,
                     )?;
                 } else {
                     error!("A new input variant was added to this type!")
@@ -694,6 +821,8 @@ impl Collector {
                     output.clone(),
                     ledger_inclusion_state,
                     confirmed_milestone_index,
+                    keyspace,
+                    ttl,
                 )?;
                 // insert address row
                 self.insert_address(
@@ -703,6 +832,8 @@ impl Collector {
                     output_index as u16,
                     milestone_index,
                     ledger_inclusion_state,
+                    keyspace,
+                    ttl,
                 )?;
             }
             if let Some(payload) = regular.payload() {
@@ -714,11 +845,31 @@ impl Collector {
                     milestone_index,
                     ledger_inclusion_state,
                     metadata,
+                    keyspace,
+                    ttl,
                 )?
             }
         };
         Ok(())
     }
+    /// Mark an output spent. The output is stored in the keyspace of the message which created it, which isn't known
+    /// here, so the marker goes into every keyspace messages can be stored in. It is written without a TTL, so it
+    /// never expires before the output.
+    fn insert_spent<I: Inherent>(
+        &self,
+        inherent_worker: &I,
+        output_id: OutputId,
+        spent_record: SpentRecord,
+    ) -> anyhow::Result<()> {
+        #[cfg(feature = "filter")]
+        for keyspace in self.filter_config.keyspaces(self.default_keyspace.name()) {
+            let keyspace = ChronicleKeyspace::new(keyspace.to_owned());
+            self.insert(inherent_worker, &keyspace, output_id, spent_record.clone(), 0)?;
+        }
+        #[cfg(not(feature = "filter"))]
+        self.insert(inherent_worker, &self.get_keyspace(), output_id, spent_record, 0)?;
+        Ok(())
+    }
     /// Insert the `InputData` to the table
     fn insert_input<I: Inherent>(
         &self,
@@ -729,11 +880,20 @@ impl Collector {
         input_data: InputData,
         inclusion_state: Option<LedgerInclusionState>,
         milestone_index: Option<MilestoneIndex>,
+        keyspace: &ChronicleKeyspace,
+        ttl: u32,
     ) -> anyhow::Result<()> {
+        // mark the output consumed by an included transaction as spent
+        if let (InputData::Utxo(utxo_input, _), Some(LedgerInclusionState::Included), Some(milestone_index)) =
+            (&input_data, inclusion_state, milestone_index)
+        {
+            let spent_record = SpentRecord::new(*transaction_id, *message_id, milestone_index);
+            self.insert_spent(inherent_worker, *utxo_input.output_id(), spent_record)?;
+        }
         // -input variant: (InputTransactionId, InputIndex) -> UTXOInput data column
         let input_id = (*transaction_id, index);
         let transaction_record = TransactionRecord::input(*message_id, input_data, inclusion_state, milestone_index);
-        self.insert(inherent_worker, &self.get_keyspace(), input_id, transaction_record)
+        self.insert(inherent_worker, keyspace, input_id, transaction_record, ttl)
     }
     /// Insert the `UnlockData` to the table
     fn insert_unlock<I: Inherent>(
@@ -745,11 +905,13 @@ impl Collector {
         unlock_data: UnlockData,
         inclusion_state: Option<LedgerInclusionState>,
         milestone_index: Option<MilestoneIndex>,
+        keyspace: &ChronicleKeyspace,
+        ttl: u32,
     ) -> anyhow::Result<()> {
         // -unlock variant: (UtxoInputTransactionId, UtxoInputOutputIndex) -> Unlock data column
         let utxo_id = (*utxo_transaction_id, utxo_index);
         let transaction_record = TransactionRecord::unlock(*message_id, unlock_data, inclusion_state, milestone_index);
-        self.insert(inherent_worker, &self.get_keyspace(), utxo_id, transaction_record)
+        self.insert(inherent_worker, keyspace, utxo_id, transaction_record, ttl)
     }
     /// Insert the `Output` to the table
     fn insert_output<I: Inherent>(
@@ -761,11 +923,13 @@ impl Collector {
         output: Output,
         inclusion_state: Option<LedgerInclusionState>,
         milestone_index: Option<MilestoneIndex>,
+        keyspace: &ChronicleKeyspace,
+        ttl: u32,
     ) -> anyhow::Result<()> {
         // -output variant: (OutputTransactionId, OutputIndex) -> Output data column
         let output_id = (*transaction_id, index);
         let transaction_record = TransactionRecord::output(*message_id, output, inclusion_state, milestone_index);
-        self.insert(inherent_worker, &self.get_keyspace(), output_id, transaction_record)
+        self.insert(inherent_worker, keyspace, output_id, transaction_record, ttl)
     }
     /// Insert the `Address` to the table
     fn insert_address<I: Inherent>(
@@ -776,6 +940,8 @@ impl Collector {
         index: u16,
         milestone_index: MilestoneIndex,
         inclusion_state: Option<LedgerInclusionState>,
+        keyspace: &ChronicleKeyspace,
+        ttl: u32,
     ) -> anyhow::Result<()> {
         let partition_id = self.get_partition_id(milestone_index);
         let output_type = output.kind();
@@ -786,11 +952,11 @@ impl Collector {
                     let partitioned = Partitioned::new(*ed_address, partition_id, milestone_index.0);
                     let address_record =
                         AddressRecord::new(output_type, *transaction_id, index, sls.amount(), inclusion_state);
-                    self.insert(inherent_worker, &self.get_keyspace(), partitioned, address_record)?;
+                    self.insert(inherent_worker, keyspace, partitioned, address_record, ttl)?;
                     // insert hint record
                     let hint = Hint::address(ed_address.to_string());
                     let partition = Partition::new(partition_id, *milestone_index);
-                    self.insert(inherent_worker, &self.get_keyspace(), hint, partition)
+                    self.insert(inherent_worker, keyspace, hint, partition, ttl)
                 }
             }
             Output::SignatureLockedDustAllowance(slda) => {
@@ -799,11 +965,11 @@ impl Collector {
                     let partitioned = Partitioned::new(*ed_address, partition_id, milestone_index.0);
                     let address_record =
                         AddressRecord::new(output_type, *transaction_id, index, slda.amount(), inclusion_state);
-                    self.insert(inherent_worker, &self.get_keyspace(), partitioned, address_record)?;
+                    self.insert(inherent_worker, keyspace, partitioned, address_record, ttl)?;
                     // insert hint record
                     let hint = Hint::address(ed_address.to_string());
                     let partition = Partition::new(partition_id, *milestone_index);
-                    self.insert(inherent_worker, &self.get_keyspace(), hint, partition)
+                    self.insert(inherent_worker, keyspace, hint, partition, ttl)
                 }
             }
             e => {
@@ -816,13 +982,15 @@ impl Collector {
         }
     }
     /// The low-level insert function to insert a key/value pair through an inherent worker
-    fn insert<I, S, K, V>(&self, inherent_worker: &I, keyspace: &S, key: K, value: V) -> anyhow::Result<()>
+    /// Note: the value is inserted with the provided time-to-live, zero means it never expires
+    fn insert<I, S, K, V>(&self, inherent_worker: &I, keyspace: &S, key: K, value: V, ttl: u32) -> anyhow::Result<()>
     where
         I: Inherent,
-        S: 'static + Insert<K, V>,
+        S: 'static + Insert<K, TTL<V>>,
         K: 'static + Send + Clone,
         V: 'static + Send + Clone,
     {
+        let value = TTL::new(value, ttl);
         let insert_req = keyspace.insert(&key, &value).consistency(Consistency::One).build()?;
         let worker = inherent_worker.inherent_boxed(keyspace.clone(), key, value);
         insert_req.send_local(worker);
@@ -831,6 +999,7 @@ impl Collector {
     /// Delete the `Parents` of a given message id in the table
     fn delete_parents(
         &self,
+        keyspace: &ChronicleKeyspace,
         message_id: &MessageId,
         parents: &Parents,
         milestone_index: MilestoneIndex,
@@ -838,24 +1007,26 @@ impl Collector {
         let partition_id = self.get_partition_id(milestone_index);
         for parent_id in parents.iter() {
             let parent_pk = ParentPK::new(*parent_id, partition_id, milestone_index, *message_id);
-            self.delete(parent_pk)?;
+            self.delete(keyspace, parent_pk)?;
         }
         Ok(())
     }
     /// Delete the `Indexation` of a given message id in the table
     fn delete_indexation(
         &self,
+        keyspace: &ChronicleKeyspace,
         message_id: &MessageId,
         indexation: Indexation,
         milestone_index: MilestoneIndex,
     ) -> anyhow::Result<()> {
         let partition_id = self.get_partition_id(milestone_index);
         let index_pk = IndexationPK::new(indexation, partition_id, milestone_index, *message_id);
-        self.delete(index_pk)
+        self.delete(keyspace, index_pk)
     }
     /// Delete the transaction partitioned rows of a given message id in the table
     fn delete_transaction_partitioned_rows(
         &self,
+        keyspace: &ChronicleKeyspace,
         message_id: &MessageId,
         transaction: &Box<TransactionPayload>,
         milestone_index: MilestoneIndex,
@@ -865,10 +1036,10 @@ impl Collector {
         {
             if let Some(Payload::Indexation(indexation)) = regular.payload() {
                 let index_key = Indexation(hex::encode(indexation.index()));
-                self.delete_indexation(&message_id, index_key, milestone_index)?;
+                self.delete_indexation(keyspace, &message_id, index_key, milestone_index)?;
             }
             for (output_index, output) in regular.outputs().iter().enumerate() {
-                self.delete_address(output, &transaction_id, output_index as u16, milestone_index)?;
+                self.delete_address(keyspace, output, &transaction_id, output_index as u16, milestone_index)?;
             }
         }
         Ok(())
@@ -876,6 +1047,7 @@ impl Collector {
     /// Delete the `Address` with a given `TransactionId` and the corresponding index in the table
     fn delete_address(
         &self,
+        keyspace: &ChronicleKeyspace,
         output: &Output,
         transaction_id: &TransactionId,
         index: u16,
@@ -895,7 +1067,7 @@ impl Collector {
                         *transaction_id,
                         index,
                     );
-                    self.delete(address_pk)?;
+                    self.delete(keyspace, address_pk)?;
                 }
             }
             Output::SignatureLockedDustAllowance(slda) => {
@@ -909,7 +1081,7 @@ impl Collector {
                         *transaction_id,
                         index,
                     );
-                    self.delete(address_pk)?;
+                    self.delete(keyspace, address_pk)?;
                 };
             }
             e => {
@@ -921,19 +1093,15 @@ impl Collector {
         }
         Ok(())
     }
-    /// Delete the key in the `Chronicle` keyspace
-    fn delete<K, V>(&self, key: K) -> anyhow::Result<()>
+    /// Delete the key in the provided `Chronicle` keyspace
+    fn delete<K, V>(&self, keyspace: &ChronicleKeyspace, key: K) -> anyhow::Result<()>
     where
         ChronicleKeyspace: Delete<K, V>,
         K: 'static + Send + Clone,
         V: 'static + Send + Clone,
     {
-        let delete_req = self
-            .default_keyspace
-            .delete(&key)
-            .consistency(Consistency::One)
-            .build()?;
-        let worker = DeleteWorker::boxed(self.default_keyspace.clone(), key, self.retries_per_query);
+        let delete_req = keyspace.delete(&key).consistency(Consistency::One).build()?;
+        let worker = DeleteWorker::boxed(keyspace.clone(), key, self.retries_per_query);
         delete_req.send_local(worker);
         Ok(())
     }
@@ -21,6 +21,7 @@ use std::collections::{
 };
 
 use chronicle_common::config::{
+    FilterConfig,
     PartitionConfig,
     StorageConfig,
 };
@@ -49,7 +50,8 @@ builder!(CollectorBuilder {
     retries_per_endpoint: usize,
     requesters_channels: Vec<(RequesterSender, RequesterReceiver)>,
     handle: CollectorHandle,
-    storage_config: StorageConfig
+    storage_config: StorageConfig,
+    filter_config: FilterConfig
 });
 
 /// Collector events
@@ -194,6 +196,11 @@ pub struct Collector {
     partition_config: PartitionConfig,
     /// The `Chronicle` keyspace
     default_keyspace: ChronicleKeyspace,
+    /// The storage config, used to lookup the keyspaces default time-to-live
+    storage_config: StorageConfig,
+    /// The filter config, loaded once to route the messages to their keyspace
+    #[cfg(feature = "filter")]
+    filter_config: FilterConfig,
 }
 
 impl<H: ChronicleBrokerScope> ActorBuilder<BrokerHandle<H>> for CollectorBuilder {}
@@ -245,6 +252,9 @@ impl Builder for CollectorBuilder {
             reqwest_client: self.reqwest_client.unwrap(),
             partition_config,
             default_keyspace,
+            storage_config: self.storage_config.unwrap_or_default(),
+            #[cfg(feature = "filter")]
+            filter_config: self.filter_config.unwrap_or_default(),
         }
         .set_name()
     }
@@ -7,6 +7,8 @@ use bee_message::{
     payload::Payload,
     prelude::{
         MilestoneIndex,
+        MilestonePayload,
+        OutputId,
         TransactionId,
     },
 };
@@ -144,6 +146,28 @@ impl<T> Importer<T> {
     pub(crate) fn get_keyspace(&self) -> ChronicleKeyspace {
         self.default_keyspace.clone()
     }
+    /// Get the `Chronicle` keyspace and the records time-to-live of a message, or None if the message is dropped
+    #[cfg(feature = "filter")]
+    fn get_keyspace_for_message(&self, message: &Message, milestone_index: u32) -> Option<(ChronicleKeyspace, u32)> {
+        let res = chronicle_filter::filter_with(
+            &self.filter_config,
+            self.default_keyspace.name(),
+            message,
+            Some(milestone_index),
+        )?;
+        let keyspace = ChronicleKeyspace::new(res.keyspace.into_owned());
+        let ttl = self.get_ttl(&keyspace, res.ttl);
+        Some((keyspace, ttl))
+    }
+    /// Get the records time-to-live in a given keyspace, the filter time-to-live (if any) overrides the keyspace
+    /// default one. Zero means the records never expire.
+    fn get_ttl(&self, keyspace: &ChronicleKeyspace, filter_ttl: Option<usize>) -> u32 {
+        filter_ttl
+            .map(|ttl| ttl.min(MAX_TTL as usize) as u32)
+            .or_else(|| self.storage_config.keyspace_ttl(keyspace.name()))
+            .unwrap_or(0)
+            .min(MAX_TTL)
+    }
     fn get_partition_id(&self, milestone_index: MilestoneIndex) -> u16 {
         self.partition_config.partition_id(milestone_index.0)
     }
@@ -159,6 +183,14 @@ impl<T: ImportMode> Importer<T> {
         let milestone_index = metadata
             .referenced_by_milestone_index
             .expect("Expected referenced milestone index in metadata");
+        #[cfg(feature = "filter")]
+        let (keyspace, ttl) = match self.get_keyspace_for_message(&message, milestone_index) {
+            Some(keyspace_and_ttl) => keyspace_and_ttl,
+            // none of the dropped message records are stored
+            None => return Ok(()),
+        };
+        #[cfg(not(feature = "filter"))]
+        let (keyspace, ttl) = (self.get_keyspace(), self.get_ttl(&self.default_keyspace, None));
         // Insert parents/children
         self.insert_parents(
             inherent_worker,
@@ -166,6 +198,8 @@ impl<T: ImportMode> Importer<T> {
             &message.parents(),
             MilestoneIndex(milestone_index),
             metadata.ledger_inclusion_state.clone(),
+            &keyspace,
+            ttl,
         )?;
         // insert payload (if any)
         if let Some(payload) = message.payload() {
@@ -177,11 +211,13 @@ impl<T: ImportMode> Importer<T> {
                 MilestoneIndex(milestone_index),
                 metadata.ledger_inclusion_state.clone(),
                 Some(metadata.clone()),
+                &keyspace,
+                ttl,
             )?;
         }
         let message_tuple = (message, metadata);
         // store message and metadata
-        self.insert(inherent_worker, message_id, message_tuple)
+        self.insert(inherent_worker, &keyspace, message_id, message_tuple, ttl)
     }
 
     fn insert_parents<I: Inherent>(
@@ -191,16 +227,18 @@ impl<T: ImportMode> Importer<T> {
         parents: &[MessageId],
         milestone_index: MilestoneIndex,
         inclusion_state: Option<LedgerInclusionState>,
+        keyspace: &ChronicleKeyspace,
+        ttl: u32,
     ) -> anyhow::Result<()> {
         let partition_id = self.get_partition_id(milestone_index);
         for parent_id in parents {
             let partitioned = Partitioned::new(*parent_id, partition_id, milestone_index.0);
             let parent_record = ParentRecord::new(*message_id, inclusion_state);
-            self.insert(inherent_worker, partitioned, parent_record)?;
+            self.insert(inherent_worker, keyspace, partitioned, parent_record, ttl)?;
             // insert hint record
             let hint = Hint::parent(parent_id.to_string());
             let partition = Partition::new(partition_id, *milestone_index);
-            self.insert(inherent_worker, hint, partition)?;
+            self.insert(inherent_worker, keyspace, hint, partition, ttl)?;
         }
         Ok(())
     }
@@ -213,6 +251,8 @@ impl<T: ImportMode> Importer<T> {
         milestone_index: MilestoneIndex,
         inclusion_state: Option<LedgerInclusionState>,
         metadata: Option<MessageMetadata>,
+        keyspace: &ChronicleKeyspace,
+        ttl: u32,
     ) -> anyhow::Result<()> {
         match payload {
             Payload::Indexation(indexation) => {
@@ -222,6 +262,8 @@ impl<T: ImportMode> Importer<T> {
                     Indexation(hex::encode(indexation.index())),
                     milestone_index,
                     inclusion_state,
+                    keyspace,
+                    ttl,
                 )?;
             }
             Payload::Transaction(transaction) => {
@@ -233,6 +275,8 @@ impl<T: ImportMode> Importer<T> {
                     inclusion_state,
                     milestone_index,
                     metadata,
+                    keyspace,
+                    ttl,
                 )?;
             }
             Payload::Milestone(milestone) => {
@@ -241,9 +285,20 @@ impl<T: ImportMode> Importer<T> {
                 if metadata.is_some() && parents_check {
                     self.insert(
                         inherent_worker,
+                        keyspace,
                         MilestoneIndex(ms_index),
                         (*message_id, milestone.clone()),
+                        ttl,
                     )?;
+                    // index the milestone by its timestamp
+                    self.insert(
+                        inherent_worker,
+                        keyspace,
+                        MilestoneTimestamp(milestone.essence().timestamp()),
+                        (MilestoneIndex(ms_index), *message_id),
+                        ttl,
+                    )?;
+                    self.insert_receipt(inherent_worker, milestone, keyspace)?;
                 }
             }
             e => {
@@ -253,6 +308,25 @@ impl<T: ImportMode> Importer<T> {
         }
         Ok(())
     }
+    /// Insert the receipt carried by a milestone, along with its migrated funds and the treasury output it created.
+    /// They are written without a TTL, so the current treasury and the migration history are never lost.
+    fn insert_receipt<I: Inherent>(
+        &self,
+        inherent_worker: &I,
+        milestone: &MilestonePayload,
+        keyspace: &ChronicleKeyspace,
+    ) -> anyhow::Result<()> {
+        if let Some(rows) = ReceiptRows::new(milestone) {
+            for (tail_transaction_hash, migrated_funds) in rows.migrated_funds {
+                self.insert(inherent_worker, keyspace, tail_transaction_hash, migrated_funds, 0)?;
+            }
+            if let Some(treasury) = rows.treasury {
+                self.insert(inherent_worker, keyspace, Synckey, treasury, 0)?;
+            }
+            self.insert(inherent_worker, keyspace, Synckey, rows.receipt, 0)?;
+        }
+        Ok(())
+    }
     fn insert_index<I: Inherent>(
         &self,
         inherent_worker: &I,
@@ -260,15 +334,17 @@ impl<T: ImportMode> Importer<T> {
         index: Indexation,
         milestone_index: MilestoneIndex,
         inclusion_state: Option<LedgerInclusionState>,
+        keyspace: &ChronicleKeyspace,
+        ttl: u32,
     ) -> anyhow::Result<()> {
         let partition_id = self.get_partition_id(milestone_index);
         let partitioned = Partitioned::new(index.clone(), partition_id, milestone_index.0);
         let index_record = IndexationRecord::new(*message_id, inclusion_state);
-        self.insert(inherent_worker, partitioned, index_record)?;
+        self.insert(inherent_worker, keyspace, partitioned, index_record, ttl)?;
         // insert hint record
         let hint = Hint::index(index.0);
         let partition = Partition::new(partition_id, *milestone_index);
-        self.insert(inherent_worker, hint, partition)
+        self.insert(inherent_worker, keyspace, hint, partition, ttl)
     }
     fn insert_transaction<I: Inherent>(
         &mut self,
@@ -279,6 +355,8 @@ impl<T: ImportMode> Importer<T> {
         ledger_inclusion_state: Option<LedgerInclusionState>,
         milestone_index: MilestoneIndex,
         metadata: Option<MessageMetadata>,
+        keyspace: &ChronicleKeyspace,
+        ttl: u32,
     ) -> anyhow::Result<()> {
         let transaction_id = transaction.id();
         let unlock_blocks = transaction.unlock_blocks();
@@ -304,6 +382,8 @@ impl<T: ImportMode> Importer<T> {
                         input_data,
                         ledger_inclusion_state,
                         confirmed_milestone_index,
+                        keyspace,
+                        ttl,
                     )?;
                     // this is the spent_output which the input is spending from
                     let output_id = utxo_input.output_id();
@@ -317,6 +397,8 @@ impl<T: ImportMode> Importer<T> {
                         unlock_data,
                         ledger_inclusion_state,
                         confirmed_milestone_index,
+                        keyspace,
+                        ttl,
                     )?;
                 } else if let Input::Treasury(treasury_input) = input {
                     let input_data = InputData::treasury(treasury_input.clone());
@@ -329,6 +411,8 @@ impl<T: ImportMode> Importer<T> {
                         input_data,
                         ledger_inclusion_state,
                         confirmed_milestone_index,
+                        keyspace,
+                        ttl,
                     )?;
                 };
             }
@@ -342,6 +426,8 @@ impl<T: ImportMode> Importer<T> {
                     output.clone(),
                     ledger_inclusion_state,
                     confirmed_milestone_index,
+                    keyspace,
+                    ttl,
                 )?;
                 // insert address row
                 self.insert_address(
@@ -351,6 +437,8 @@ impl<T: ImportMode> Importer<T> {
                     output_index as u16,
                     milestone_index,
                     ledger_inclusion_state,
+                    keyspace,
+                    ttl,
                 )?;
             }
             if let Some(payload) = regular.payload() {
@@ -362,11 +450,31 @@ impl<T: ImportMode> Importer<T> {
                     milestone_index,
                     ledger_inclusion_state,
                     metadata,
+                    keyspace,
+                    ttl,
                 )?;
             }
         };
         Ok(())
     }
+    /// Mark an output spent. The output is stored in the keyspace of the message which created it, which isn't known
+    /// here, so the marker goes into every keyspace messages can be stored in. It is written without a TTL, so it
+    /// never expires before the output.
+    fn insert_spent<I: Inherent>(
+        &self,
+        inherent_worker: &I,
+        output_id: OutputId,
+        spent_record: SpentRecord,
+    ) -> anyhow::Result<()> {
+        #[cfg(feature = "filter")]
+        for keyspace in self.filter_config.keyspaces(self.default_keyspace.name()) {
+            let keyspace = ChronicleKeyspace::new(keyspace.to_owned());
+            self.insert(inherent_worker, &keyspace, output_id, spent_record.clone(), 0)?;
+        }
+        #[cfg(not(feature = "filter"))]
+        self.insert(inherent_worker, &self.get_keyspace(), output_id, spent_record, 0)?;
+        Ok(())
+    }
     fn insert_input<I: Inherent>(
         &self,
         inherent_worker: &I,
@@ -376,11 +484,20 @@ impl<T: ImportMode> Importer<T> {
         input_data: InputData,
         inclusion_state: Option<LedgerInclusionState>,
         milestone_index: Option<MilestoneIndex>,
+        keyspace: &ChronicleKeyspace,
+        ttl: u32,
     ) -> anyhow::Result<()> {
+        // mark the output consumed by an included transaction as spent
+        if let (InputData::Utxo(utxo_input, _), Some(LedgerInclusionState::Included), Some(milestone_index)) =
+            (&input_data, inclusion_state, milestone_index)
+        {
+            let spent_record = SpentRecord::new(*transaction_id, *message_id, milestone_index);
+            self.insert_spent(inherent_worker, *utxo_input.output_id(), spent_record)?;
+        }
         // -input variant: (InputTransactionId, InputIndex) -> UTXOInput data column
         let input_id = (*transaction_id, index);
         let transaction_record = TransactionRecord::input(*message_id, input_data, inclusion_state, milestone_index);
-        self.insert(inherent_worker, input_id, transaction_record)
+        self.insert(inherent_worker, keyspace, input_id, transaction_record, ttl)
     }
     fn insert_unlock<I: Inherent>(
         &self,
@@ -391,11 +508,13 @@ impl<T: ImportMode> Importer<T> {
         unlock_data: UnlockData,
         inclusion_state: Option<LedgerInclusionState>,
         milestone_index: Option<MilestoneIndex>,
+        keyspace: &ChronicleKeyspace,
+        ttl: u32,
     ) -> anyhow::Result<()> {
         // -unlock variant: (UtxoInputTransactionId, UtxoInputOutputIndex) -> Unlock data column
         let utxo_id = (*utxo_transaction_id, utxo_index);
         let transaction_record = TransactionRecord::unlock(*message_id, unlock_data, inclusion_state, milestone_index);
-        self.insert(inherent_worker, utxo_id, transaction_record)
+        self.insert(inherent_worker, keyspace, utxo_id, transaction_record, ttl)
     }
     fn insert_output<I: Inherent>(
         &self,
@@ -406,11 +525,13 @@ impl<T: ImportMode> Importer<T> {
         output: Output,
         inclusion_state: Option<LedgerInclusionState>,
         milestone_index: Option<MilestoneIndex>,
+        keyspace: &ChronicleKeyspace,
+        ttl: u32,
     ) -> anyhow::Result<()> {
         // -output variant: (OutputTransactionId, OutputIndex) -> Output data column
         let output_id = (*transaction_id, index);
         let transaction_record = TransactionRecord::output(*message_id, output, inclusion_state, milestone_index);
-        self.insert(inherent_worker, output_id, transaction_record)
+        self.insert(inherent_worker, keyspace, output_id, transaction_record, ttl)
     }
     fn insert_address<I: Inherent>(
         &self,
@@ -420,6 +541,8 @@ impl<T: ImportMode> Importer<T> {
         index: u16,
         milestone_index: MilestoneIndex,
         inclusion_state: Option<LedgerInclusionState>,
+        keyspace: &ChronicleKeyspace,
+        ttl: u32,
     ) -> anyhow::Result<()> {
         let partition_id = self.get_partition_id(milestone_index);
         let output_type = output.kind();
@@ -430,11 +553,11 @@ impl<T: ImportMode> Importer<T> {
                     let partitioned = Partitioned::new(*ed_address, partition_id, milestone_index.0);
                     let address_record =
                         AddressRecord::new(output_type, *transaction_id, index, sls.amount(), inclusion_state);
-                    self.insert(inherent_worker, partitioned, address_record)?;
+                    self.insert(inherent_worker, keyspace, partitioned, address_record, ttl)?;
                     // insert hint record
                     let hint = Hint::address(ed_address.to_string());
                     let partition = Partition::new(partition_id, *milestone_index);
-                    self.insert(inherent_worker, hint, partition)?;
+                    self.insert(inherent_worker, keyspace, hint, partition, ttl)?;
                 };
             }
             Output::SignatureLockedDustAllowance(slda) => {
@@ -443,11 +566,11 @@ impl<T: ImportMode> Importer<T> {
                     let partitioned = Partitioned::new(*ed_address, partition_id, milestone_index.0);
                     let address_record =
                         AddressRecord::new(output_type, *transaction_id, index, slda.amount(), inclusion_state);
-                    self.insert(inherent_worker, partitioned, address_record)?;
+                    self.insert(inherent_worker, keyspace, partitioned, address_record, ttl)?;
                     // insert hint record
                     let hint = Hint::address(ed_address.to_string());
                     let partition = Partition::new(partition_id, *milestone_index);
-                    self.insert(inherent_worker, hint, partition)?;
+                    self.insert(inherent_worker, keyspace, hint, partition, ttl)?;
                 };
             }
             e => {
@@ -459,20 +582,54 @@ impl<T: ImportMode> Importer<T> {
         }
         Ok(())
     }
-    fn insert<I, K, V>(&self, inherent_worker: &I, key: K, value: V) -> anyhow::Result<()>
+    /// Note: the value is inserted with the provided time-to-live, zero means it never expires
+    fn insert<I, K, V>(
+        &self,
+        inherent_worker: &I,
+        keyspace: &ChronicleKeyspace,
+        key: K,
+        value: V,
+        ttl: u32,
+    ) -> anyhow::Result<()>
     where
         I: Inherent,
         K: 'static + Send + Clone,
         V: 'static + Send + Clone,
-        ChronicleKeyspace: Insert<K, V>,
+        ChronicleKeyspace: Insert<K, TTL<V>>,
     {
-        let req = self
-            .default_keyspace
-            .insert(&key, &value)
-            .consistency(Consistency::One)
-            .build()?;
-        let worker = inherent_worker.inherent_boxed(key, value);
+        let value = TTL::new(value, ttl);
+        let req = keyspace.insert(&key, &value).consistency(Consistency::One).build()?;
+        let worker = inherent_worker.inherent_boxed(keyspace.clone(), key, value);
         req.send_local(worker);
         Ok(())
     }
 }
+
+#[async_trait::async_trait]
+impl<H: ChronicleBrokerScope> EventLoop<BrokerHandle<H>> for Importer<Snapshot> {
+    async fn event_loop(
+        &mut self,
+        status: Result<(), Need>,
+        supervisor: &mut Option<BrokerHandle<H>>,
+    ) -> Result<(), Need> {
+        status?;
+        info!("{} is running", self.get_name());
+        self.service.update_status(ServiceStatus::Running);
+        let event = BrokerEvent::Children(BrokerChild::Importer(self.service.clone(), status, self.parallelism));
+        if let Some(supervisor) = supervisor {
+            supervisor.send(event).ok();
+            self.import_snapshots(supervisor).await.map_err(|e| {
+                error!("Unable to import snapshot files. Error: {}", e);
+                Need::Abort
+            })?;
+            if self.service.is_stopping() {
+                // the importer got shutdown before importing all the snapshot files
+                return Err(Need::Abort);
+            }
+            info!("Imported the snapshot files: {}", self.get_name());
+            Ok(())
+        } else {
+            Err(Need::Abort)
+        }
+    }
+}
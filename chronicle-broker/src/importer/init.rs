@@ -140,17 +140,19 @@ impl<T: ImportMode> Importer<T> {
         milestone_index: u32,
         milestone_data: &mut IntoIter<MessageId, FullMessage>,
     ) -> anyhow::Result<()> {
-        let importer_handle = self
-            .handle
-            .clone()
-            .ok_or_else(|| anyhow!("No importer handle available!"))?;
-        let keyspace = self.get_keyspace();
-        let inherent_worker =
-            MilestoneDataWorker::new(importer_handle, keyspace, milestone_index, self.retries_per_query);
+        let inherent_worker = self.milestone_data_worker(milestone_index, ImporterEvent::ProcessMore)?;
+        self.insert_messages_with(&inherent_worker, milestone_data)
+    }
+    /// Insert the next messages of the milestone data with the given milestone data worker
+    pub(crate) fn insert_messages_with<I: Inherent>(
+        &mut self,
+        inherent_worker: &I,
+        milestone_data: &mut IntoIter<MessageId, FullMessage>,
+    ) -> anyhow::Result<()> {
         for _ in 0..self.parallelism {
             if let Some((message_id, FullMessage(message, metadata))) = milestone_data.next() {
                 // Insert the message
-                self.insert_message_with_metadata(&inherent_worker, message_id, message, metadata)?;
+                self.insert_message_with_metadata(inherent_worker, message_id, message, metadata)?;
             } else {
                 // break for loop
                 break;
@@ -159,3 +161,70 @@ impl<T: ImportMode> Importer<T> {
         Ok(())
     }
 }
+
+#[async_trait::async_trait]
+impl<H: ChronicleBrokerScope> Init<BrokerHandle<H>> for Importer<Snapshot> {
+    async fn init(&mut self, status: Result<(), Need>, supervisor: &mut Option<BrokerHandle<H>>) -> Result<(), Need> {
+        info!(
+            "{} is Initializing, with permanode keyspace: {}",
+            self.get_name(),
+            self.default_keyspace.name()
+        );
+        if let Some(supervisor) = supervisor {
+            self.service.update_status(ServiceStatus::Initializing);
+            let event = BrokerEvent::Children(BrokerChild::Importer(self.service.clone(), Ok(()), self.parallelism));
+            supervisor.send(event).ok();
+            // The ledger child applies its diffs on top of the same checkpoint the snapshots would move
+            if get_config_async().await.broker_config.ledger {
+                error!("Unable to import snapshots while the ledger is running, disable the broker ledger first");
+                return Err(Need::Abort);
+            }
+            let snapshots = snapshot_files(&self.file_path).map_err(|e| {
+                error!("Unable to read snapshot files. Error: {}", e);
+                Need::Abort
+            })?;
+            let network_id = self.keyspace_network_id();
+            if let Some((path, header)) = snapshots.iter().find(|(_, header)| header.network_id != network_id) {
+                error!(
+                    "Snapshot {:?} belongs to network {}, while keyspace {} stores network {}",
+                    path,
+                    header.network_id,
+                    self.default_keyspace.name(),
+                    network_id
+                );
+                return Err(Need::Abort);
+            }
+            for (path, _) in snapshots.iter() {
+                self.log_file_size += std::fs::metadata(path)
+                    .map_err(|e| {
+                        error!("Unable to read snapshot file metadata. Error: {}", e);
+                        Need::Abort
+                    })?
+                    .len();
+            }
+            self.from_ms = snapshots
+                .iter()
+                .map(|(_, header)| header.sep_index)
+                .min()
+                .unwrap_or_default();
+            self.to_ms = snapshots
+                .iter()
+                .map(|(_, header)| header.ledger_index)
+                .max()
+                .unwrap_or_default();
+            self.snapshots = snapshots;
+            let importer_session = ImporterSession::ProgressBar {
+                log_file_size: self.log_file_size,
+                from_ms: self.from_ms,
+                to_ms: self.to_ms,
+                ms_bytes_size: 0,
+                milestone_index: 0,
+                skipped: true,
+            };
+            supervisor.send(BrokerEvent::Importer(importer_session)).ok();
+            status
+        } else {
+            Err(Need::Abort)
+        }
+    }
+}
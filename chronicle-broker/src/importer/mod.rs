@@ -12,13 +12,22 @@ use crate::{
 };
 use bee_message::{
     output::Output,
-    payload::transaction::{
-        Essence,
-        TransactionPayload,
+    payload::{
+        transaction::{
+            Essence,
+            TransactionPayload,
+        },
+        Payload,
     },
+    prelude::MilestoneIndex,
 };
+#[cfg(feature = "filter")]
+use chronicle_common::config::FilterConfig;
 use chronicle_common::{
-    config::PartitionConfig,
+    config::{
+        PartitionConfig,
+        StorageConfig,
+    },
     Synckey,
 };
 use chronicle_storage::access::SyncRecord;
@@ -39,12 +48,17 @@ use std::{
 
 mod event_loop;
 mod init;
+mod snapshot;
 mod terminating;
 
+pub use snapshot::*;
+
 /// Import all records to all tables
 pub struct All;
-/// Import analytics records only which are stored in analytics table
+/// Import analytics records only which are stored in analytics table, along with the milestones time index
 pub struct Analytics;
+/// Import the ledger state of Chrysalis full and delta snapshots into the ledger tables
+pub struct Snapshot;
 
 /// Defines the Importer Mode
 pub trait ImportMode: Sized + Send + 'static {
@@ -58,8 +72,12 @@ impl ImportMode for All {
             e
         })?;
         let milestone_index = milestone_data.milestone_index();
+        // The extended analytic record is inserted along with the first messages, so the
+        // milestone is only synced once it is in the database as well
+        let inherent_worker = importer.milestone_data_worker(milestone_index, ImporterEvent::ProcessMore)?;
+        importer.insert_extended_analytic_record(&inherent_worker, &milestone_data)?;
         let mut iterator = milestone_data.into_iter();
-        importer.insert_some_messages(milestone_index, &mut iterator)?;
+        importer.insert_messages_with(&inherent_worker, &mut iterator)?;
         importer
             .in_progress_milestones_data
             .insert(milestone_index, (iterator, analytic_record));
@@ -74,8 +92,14 @@ impl ImportMode for Analytics {
             e
         })?;
         let milestone_index = milestone_data.milestone_index();
+        // The milestone is imported once all of its records are in the database
+        let inherent_worker = importer.milestone_data_worker(milestone_index, |milestone_index| {
+            ImporterEvent::CqlResult(Ok(milestone_index))
+        })?;
+        importer.insert_analytic_record(&inherent_worker, &analytic_record)?;
+        importer.insert_extended_analytic_record(&inherent_worker, &milestone_data)?;
+        importer.insert_milestone_timestamp(&inherent_worker, &milestone_data)?;
         let iterator = milestone_data.into_iter();
-        importer.insert_analytic_record(&analytic_record)?;
         // note: iterator is not needed to presist analytic record in Analytics mode,
         // however we kept them for simplicty sake.
         importer
@@ -170,6 +194,11 @@ pub struct Importer<T> {
     default_keyspace: ChronicleKeyspace,
     /// The partition configuration
     partition_config: PartitionConfig,
+    /// The storage configuration, used to lookup the keyspaces default time-to-live
+    storage_config: StorageConfig,
+    /// The filter configuration, loaded once to route the messages to their keyspace
+    #[cfg(feature = "filter")]
+    filter_config: FilterConfig,
     /// The number of retires per query
     retries_per_query: usize,
     /// The chronicle id
@@ -191,14 +220,17 @@ pub struct Importer<T> {
     inbox: ImporterInbox,
     /// The flag of end of file
     eof: bool,
+    /// The snapshot files to import in order, along with their headers
+    snapshots: Vec<(PathBuf, SnapshotHeader)>,
     /// Import mode marker
     _mode: std::marker::PhantomData<T>,
 }
 
 impl<H: ChronicleBrokerScope, T: ImportMode> ActorBuilder<BrokerHandle<H>> for ImporterBuilder<T> {}
+impl<H: ChronicleBrokerScope> ActorBuilder<BrokerHandle<H>> for ImporterBuilder<Snapshot> {}
 
 /// Implementation of builder
-impl<T: ImportMode> Builder for ImporterBuilder<T> {
+impl<T: Send + 'static> Builder for ImporterBuilder<T> {
     type State = Importer<T>;
     fn build(self) -> Self::State {
         // Get the first keyspace or default to "permanode"
@@ -213,7 +245,8 @@ impl<T: ImportMode> Builder for ImporterBuilder<T> {
                 .and_then(|keyspace| Some(keyspace.name.clone()))
                 .unwrap_or("permanode".to_owned()),
         );
-        let partition_config = config.storage_config.partition_config;
+        let partition_config = config.storage_config.partition_config.clone();
+        let storage_config = config.storage_config;
         let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
         let handle = Some(ImporterHandle { tx });
         let inbox = ImporterInbox { rx };
@@ -230,6 +263,9 @@ impl<T: ImportMode> Builder for ImporterBuilder<T> {
             to_ms: 0,
             default_keyspace,
             partition_config,
+            storage_config,
+            #[cfg(feature = "filter")]
+            filter_config: config.filter_config,
             parallelism: self.parallelism.unwrap_or(10),
             chronicle_id: self.chronicle_id.unwrap(),
             in_progress_milestones_data: HashMap::new(),
@@ -241,6 +277,7 @@ impl<T: ImportMode> Builder for ImporterBuilder<T> {
             handle,
             inbox,
             eof: false,
+            snapshots: Vec::new(),
             _mode: std::marker::PhantomData::<T>,
         }
         .set_name()
@@ -263,29 +300,92 @@ impl<T> Name for Importer<T> {
         self.service.get_name()
     }
 }
-impl Importer<Analytics> {
-    pub(crate) fn insert_analytic_record(&self, analytic_record: &AnalyticRecord) -> anyhow::Result<()> {
-        if let Some(importer_handle) = self.handle.clone() {
-            let keyspace = self.get_keyspace();
-            let worker = AnalyzeWorker::boxed(
-                importer_handle,
-                keyspace,
-                analytic_record.clone(),
-                self.retries_per_query,
+impl<T> Importer<T> {
+    /// Create the milestone data worker of a milestone, which sends the given event once all of its inserts succeeded
+    pub(crate) fn milestone_data_worker(
+        &self,
+        milestone_index: u32,
+        on_success: fn(u32) -> ImporterEvent,
+    ) -> anyhow::Result<MilestoneDataWorker<ChronicleKeyspace>> {
+        let importer_handle = self
+            .handle
+            .clone()
+            .ok_or_else(|| anyhow!("No importer handle available!"))?;
+        Ok(MilestoneDataWorker::new(
+            importer_handle,
+            self.get_keyspace(),
+            milestone_index,
+            self.retries_per_query,
+            on_success,
+        ))
+    }
+    /// Index the milestone of the milestone data by its timestamp, which backfills the `milestones_by_time` table
+    /// for milestones which were synced before it existed
+    pub(crate) fn insert_milestone_timestamp<I: Inherent>(
+        &self,
+        inherent_worker: &I,
+        milestone_data: &MilestoneData,
+    ) -> anyhow::Result<()> {
+        let milestone_index = milestone_data.milestone_index();
+        let milestone_message = milestone_data.messages().iter().find_map(|(message_id, full_message)| {
+            match full_message.message().payload() {
+                Some(Payload::Milestone(milestone)) if *milestone.essence().index() == milestone_index => {
+                    Some((*message_id, milestone.essence().timestamp()))
+                }
+                _ => None,
+            }
+        });
+        let (message_id, timestamp) = milestone_message
+            .ok_or_else(|| anyhow!("No milestone message for milestone index: {}", milestone_index))?;
+        let key = MilestoneTimestamp(timestamp);
+        let value = (MilestoneIndex(milestone_index), message_id);
+        let worker = inherent_worker.inherent_boxed(self.get_keyspace(), key, value);
+        self.default_keyspace
+            .insert_prepared(&key, &value)
+            .consistency(Consistency::One)
+            .build()?
+            .send_local(worker);
+        Ok(())
+    }
+    /// Insert the extended analytic record of the milestone data
+    pub(crate) fn insert_extended_analytic_record<I: Inherent>(
+        &self,
+        inherent_worker: &I,
+        milestone_data: &MilestoneData,
+    ) -> anyhow::Result<()> {
+        let extended_analytic_record = milestone_data.get_extended_analytic_record().map_err(|e| {
+            error!(
+                "Unable to get extended analytic record for milestone data. Error: {}",
+                e
             );
-            self.default_keyspace
-                .insert_prepared(&Synckey, analytic_record)
-                .consistency(Consistency::One)
-                .build()?
-                .send_local(worker);
-            Ok(())
-        } else {
-            bail!("Expected importer handle in order to import/insert analytic record");
-        }
+            e
+        })?;
+        let worker = inherent_worker.inherent_boxed(self.get_keyspace(), Synckey, extended_analytic_record.clone());
+        self.default_keyspace
+            .insert_prepared(&Synckey, &extended_analytic_record)
+            .consistency(Consistency::One)
+            .build()?
+            .send_local(worker);
+        Ok(())
+    }
+}
+impl Importer<Analytics> {
+    pub(crate) fn insert_analytic_record<I: Inherent>(
+        &self,
+        inherent_worker: &I,
+        analytic_record: &AnalyticRecord,
+    ) -> anyhow::Result<()> {
+        let worker = inherent_worker.inherent_boxed(self.get_keyspace(), Synckey, analytic_record.clone());
+        self.default_keyspace
+            .insert_prepared(&Synckey, analytic_record)
+            .consistency(Consistency::One)
+            .build()?
+            .send_local(worker);
+        Ok(())
     }
 }
 #[async_trait::async_trait]
-impl<H: ChronicleBrokerScope, T: ImportMode> AknShutdown<Importer<T>> for BrokerHandle<H> {
+impl<H: ChronicleBrokerScope, T: Send + 'static> AknShutdown<Importer<T>> for BrokerHandle<H> {
     async fn aknowledge_shutdown(self, mut state: Importer<T>, status: Result<(), Need>) {
         let parallelism = state.parallelism;
         state.service.update_status(ServiceStatus::Stopped);
@@ -324,6 +424,8 @@ where
     pub(crate) any_error: std::sync::atomic::AtomicBool,
     /// The number of retires
     pub(crate) retries: usize,
+    /// The event sent to the importer once every insert succeeded
+    pub(crate) on_success: fn(u32) -> ImporterEvent,
 }
 
 impl<S> AtomicImporterHandle<S>
@@ -345,6 +447,7 @@ where
             milestone_index,
             any_error,
             retries,
+            on_success: ImporterEvent::ProcessMore,
         }
     }
 }
@@ -356,8 +459,7 @@ where
 {
     /// Create a new atomic importer worker with an atomic importer handle, a keyspace, a key, a value, and a number of
     /// retries
-    pub fn new(handle: std::sync::Arc<AtomicImporterHandle<S>>, key: K, value: V) -> Self {
-        let keyspace = handle.keyspace.clone();
+    pub fn new(handle: std::sync::Arc<AtomicImporterHandle<S>>, keyspace: S, key: K, value: V) -> Self {
         let retries = handle.retries;
         Self {
             handle,
@@ -369,8 +471,8 @@ where
     }
     /// Create a new boxed atomic importer worker with an atomic importer handle, a keyspace, a key, a value, and a
     /// number of retries
-    pub fn boxed(handle: std::sync::Arc<AtomicImporterHandle<S>>, key: K, value: V) -> Box<Self> {
-        Box::new(Self::new(handle, key, value))
+    pub fn boxed(handle: std::sync::Arc<AtomicImporterHandle<S>>, keyspace: S, key: K, value: V) -> Box<Self> {
+        Box::new(Self::new(handle, keyspace, key, value))
     }
 }
 
@@ -420,8 +522,8 @@ where
         if any_error {
             let _ = self.handle.send(ImporterEvent::CqlResult(Err(self.milestone_index)));
         } else {
-            // tell importer to process more
-            let _ = self.handle.send(ImporterEvent::ProcessMore(self.milestone_index));
+            // tell importer to process more, or that the milestone is imported
+            let _ = self.handle.send((self.on_success)(self.milestone_index));
         }
     }
 }
@@ -571,20 +673,27 @@ impl<S> MilestoneDataWorker<S>
 where
     S: 'static + Insert<Synckey, SyncRecord>,
 {
-    /// Create a new milestone data worker with an importer handle, a keyspace, a milestone index, and a number of
-    /// retries
-    fn new(importer_handle: ImporterHandle, keyspace: S, milestone_index: u32, retries: usize) -> Self {
+    /// Create a new milestone data worker with an importer handle, a keyspace, a milestone index, a number of
+    /// retries, and the event to send once every insert succeeded
+    fn new(
+        importer_handle: ImporterHandle,
+        keyspace: S,
+        milestone_index: u32,
+        retries: usize,
+        on_success: fn(u32) -> ImporterEvent,
+    ) -> Self {
         let any_error = std::sync::atomic::AtomicBool::new(false);
-        let atomic_handle =
+        let mut atomic_handle =
             AtomicImporterHandle::new(importer_handle, keyspace.clone(), milestone_index, any_error, retries);
+        atomic_handle.on_success = on_success;
         let arc_handle = std::sync::Arc::new(atomic_handle);
         Self { arc_handle }
     }
 }
 
-/// The inherent trait to return a boxed worker for a given key/value pair
+/// The inherent trait to return a boxed worker for a given key/value pair in a keyspace
 pub(crate) trait Inherent {
-    fn inherent_boxed<K, V>(&self, key: K, value: V) -> Box<dyn Worker>
+    fn inherent_boxed<K, V>(&self, keyspace: ChronicleKeyspace, key: K, value: V) -> Box<dyn Worker>
     where
         ChronicleKeyspace: 'static + Insert<K, V> + Insert<Synckey, SyncRecord>,
         K: 'static + Send + Clone,
@@ -594,91 +703,12 @@ pub(crate) trait Inherent {
 /// Implement the `Inherent` trait for the milestone data worker, so we can get the atomic importer worker
 /// which contains the atomic importer handle of the milestone data worker
 impl Inherent for MilestoneDataWorker<ChronicleKeyspace> {
-    fn inherent_boxed<K, V>(&self, key: K, value: V) -> Box<dyn Worker>
+    fn inherent_boxed<K, V>(&self, keyspace: ChronicleKeyspace, key: K, value: V) -> Box<dyn Worker>
     where
         ChronicleKeyspace: 'static + Insert<K, V> + Insert<Synckey, SyncRecord>,
         K: 'static + Send + Clone,
         V: 'static + Send + Clone,
     {
-        AtomicImporterWorker::boxed(self.arc_handle.clone(), key, value)
-    }
-}
-
-/// Scylla worker implementation for importer when running in Analytics mode
-#[derive(Clone)]
-pub struct AnalyzeWorker<S>
-where
-    S: 'static + Insert<Synckey, AnalyticRecord>,
-{
-    /// The importer handle
-    handle: ImporterHandle,
-    /// The keyspace
-    keyspace: S,
-    /// The `analytics` table row
-    analytic_record: AnalyticRecord,
-    /// The number of retries
-    retries: usize,
-}
-
-impl<S> AnalyzeWorker<S>
-where
-    S: 'static + Insert<Synckey, AnalyticRecord>,
-{
-    /// Create a new AnalyzeWorker with an importer handle, a keyspace, a `analytics` table row (`AnalyticRecord`), and
-    /// a number of retries
-    pub fn new(handle: ImporterHandle, keyspace: S, analytic_record: AnalyticRecord, retries: usize) -> Self {
-        Self {
-            handle,
-            keyspace,
-            analytic_record,
-            retries,
-        }
-    }
-    /// Create a new boxed AnalyzeWorker with an importer handle, a keyspace, a `analytics` table row
-    /// (`AnalyticRecord`), and a number of retries
-    pub fn boxed(handle: ImporterHandle, keyspace: S, analytic_record: AnalyticRecord, retries: usize) -> Box<Self> {
-        Box::new(Self::new(handle, keyspace, analytic_record, retries))
-    }
-}
-
-/// Implement the Scylla `Worker` trait
-impl<S> Worker for AnalyzeWorker<S>
-where
-    S: 'static + Insert<Synckey, AnalyticRecord>,
-{
-    fn handle_response(self: Box<Self>, giveload: Vec<u8>) -> anyhow::Result<()> {
-        Decoder::from(giveload.try_into()?).get_void()?;
-        let milestone_index = self.analytic_record.milestone_index();
-        self.handle.send(ImporterEvent::CqlResult(Ok(**milestone_index))).ok();
-        Ok(())
-    }
-    fn handle_error(
-        mut self: Box<Self>,
-        mut error: WorkerError,
-        reporter: &Option<ReporterHandle>,
-    ) -> anyhow::Result<()> {
-        if let WorkerError::Cql(ref mut cql_error) = error {
-            if let (Some(id), Some(reporter)) = (cql_error.take_unprepared_id(), reporter) {
-                handle_insert_unprepared_error(&self, &self.keyspace, &Synckey, &self.analytic_record, id, reporter)?;
-                return Ok(());
-            }
-        }
-        if self.retries > 0 {
-            self.retries -= 1;
-            // currently we assume all cql/worker errors are retryable, but we might change this in future
-            // retry inserting an analytic record
-            let req = self
-                .keyspace
-                .insert_query(&Synckey, &self.analytic_record)
-                .consistency(Consistency::One)
-                .build()?;
-            tokio::spawn(async { req.send_global(self) });
-        } else {
-            // no more retries
-            // respond with error
-            let milestone_index = self.analytic_record.milestone_index();
-            let _ = self.handle.send(ImporterEvent::CqlResult(Err(**milestone_index)));
-        }
-        Ok(())
+        AtomicImporterWorker::boxed(self.arc_handle.clone(), keyspace, key, value)
     }
 }
@@ -0,0 +1,927 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::ledger::{
+    BalanceDelta,
+    Ledger,
+    LedgerBuilder,
+    LedgerWriteSender,
+};
+use bee_message::prelude::{
+    Address,
+    Ed25519Address,
+    MilestoneId,
+    MilestoneIndex,
+    MilestonePayload,
+    OutputId,
+    Payload,
+    TransactionId,
+};
+use blake2::{
+    digest::{
+        Update,
+        VariableOutput,
+    },
+    VarBlake2b,
+};
+use chronicle_common::config::KeyspaceConfig;
+use std::{
+    io::{
+        BufReader,
+        Read,
+    },
+    path::Path,
+};
+
+/// The supported version of the snapshot file format
+pub const SNAPSHOT_VERSION: u8 = 1;
+
+/// The receiver of the entries read from a snapshot file, along with the number of bytes read so far
+pub type SnapshotEntryReceiver = tokio::sync::mpsc::Receiver<anyhow::Result<(SnapshotEntry, u64)>>;
+/// The sender of the entries read from a snapshot file, along with the number of bytes read so far
+pub type SnapshotEntrySender = tokio::sync::mpsc::Sender<anyhow::Result<(SnapshotEntry, u64)>>;
+
+/// Get the id of a network from its name, which is the first 8 bytes of the BLAKE2b-256 hash of the name
+pub fn network_id(network_name: &str) -> u64 {
+    let mut hasher = VarBlake2b::new(32).expect("Expected a valid BLAKE2b-256 output size");
+    hasher.update(network_name.as_bytes());
+    let mut network_id = [0u8; 8];
+    hasher.finalize_variable(|hash| network_id.copy_from_slice(&hash[..8]));
+    u64::from_le_bytes(network_id)
+}
+
+/// The kind of a snapshot file
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnapshotKind {
+    /// A full snapshot, which holds the unspent outputs at its ledger index
+    Full,
+    /// A delta snapshot, which holds the milestone diffs following a full snapshot
+    Delta,
+}
+
+/// The header of a Chrysalis snapshot file
+#[derive(Clone, Debug)]
+pub struct SnapshotHeader {
+    /// The kind of the snapshot
+    pub kind: SnapshotKind,
+    /// The time at which the snapshot was taken
+    pub timestamp: u64,
+    /// The network the snapshot belongs to
+    pub network_id: u64,
+    /// The milestone index of the solid entry points
+    pub sep_index: u32,
+    /// The milestone index of the ledger state
+    pub ledger_index: u32,
+    /// The number of solid entry points
+    pub sep_count: u64,
+    /// The number of unspent outputs, only held by full snapshots
+    pub output_count: u64,
+    /// The number of milestone diffs
+    pub milestone_diff_count: u64,
+    /// The id of the milestone which created the treasury output and its amount, only held by full snapshots
+    pub treasury_output: Option<(MilestoneId, u64)>,
+}
+
+impl SnapshotHeader {
+    /// Unpack a snapshot header from the reader
+    pub fn unpack<R: Read>(reader: &mut R) -> anyhow::Result<Self> {
+        let version = u8::unpack(reader)?;
+        ensure!(
+            version == SNAPSHOT_VERSION,
+            "Unsupported snapshot version {}, expected {}",
+            version,
+            SNAPSHOT_VERSION
+        );
+        let kind = match u8::unpack(reader)? {
+            0 => SnapshotKind::Full,
+            1 => SnapshotKind::Delta,
+            kind => bail!("Unknown snapshot kind {}", kind),
+        };
+        let timestamp = u64::unpack(reader)?;
+        let network_id = u64::unpack(reader)?;
+        let sep_index = u32::unpack(reader)?;
+        let ledger_index = u32::unpack(reader)?;
+        let sep_count = u64::unpack(reader)?;
+        let output_count = match kind {
+            SnapshotKind::Full => u64::unpack(reader)?,
+            SnapshotKind::Delta => 0,
+        };
+        let milestone_diff_count = u64::unpack(reader)?;
+        let treasury_output = match kind {
+            SnapshotKind::Full => Some((MilestoneId::unpack(reader)?, u64::unpack(reader)?)),
+            SnapshotKind::Delta => None,
+        };
+        Ok(Self {
+            kind,
+            timestamp,
+            network_id,
+            sep_index,
+            ledger_index,
+            sep_count,
+            output_count,
+            milestone_diff_count,
+            treasury_output,
+        })
+    }
+    /// Read the header of a snapshot file
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let mut reader = BufReader::new(std::fs::File::open(path)?);
+        Self::unpack(&mut reader)
+    }
+}
+
+/// A milestone diff of a snapshot file
+#[derive(Clone, Debug)]
+pub struct SnapshotMilestoneDiff {
+    /// The milestone
+    pub milestone: MilestonePayload,
+    /// The id of the milestone which created the treasury output consumed by the receipt of the milestone, and its
+    /// amount
+    pub consumed_treasury: Option<(MilestoneId, u64)>,
+    /// The ledger mutations of the milestone
+    pub diff: MilestoneDiff,
+    /// The balance, dust allowance and output count changes of the addresses touched by the milestone
+    pub(crate) balance_deltas: HashMap<Ed25519Address, BalanceDelta>,
+}
+
+impl SnapshotMilestoneDiff {
+    /// Unpack a milestone diff from the reader
+    pub fn unpack<R: Read>(reader: &mut R) -> anyhow::Result<Self> {
+        // The length prefix of the milestone is not needed, as the payload is self describing
+        let _milestone_len = u32::unpack(reader)?;
+        let milestone = match Payload::unpack(reader)? {
+            Payload::Milestone(milestone) => *milestone,
+            payload => bail!(
+                "Expected a milestone payload in the milestone diff, found {:?}",
+                payload
+            ),
+        };
+        let milestone_index = *milestone.essence().index();
+        let consumed_treasury = match milestone.essence().receipt() {
+            Some(Payload::Receipt(_)) => Some((MilestoneId::unpack(reader)?, u64::unpack(reader)?)),
+            _ => None,
+        };
+        let mut balance_deltas = HashMap::new();
+        let created_count = u64::unpack(reader)?;
+        let mut created = HashMap::new();
+        for _ in 0..created_count {
+            let (output_id, message_id, output) = unpack_output(reader)?;
+            add_balance_delta(&mut balance_deltas, &output, 1);
+            created.insert(
+                output_id,
+                LedgerOutputRecord::new(message_id, output, MilestoneIndex(milestone_index)),
+            );
+        }
+        let consumed_count = u64::unpack(reader)?;
+        let mut consumed = HashMap::new();
+        for _ in 0..consumed_count {
+            let (output_id, _, output) = unpack_output(reader)?;
+            let spent_by = TransactionId::unpack(reader)?;
+            add_balance_delta(&mut balance_deltas, &output, -1);
+            consumed.insert(output_id, spent_by);
+        }
+        Ok(Self {
+            milestone,
+            consumed_treasury,
+            diff: MilestoneDiff::from_parts(milestone_index, created, consumed),
+            balance_deltas,
+        })
+    }
+}
+
+/// An entry of a snapshot file
+pub enum SnapshotEntry {
+    /// The header of the snapshot
+    Header(SnapshotHeader),
+    /// An unspent output of a full snapshot
+    Output(OutputId, LedgerOutputRecord),
+    /// A milestone diff
+    MilestoneDiff(SnapshotMilestoneDiff),
+}
+
+/// Unpack an output entry, which is the output id, the id of the message which created it, and the output itself
+fn unpack_output<R: Read>(reader: &mut R) -> anyhow::Result<(OutputId, MessageId, Output)> {
+    let message_id = MessageId::unpack(reader)?;
+    let output_id = OutputId::unpack(reader)?;
+    let output = Output::unpack(reader)?;
+    Ok((output_id, message_id, output))
+}
+
+/// Add an output to the balance deltas of its address, with the given sign
+fn add_balance_delta(deltas: &mut HashMap<Ed25519Address, BalanceDelta>, output: &Output, sign: i128) {
+    let address = match output {
+        Output::SignatureLockedSingle(output) => output.address(),
+        Output::SignatureLockedDustAllowance(output) => output.address(),
+        _ => return,
+    };
+    let Address::Ed25519(address) = address;
+    deltas.entry(*address).or_default().add(output, sign);
+}
+
+/// Rewind the balances of the addresses touched by a milestone diff of a full snapshot, returning their balance
+/// records as of the milestone and leaving the balances as of the previous milestone
+fn rewind_balances(
+    balances: &mut HashMap<Ed25519Address, BalanceDelta>,
+    milestone_index: u32,
+    balance_deltas: HashMap<Ed25519Address, BalanceDelta>,
+) -> anyhow::Result<Vec<(Ed25519Address, BalanceRecord)>> {
+    let mut records = Vec::with_capacity(balance_deltas.len());
+    for (address, delta) in balance_deltas {
+        let balance = balances.entry(address).or_default();
+        records.push((address, balance.to_record(milestone_index)));
+        balance.add_delta(&delta, -1);
+        ensure!(
+            balance.is_valid(),
+            "Rewinding milestone {} overdraws the balance of address {}",
+            milestone_index,
+            address
+        );
+    }
+    Ok(records)
+}
+
+/// A reader which counts the bytes read from the inner reader
+struct CountingReader<R> {
+    inner: R,
+    read: u64,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.read += read as u64;
+        Ok(read)
+    }
+}
+
+/// Read the entries of a snapshot file and send them in file order, this is blocking and must be spawned as such
+pub fn read_snapshot(path: PathBuf, tx: SnapshotEntrySender) {
+    if let Err(e) = try_read_snapshot(&path, &tx) {
+        let _ = tx.blocking_send(Err(e));
+    }
+}
+
+fn try_read_snapshot(path: &Path, tx: &SnapshotEntrySender) -> anyhow::Result<()> {
+    let mut reader = CountingReader {
+        inner: BufReader::new(std::fs::File::open(path)?),
+        read: 0,
+    };
+    let header = SnapshotHeader::unpack(&mut reader)?;
+    let (kind, ledger_index) = (header.kind, header.ledger_index);
+    let (sep_count, output_count, milestone_diff_count) =
+        (header.sep_count, header.output_count, header.milestone_diff_count);
+    // The solid entry points are not needed to seed the ledger
+    for _ in 0..sep_count {
+        MessageId::unpack(&mut reader)?;
+    }
+    // Stop reading once the receiver is dropped
+    if tx
+        .blocking_send(Ok((SnapshotEntry::Header(header), reader.read)))
+        .is_err()
+    {
+        return Ok(());
+    }
+    if let SnapshotKind::Full = kind {
+        for _ in 0..output_count {
+            let (output_id, message_id, output) = unpack_output(&mut reader)?;
+            // The snapshot doesn't hold the milestone which created the output, so it's created as of the ledger index
+            let record = LedgerOutputRecord::new(message_id, output, MilestoneIndex(ledger_index));
+            if tx
+                .blocking_send(Ok((SnapshotEntry::Output(output_id, record), reader.read)))
+                .is_err()
+            {
+                return Ok(());
+            }
+        }
+    }
+    for _ in 0..milestone_diff_count {
+        let milestone_diff = SnapshotMilestoneDiff::unpack(&mut reader)?;
+        if tx
+            .blocking_send(Ok((SnapshotEntry::MilestoneDiff(milestone_diff), reader.read)))
+            .is_err()
+        {
+            return Ok(());
+        }
+    }
+    Ok(())
+}
+
+/// Read the headers of the snapshot files at the path, which is either a snapshot file or a directory of snapshot
+/// files, and order them to be imported: the latest full snapshot first, then the delta snapshots
+pub fn snapshot_files(path: &Path) -> anyhow::Result<Vec<(PathBuf, SnapshotHeader)>> {
+    let paths = if path.is_dir() {
+        std::fs::read_dir(path)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file())
+            .collect()
+    } else {
+        vec![path.to_path_buf()]
+    };
+    let mut snapshots = Vec::new();
+    for path in paths {
+        match SnapshotHeader::from_file(&path) {
+            Ok(header) => snapshots.push((path, header)),
+            Err(e) => warn!("Skipping {:?}, which is not a snapshot file. Error: {}", path, e),
+        }
+    }
+    ensure!(!snapshots.is_empty(), "No snapshot files in {:?}", path);
+    // Only the latest full snapshot is needed to seed the ledger
+    let latest_full = snapshots
+        .iter()
+        .filter(|(_, header)| header.kind == SnapshotKind::Full)
+        .map(|(_, header)| header.ledger_index)
+        .max();
+    snapshots.retain(|(_, header)| header.kind == SnapshotKind::Delta || Some(header.ledger_index) == latest_full);
+    snapshots.sort_by_key(|(_, header)| (header.kind == SnapshotKind::Delta, header.ledger_index));
+    Ok(snapshots)
+}
+
+/// Bounds the number of snapshot writes in flight
+struct SnapshotWrites {
+    tx: LedgerWriteSender,
+    rx: tokio::sync::mpsc::UnboundedReceiver<anyhow::Result<()>>,
+    in_flight: usize,
+    max_in_flight: usize,
+}
+
+impl SnapshotWrites {
+    fn new(max_in_flight: usize) -> Self {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        Self {
+            tx,
+            rx,
+            in_flight: 0,
+            max_in_flight,
+        }
+    }
+    /// Insert the key value pair through the ledger, awaiting the writes in flight once there are too many of them
+    async fn insert<K, V>(&mut self, ledger: &Ledger, key: K, value: V) -> anyhow::Result<()>
+    where
+        ChronicleKeyspace: Insert<K, V>,
+        K: 'static + Send + Clone,
+        V: 'static + Send + Clone,
+    {
+        ledger.insert(key, value, &self.tx)?;
+        self.in_flight += 1;
+        if self.in_flight >= self.max_in_flight {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+    /// Await the outcome of the writes in flight
+    async fn flush(&mut self) -> anyhow::Result<()> {
+        while self.in_flight > 0 {
+            self.rx
+                .recv()
+                .await
+                .ok_or_else(|| anyhow!("Snapshot write workers dropped without a response"))??;
+            self.in_flight -= 1;
+        }
+        Ok(())
+    }
+}
+
+impl Importer<Snapshot> {
+    /// Get the id of the network stored in the default keyspace
+    pub(crate) fn keyspace_network_id(&self) -> u64 {
+        let network_name = self
+            .storage_config
+            .keyspaces
+            .iter()
+            .find(|keyspace| keyspace.name == *self.default_keyspace.name())
+            .map(|keyspace| keyspace.network_id.clone())
+            .unwrap_or_else(|| KeyspaceConfig::default().network_id);
+        network_id(&network_name)
+    }
+    /// Import the snapshot files in order, seeding the ledger with the full snapshot and moving it forward with the
+    /// delta snapshots
+    pub(crate) async fn import_snapshots<H: ChronicleBrokerScope>(
+        &mut self,
+        supervisor: &BrokerHandle<H>,
+    ) -> anyhow::Result<()> {
+        let mut ledger = LedgerBuilder::new()
+            .keyspace(self.default_keyspace.clone())
+            .partition_config(self.partition_config.clone())
+            .retries_per_query(self.retries_per_query)
+            .build();
+        for (path, header) in self.snapshots.clone() {
+            if self.service.is_stopping() {
+                break;
+            }
+            let file_size = std::fs::metadata(&path)?.len();
+            let (tx, mut entries) = tokio::sync::mpsc::channel(self.parallelism as usize * 100);
+            let reader = tokio::task::spawn_blocking(move || read_snapshot(path, tx));
+            match header.kind {
+                SnapshotKind::Full => {
+                    self.import_full_snapshot(&mut ledger, &header, file_size, &mut entries, supervisor)
+                        .await?
+                }
+                SnapshotKind::Delta => {
+                    self.import_delta_snapshot(&mut ledger, file_size, &mut entries, supervisor)
+                        .await?
+                }
+            }
+            // Dropping the receiver stops the reader if the snapshot was skipped
+            drop(entries);
+            reader.await?;
+        }
+        Ok(())
+    }
+    /// Seed an empty ledger with the unspent outputs and the treasury output of a full snapshot, and rewind its
+    /// milestone diffs to store the balances of the milestones it covers
+    async fn import_full_snapshot<H: ChronicleBrokerScope>(
+        &mut self,
+        ledger: &mut Ledger,
+        header: &SnapshotHeader,
+        file_size: u64,
+        entries: &mut SnapshotEntryReceiver,
+        supervisor: &BrokerHandle<H>,
+    ) -> anyhow::Result<()> {
+        let ledger_index = header.ledger_index;
+        match ledger.load_checkpoint().await? {
+            Some(checkpoint) if checkpoint.milestone_index >= ledger_index => {
+                warn!(
+                    "Skipped full snapshot of milestone {}, the ledger is already at milestone {}",
+                    ledger_index, checkpoint.milestone_index
+                );
+                self.snapshot_progress(supervisor, file_size as usize, ledger_index, true);
+                return Ok(());
+            }
+            Some(checkpoint) => bail!(
+                "The ledger already holds the state of milestone {}, a full snapshot can only seed an empty ledger",
+                checkpoint.milestone_index
+            ),
+            None => (),
+        }
+        let mut writes = SnapshotWrites::new(self.parallelism as usize * 100);
+        let mut balances: HashMap<Ed25519Address, BalanceDelta> = HashMap::new();
+        let mut reported = 0;
+        // The milestone index of the balances, which moves backwards as the milestone diffs are rewound
+        let mut rewound_index = ledger_index;
+        let mut treasury_index = None;
+        while let Some((entry, read)) = self.next_snapshot_entry(entries).await? {
+            match entry {
+                SnapshotEntry::Header(_) => (),
+                SnapshotEntry::Output(output_id, record) => {
+                    if let Some(address) = record.address() {
+                        balances.entry(*address).or_default().add(&record.output, 1);
+                    }
+                    writes.insert(ledger, output_id, record).await?;
+                    if read - reported > file_size / 100 {
+                        self.snapshot_progress(supervisor, (read - reported) as usize, ledger_index, false);
+                        reported = read;
+                    }
+                }
+                SnapshotEntry::MilestoneDiff(milestone_diff) => {
+                    let milestone_index = milestone_diff.diff.milestone_index();
+                    // The milestone diffs of a full snapshot go backwards from its ledger index
+                    ensure!(
+                        milestone_index == rewound_index,
+                        "Expected the milestone diff of milestone {}, found milestone {}",
+                        rewound_index,
+                        milestone_index
+                    );
+                    if let Some((milestone_id, _)) = header.treasury_output {
+                        if milestone_diff.milestone.id() == milestone_id {
+                            treasury_index = Some(milestone_index);
+                        }
+                    }
+                    self.insert_snapshot_receipt(ledger, &mut writes, &milestone_diff.milestone)
+                        .await?;
+                    for (address, record) in
+                        rewind_balances(&mut balances, milestone_index, milestone_diff.balance_deltas)?
+                    {
+                        writes.insert(ledger, address, record).await?;
+                    }
+                    rewound_index = milestone_index - 1;
+                    self.snapshot_progress(supervisor, (read - reported) as usize, milestone_index, false);
+                    reported = read;
+                }
+            }
+        }
+        if self.service.is_stopping() {
+            return Ok(());
+        }
+        // The balances of the addresses untouched by the milestone diffs are valid since the oldest milestone
+        for (address, balance) in balances {
+            writes.insert(ledger, address, balance.to_record(rewound_index)).await?;
+        }
+        // The treasury output was created before the oldest milestone diff, if none of them created it
+        if let (Some((milestone_id, amount)), None) = (header.treasury_output, treasury_index) {
+            let record = TreasuryRecord::new(MilestoneIndex(rewound_index), milestone_id, amount);
+            writes.insert(ledger, Synckey, record).await?;
+        }
+        for milestone_index in rewound_index..=ledger_index {
+            self.insert_snapshot_sync_record(ledger, &mut writes, milestone_index)
+                .await?;
+        }
+        writes.flush().await?;
+        ledger
+            .write_checkpoint(LedgerCheckpoint::new(ledger_index, None))
+            .await?;
+        info!("Seeded the ledger with the full snapshot of milestone {}", ledger_index);
+        self.snapshot_progress(
+            supervisor,
+            file_size.saturating_sub(reported) as usize,
+            ledger_index,
+            false,
+        );
+        Ok(())
+    }
+    /// Apply the milestone diffs of a delta snapshot which follow the ledger checkpoint
+    async fn import_delta_snapshot<H: ChronicleBrokerScope>(
+        &mut self,
+        ledger: &mut Ledger,
+        file_size: u64,
+        entries: &mut SnapshotEntryReceiver,
+        supervisor: &BrokerHandle<H>,
+    ) -> anyhow::Result<()> {
+        ledger
+            .load_checkpoint()
+            .await?
+            .ok_or_else(|| anyhow!("The ledger is empty, a full snapshot must be imported first"))?;
+        let mut writes = SnapshotWrites::new(self.parallelism as usize * 100);
+        let mut reported = 0;
+        while let Some((entry, read)) = self.next_snapshot_entry(entries).await? {
+            if let SnapshotEntry::MilestoneDiff(milestone_diff) = entry {
+                let milestone_index = milestone_diff.diff.milestone_index();
+                let skipped = milestone_index <= ledger.checkpoint();
+                if !skipped {
+                    ensure!(
+                        milestone_index == ledger.checkpoint() + 1,
+                        "Expected the milestone diff of milestone {}, found milestone {}",
+                        ledger.checkpoint() + 1,
+                        milestone_index
+                    );
+                    self.insert_snapshot_receipt(ledger, &mut writes, &milestone_diff.milestone)
+                        .await?;
+                    writes.flush().await?;
+                    ledger.apply(milestone_diff.diff).await?;
+                    self.insert_snapshot_sync_record(ledger, &mut writes, milestone_index)
+                        .await?;
+                }
+                self.snapshot_progress(supervisor, (read - reported) as usize, milestone_index, skipped);
+                reported = read;
+            }
+        }
+        writes.flush().await?;
+        info!("Moved the ledger forward to milestone {}", ledger.checkpoint());
+        self.snapshot_progress(
+            supervisor,
+            file_size.saturating_sub(reported) as usize,
+            ledger.checkpoint(),
+            false,
+        );
+        Ok(())
+    }
+    /// Receive the next snapshot entry, returning None once the snapshot is read or the importer is shutting down
+    async fn next_snapshot_entry(
+        &mut self,
+        entries: &mut SnapshotEntryReceiver,
+    ) -> anyhow::Result<Option<(SnapshotEntry, u64)>> {
+        loop {
+            tokio::select! {
+                entry = entries.recv() => return entry.transpose(),
+                event = self.inbox.recv() => match event {
+                    Some(ImporterEvent::Shutdown) | None => {
+                        self.service.update_status(ServiceStatus::Stopping);
+                        self.handle.take();
+                        return Ok(None);
+                    }
+                    _ => (),
+                },
+            }
+        }
+    }
+    /// Insert the receipt carried by a milestone, along with its migrated funds and the treasury output it created
+    async fn insert_snapshot_receipt(
+        &self,
+        ledger: &Ledger,
+        writes: &mut SnapshotWrites,
+        milestone: &MilestonePayload,
+    ) -> anyhow::Result<()> {
+        if let Some(Payload::Receipt(receipt)) = milestone.essence().receipt() {
+            let milestone_index = MilestoneIndex(*milestone.essence().index());
+            for funds in receipt.funds() {
+                let tail_transaction_hash = LegacyTailTransactionHash(funds.tail_transaction_hash().to_string());
+                let migrated_funds =
+                    MigratedFundsRecord::new(milestone_index, receipt.migrated_at(), funds.output().clone());
+                writes.insert(ledger, tail_transaction_hash, migrated_funds).await?;
+            }
+            if let Payload::TreasuryTransaction(treasury_transaction) = receipt.transaction() {
+                if let Output::Treasury(treasury_output) = treasury_transaction.output() {
+                    let treasury_record =
+                        TreasuryRecord::new(milestone_index, milestone.id(), treasury_output.amount());
+                    writes.insert(ledger, Synckey, treasury_record).await?;
+                }
+            }
+            let receipt_record = ReceiptRecord::new(milestone_index, (**receipt).clone());
+            writes.insert(ledger, Synckey, receipt_record).await?;
+        }
+        Ok(())
+    }
+    /// Mark a milestone covered by the snapshot as synced. It's left unlogged, as none of its messages were written
+    /// to the archive logs, so the syncer fills it once an archiver runs.
+    async fn insert_snapshot_sync_record(
+        &self,
+        ledger: &Ledger,
+        writes: &mut SnapshotWrites,
+        milestone_index: u32,
+    ) -> anyhow::Result<()> {
+        let synced_by = Some(self.chronicle_id);
+        let logged_by = None;
+        let sync_record = SyncRecord::new(MilestoneIndex(milestone_index), synced_by, logged_by);
+        writes.insert(ledger, Synckey, sync_record).await
+    }
+    /// Report the progress of the snapshot import
+    fn snapshot_progress<H: ChronicleBrokerScope>(
+        &self,
+        supervisor: &BrokerHandle<H>,
+        ms_bytes_size: usize,
+        milestone_index: u32,
+        skipped: bool,
+    ) {
+        let importer_session = ImporterSession::ProgressBar {
+            log_file_size: self.log_file_size,
+            from_ms: self.from_ms,
+            to_ms: self.to_ms,
+            ms_bytes_size,
+            milestone_index,
+            skipped,
+        };
+        supervisor.send(BrokerEvent::Importer(importer_session)).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bee_message::prelude::*;
+
+    fn header_bytes(kind: SnapshotKind, network_id: u64, ledger_index: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        SNAPSHOT_VERSION.pack(&mut bytes).unwrap();
+        (kind as u8).pack(&mut bytes).unwrap();
+        1_600_000_000u64.pack(&mut bytes).unwrap();
+        network_id.pack(&mut bytes).unwrap();
+        (ledger_index - 1).pack(&mut bytes).unwrap();
+        ledger_index.pack(&mut bytes).unwrap();
+        1u64.pack(&mut bytes).unwrap();
+        if kind == SnapshotKind::Full {
+            2u64.pack(&mut bytes).unwrap();
+        }
+        3u64.pack(&mut bytes).unwrap();
+        if kind == SnapshotKind::Full {
+            MilestoneId::new([1; 32]).pack(&mut bytes).unwrap();
+            4_000_000u64.pack(&mut bytes).unwrap();
+        }
+        bytes
+    }
+
+    fn milestone(receipt: Option<Payload>) -> MilestonePayload {
+        let essence = MilestonePayloadEssence::new(
+            MilestoneIndex(5),
+            1_600_000_000,
+            Parents::new(vec![MessageId::new([1; 32]), MessageId::new([2; 32])]).unwrap(),
+            [0; MILESTONE_MERKLE_PROOF_LENGTH],
+            0,
+            0,
+            vec![[0; MILESTONE_PUBLIC_KEY_LENGTH]],
+            receipt,
+        )
+        .unwrap();
+        MilestonePayload::new(essence, vec![Box::new([0; MILESTONE_SIGNATURE_LENGTH])]).unwrap()
+    }
+
+    fn receipt() -> Payload {
+        let funds = MigratedFundsEntry::new(
+            TailTransactionHash::new([0; 49]).unwrap(),
+            SignatureLockedSingleOutput::new(Address::Ed25519(Ed25519Address::new([4; 32])), 1_000_000).unwrap(),
+        )
+        .unwrap();
+        let treasury_transaction = TreasuryTransactionPayload::new(
+            Input::Treasury(TreasuryInput::new(MilestoneId::new([5; 32]))),
+            Output::Treasury(TreasuryOutput::new(2_000_000).unwrap()),
+        )
+        .unwrap();
+        Payload::Receipt(Box::new(
+            ReceiptPayload::new(
+                MilestoneIndex(4),
+                true,
+                vec![funds],
+                Payload::TreasuryTransaction(Box::new(treasury_transaction)),
+            )
+            .unwrap(),
+        ))
+    }
+
+    fn single(address: [u8; 32], amount: u64) -> Output {
+        Output::SignatureLockedSingle(
+            SignatureLockedSingleOutput::new(Address::Ed25519(Ed25519Address::new(address)), amount).unwrap(),
+        )
+    }
+
+    fn dust_allowance(address: [u8; 32], amount: u64) -> Output {
+        Output::SignatureLockedDustAllowance(
+            SignatureLockedDustAllowanceOutput::new(Address::Ed25519(Ed25519Address::new(address)), amount).unwrap(),
+        )
+    }
+
+    fn pack_output(bytes: &mut Vec<u8>, output_id: OutputId, output: &Output) {
+        MessageId::new([9; 32]).pack(bytes).unwrap();
+        output_id.pack(bytes).unwrap();
+        output.pack(bytes).unwrap();
+    }
+
+    fn milestone_diff_bytes(milestone: MilestonePayload, consumed_treasury: Option<(MilestoneId, u64)>) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let payload = Payload::Milestone(Box::new(milestone));
+        (payload.packed_len() as u32).pack(&mut bytes).unwrap();
+        payload.pack(&mut bytes).unwrap();
+        if let Some((milestone_id, amount)) = consumed_treasury {
+            milestone_id.pack(&mut bytes).unwrap();
+            amount.pack(&mut bytes).unwrap();
+        }
+        2u64.pack(&mut bytes).unwrap();
+        pack_output(
+            &mut bytes,
+            OutputId::new(TransactionId::new([1; 32]), 0).unwrap(),
+            &single([1; 32], 3_000_000),
+        );
+        pack_output(
+            &mut bytes,
+            OutputId::new(TransactionId::new([1; 32]), 1).unwrap(),
+            &dust_allowance([2; 32], 1_000_000),
+        );
+        1u64.pack(&mut bytes).unwrap();
+        pack_output(
+            &mut bytes,
+            OutputId::new(TransactionId::new([0; 32]), 0).unwrap(),
+            &single([1; 32], 4_000_000),
+        );
+        TransactionId::new([1; 32]).pack(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn unpack_full_snapshot_header() {
+        let bytes = header_bytes(SnapshotKind::Full, 7, 10);
+        let header = SnapshotHeader::unpack(&mut bytes.as_slice()).unwrap();
+        assert_eq!(header.kind, SnapshotKind::Full);
+        assert_eq!(header.timestamp, 1_600_000_000);
+        assert_eq!(header.network_id, 7);
+        assert_eq!(header.sep_index, 9);
+        assert_eq!(header.ledger_index, 10);
+        assert_eq!(header.sep_count, 1);
+        assert_eq!(header.output_count, 2);
+        assert_eq!(header.milestone_diff_count, 3);
+        assert_eq!(header.treasury_output, Some((MilestoneId::new([1; 32]), 4_000_000)));
+    }
+
+    #[test]
+    fn unpack_delta_snapshot_header() {
+        let bytes = header_bytes(SnapshotKind::Delta, 7, 10);
+        let header = SnapshotHeader::unpack(&mut bytes.as_slice()).unwrap();
+        assert_eq!(header.kind, SnapshotKind::Delta);
+        assert_eq!(header.ledger_index, 10);
+        assert_eq!(header.output_count, 0);
+        assert_eq!(header.milestone_diff_count, 3);
+        assert_eq!(header.treasury_output, None);
+
+        let mut bytes = header_bytes(SnapshotKind::Delta, 7, 10);
+        bytes[0] = SNAPSHOT_VERSION + 1;
+        assert!(SnapshotHeader::unpack(&mut bytes.as_slice()).is_err());
+        bytes[0] = SNAPSHOT_VERSION;
+        bytes[1] = 2;
+        assert!(SnapshotHeader::unpack(&mut bytes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn unpack_snapshot_milestone_diff() {
+        let bytes = milestone_diff_bytes(milestone(None), None);
+        let milestone_diff = SnapshotMilestoneDiff::unpack(&mut bytes.as_slice()).unwrap();
+        assert_eq!(*milestone_diff.milestone.essence().index(), 5);
+        assert_eq!(milestone_diff.consumed_treasury, None);
+        let diff = &milestone_diff.diff;
+        assert_eq!(diff.milestone_index(), 5);
+        assert_eq!(diff.created().len(), 2);
+        let created = &diff.created()[&OutputId::new(TransactionId::new([1; 32]), 0).unwrap()];
+        assert_eq!(created.message_id, MessageId::new([9; 32]));
+        assert_eq!(created.created_at, MilestoneIndex(5));
+        assert_eq!(
+            diff.consumed()[&OutputId::new(TransactionId::new([0; 32]), 0).unwrap()],
+            TransactionId::new([1; 32])
+        );
+        assert_eq!(milestone_diff.balance_deltas.len(), 2);
+        assert_eq!(
+            milestone_diff.balance_deltas[&Ed25519Address::new([1; 32])],
+            delta(-1_000_000, 0, 0, 0)
+        );
+        assert_eq!(
+            milestone_diff.balance_deltas[&Ed25519Address::new([2; 32])],
+            delta(1_000_000, 1_000_000, 1, 0)
+        );
+    }
+
+    #[test]
+    fn unpack_snapshot_milestone_diff_with_receipt() {
+        let consumed_treasury = Some((MilestoneId::new([5; 32]), 3_000_000));
+        let bytes = milestone_diff_bytes(milestone(Some(receipt())), consumed_treasury);
+        let milestone_diff = SnapshotMilestoneDiff::unpack(&mut bytes.as_slice()).unwrap();
+        assert!(milestone_diff.milestone.essence().receipt().is_some());
+        assert_eq!(milestone_diff.consumed_treasury, consumed_treasury);
+        assert_eq!(milestone_diff.diff.created().len(), 2);
+        assert_eq!(milestone_diff.diff.consumed().len(), 1);
+
+        // The consumed treasury output is required once the milestone holds a receipt
+        let bytes = milestone_diff_bytes(milestone(Some(receipt())), None);
+        assert!(SnapshotMilestoneDiff::unpack(&mut bytes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn order_and_retain_snapshot_files() {
+        let dir = std::env::temp_dir().join(format!("chronicle-snapshots-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let files = vec![
+            ("delta_30.bin", header_bytes(SnapshotKind::Delta, 7, 30)),
+            ("full_10.bin", header_bytes(SnapshotKind::Full, 7, 10)),
+            ("delta_25.bin", header_bytes(SnapshotKind::Delta, 7, 25)),
+            ("full_20.bin", header_bytes(SnapshotKind::Full, 7, 20)),
+            ("notes.txt", b"not a snapshot".to_vec()),
+        ];
+        for (name, bytes) in files.iter() {
+            std::fs::write(dir.join(name), bytes).unwrap();
+        }
+        let snapshots = snapshot_files(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        // Only the latest full snapshot is kept, and it's imported before the delta snapshots
+        let snapshots = snapshots
+            .unwrap()
+            .into_iter()
+            .map(|(path, header)| {
+                (
+                    path.file_name().unwrap().to_str().unwrap().to_owned(),
+                    header.ledger_index,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            snapshots,
+            vec![
+                ("full_20.bin".to_owned(), 20),
+                ("delta_25.bin".to_owned(), 25),
+                ("delta_30.bin".to_owned(), 30)
+            ]
+        );
+    }
+
+    fn delta(balance: i128, dust_allowance: i128, output_count: i128, dust_output_count: i128) -> BalanceDelta {
+        BalanceDelta {
+            balance,
+            dust_allowance,
+            output_count,
+            dust_output_count,
+        }
+    }
+
+    #[test]
+    fn rewind_full_snapshot_balances() {
+        let (first, second) = (Ed25519Address::new([1; 32]), Ed25519Address::new([2; 32]));
+        // The balances as of the ledger index of the snapshot
+        let mut balances = HashMap::new();
+        balances.insert(first, delta(5_000_000, 1_000_000, 3, 1));
+        // Milestone 10 sent 2 Mi from the second address to the first one, including a dust allowance
+        let mut deltas = HashMap::new();
+        deltas.insert(first, delta(2_000_000, 1_000_000, 2, 0));
+        deltas.insert(second, delta(-2_000_000, 0, -1, 0));
+        let mut records = rewind_balances(&mut balances, 10, deltas).unwrap();
+        records.sort_by(|(a, _), (b, _)| a.as_ref().cmp(b.as_ref()));
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].0, first);
+        assert_eq!(records[0].1.milestone_index, MilestoneIndex(10));
+        assert_eq!(
+            (records[0].1.balance, records[0].1.dust_allowance),
+            (5_000_000, 1_000_000)
+        );
+        assert_eq!((records[0].1.output_count, records[0].1.dust_output_count), (3, 1));
+        assert_eq!(records[1].0, second);
+        assert_eq!((records[1].1.balance, records[1].1.dust_allowance), (0, 0));
+        assert_eq!((records[1].1.output_count, records[1].1.dust_output_count), (0, 0));
+        // The balances are left as of milestone 9
+        assert_eq!(balances[&first], delta(3_000_000, 0, 1, 1));
+        assert_eq!(balances[&second], delta(2_000_000, 0, 1, 0));
+
+        // A milestone can't have created more than the balance it's rewound from
+        let mut deltas = HashMap::new();
+        deltas.insert(first, delta(3_000_001, 0, 0, 0));
+        assert!(rewind_balances(&mut balances, 9, deltas).is_err());
+    }
+
+    #[test]
+    fn network_id_of_a_network_name() {
+        assert_eq!(network_id("chrysalis-mainnet"), 1454675179895816119);
+        assert_ne!(network_id("testnet7"), network_id("chrysalis-mainnet"));
+    }
+}
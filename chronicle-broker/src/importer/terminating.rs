@@ -38,3 +38,33 @@ impl<H: ChronicleBrokerScope, T: Send + ImportMode> Terminating<BrokerHandle<H>>
         status
     }
 }
+
+#[async_trait::async_trait]
+impl<H: ChronicleBrokerScope> Terminating<BrokerHandle<H>> for Importer<Snapshot> {
+    async fn terminating(
+        &mut self,
+        status: Result<(), Need>,
+        supervisor: &mut Option<BrokerHandle<H>>,
+    ) -> Result<(), Need> {
+        info!("{} is terminating", self.get_name());
+        let msg = if status.is_ok() { "done" } else { "failed" };
+        let importer_session = if self.snapshots.is_empty() {
+            ImporterSession::PathError {
+                path: self.file_path.clone(),
+                msg: "Invalid snapshot path".into(),
+            }
+        } else {
+            ImporterSession::Finish {
+                from_ms: self.from_ms,
+                to_ms: self.to_ms,
+                msg: msg.into(),
+            }
+        };
+        let event = BrokerEvent::Importer(importer_session);
+        supervisor.as_mut().expect("Expected BrokerHandle").send(event).ok();
+        self.service.update_status(ServiceStatus::Stopping);
+        let event = BrokerEvent::Children(BrokerChild::Importer(self.service.clone(), status, self.parallelism));
+        let _ = supervisor.as_mut().expect("Expected BrokerHandle").send(event);
+        status
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[async_trait::async_trait]
+impl<H: ChronicleBrokerScope> EventLoop<BrokerHandle<H>> for Ledger {
+    async fn event_loop(
+        &mut self,
+        status: Result<(), Need>,
+        supervisor: &mut Option<BrokerHandle<H>>,
+    ) -> Result<(), Need> {
+        status?;
+        self.service.update_status(ServiceStatus::Running);
+        let event = BrokerEvent::Children(BrokerChild::Ledger(self.service.clone(), Ok(())));
+        let _ = supervisor.as_mut().expect("Ledger expected BrokerHandle").send(event);
+        // Catch up with the milestones which were synced while the ledger was not running
+        self.advance(supervisor).await;
+        while let Some(event) = self.inbox.rx.recv().await {
+            if self.diverged_at.is_some() {
+                continue;
+            }
+            self.buffer(event);
+            self.advance(supervisor).await;
+        }
+        Ok(())
+    }
+}
+
+impl Ledger {
+    /// Apply what can be applied, and report a divergence to the supervisor
+    async fn advance<H: ChronicleBrokerScope>(&mut self, supervisor: &mut Option<BrokerHandle<H>>) {
+        match self.try_advance().await {
+            Ok(()) => (),
+            Err(LedgerError::Other(e)) => {
+                // The diff is rebuilt from the database on the next attempt
+                error!(
+                    "Ledger failed to apply milestone: {}, error: {}",
+                    self.checkpoint + 1,
+                    e
+                );
+            }
+            Err(divergence) => {
+                error!(
+                    "Ledger diverged, it stopped at milestone: {}, {}",
+                    self.checkpoint, divergence
+                );
+                self.diverged_at.replace(self.checkpoint + 1);
+                self.pending.clear();
+                self.service.update_status(ServiceStatus::Degraded);
+                let event = BrokerEvent::Children(BrokerChild::Ledger(self.service.clone(), Ok(())));
+                let _ = supervisor.as_mut().expect("Ledger expected BrokerHandle").send(event);
+            }
+        }
+    }
+}
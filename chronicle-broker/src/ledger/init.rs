@@ -0,0 +1,24 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[async_trait::async_trait]
+impl<H: ChronicleBrokerScope> Init<BrokerHandle<H>> for Ledger {
+    async fn init(&mut self, status: Result<(), Need>, supervisor: &mut Option<BrokerHandle<H>>) -> Result<(), Need> {
+        self.service.update_status(ServiceStatus::Initializing);
+        // Resume from the last checkpoint, or from an empty ledger
+        match self.load_checkpoint().await {
+            Ok(Some(_)) => (),
+            Ok(None) => info!("Ledger has no checkpoint, it will start from an empty ledger"),
+            Err(e) => {
+                error!("Unable to fetch the ledger checkpoint, error: {}", e);
+                return Err(Need::Abort);
+            }
+        }
+        let event = BrokerEvent::Children(BrokerChild::Ledger(self.service.clone(), Ok(())));
+        let _ = supervisor.as_mut().expect("Ledger expected BrokerHandle").send(event);
+        info!("Ledger got initialized, last applied milestone: {}", self.checkpoint);
+        status
+    }
+}
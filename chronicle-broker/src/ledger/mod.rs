@@ -0,0 +1,862 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use bee_message::prelude::{
+    Ed25519Address,
+    MilestoneIndex,
+    Output,
+    OutputId,
+    TransactionId,
+};
+use chronicle_common::{
+    config::PartitionConfig,
+    Synckey,
+};
+use futures::FutureExt;
+use std::collections::BTreeMap;
+use thiserror::Error;
+use tokio::sync::mpsc::UnboundedSender;
+
+mod event_loop;
+mod init;
+mod terminating;
+
+/// The maximum number of diffs buffered ahead of the next milestone index to apply
+pub const MAX_PENDING_DIFFS: usize = 1000;
+
+/// The consistency of every ledger read and write. Applying a milestone reads what the previous one wrote,
+/// which is only guaranteed when both reach a quorum of the replicas.
+pub const LEDGER_CONSISTENCY: Consistency = Consistency::Quorum;
+
+/// The amount below which a signature locked single output is a dust output
+pub const DUST_THRESHOLD: u64 = 1_000_000;
+
+// Ledger builder
+builder!(LedgerBuilder {
+    keyspace: ChronicleKeyspace,
+    message_keyspaces: Vec<ChronicleKeyspace>,
+    partition_config: PartitionConfig,
+    retries_per_query: usize
+});
+
+/// LedgerHandle to be passed to the supervisor and solidifiers
+#[derive(Clone)]
+pub struct LedgerHandle {
+    pub(crate) tx: tokio::sync::mpsc::UnboundedSender<LedgerEvent>,
+}
+/// LedgerInbox is used to recv events from solidifier(s)
+pub struct LedgerInbox {
+    pub(crate) rx: tokio::sync::mpsc::UnboundedReceiver<LedgerEvent>,
+}
+impl Deref for LedgerHandle {
+    type Target = tokio::sync::mpsc::UnboundedSender<LedgerEvent>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.tx
+    }
+}
+
+impl DerefMut for LedgerHandle {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.tx
+    }
+}
+
+impl Shutdown for LedgerHandle {
+    fn shutdown(self) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
+}
+
+/// Ledger events
+pub enum LedgerEvent {
+    /// The ledger mutations of a solidified milestone
+    MilestoneDiff(MilestoneDiff),
+}
+
+/// Errors which stop the ledger from applying a milestone
+#[derive(Error, Debug)]
+pub enum LedgerError {
+    /// The milestone consumed an output which is not unspent in the ledger
+    #[error(
+        "Milestone {milestone_index} consumed output {output_id} which is not unspent, spent by transaction {spent_by}"
+    )]
+    MissingInput {
+        /// The milestone which consumed the output
+        milestone_index: u32,
+        /// The missing output
+        output_id: OutputId,
+        /// The transaction which consumed the output
+        spent_by: TransactionId,
+    },
+    /// The milestone consumed more than the balance of an address
+    #[error("Milestone {milestone_index} overdraws the balance of address {address}")]
+    NegativeBalance {
+        /// The milestone which overdraws the balance
+        milestone_index: u32,
+        /// The overdrawn address
+        address: Ed25519Address,
+    },
+    /// A query failed
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+/// The sender which ledger workers report the outcome of their writes to
+pub type LedgerWriteSender = UnboundedSender<anyhow::Result<()>>;
+
+/// Ledger state, which applies the milestone diffs in milestone order on top of the last checkpoint
+pub struct Ledger {
+    service: Service,
+    keyspace: ChronicleKeyspace,
+    /// The keyspaces the cone messages can be stored in
+    message_keyspaces: Vec<ChronicleKeyspace>,
+    partition_config: PartitionConfig,
+    retries_per_query: usize,
+    /// The last milestone index applied to the ledger
+    checkpoint: u32,
+    /// The milestone index whose consumed outputs are being removed
+    consuming: Option<u32>,
+    /// The diffs received ahead of the next milestone index to apply
+    pending: BTreeMap<u32, MilestoneDiff>,
+    /// The milestone index at which the ledger diverged, if it did
+    diverged_at: Option<u32>,
+    handle: Option<LedgerHandle>,
+    inbox: LedgerInbox,
+}
+
+impl Ledger {
+    /// Take the held ledger handle, leaving None in its place
+    pub fn take_handle(&mut self) -> Option<LedgerHandle> {
+        self.handle.take()
+    }
+    /// Get the last milestone index applied to the ledger
+    pub fn checkpoint(&self) -> u32 {
+        self.checkpoint
+    }
+    /// Load the last checkpoint from the database, returning None if the ledger is empty
+    pub(crate) async fn load_checkpoint(&mut self) -> anyhow::Result<Option<LedgerCheckpoint>> {
+        let checkpoint = self.query::<_, LedgerCheckpoint>(Synckey).await?;
+        if let Some(checkpoint) = checkpoint.as_ref() {
+            self.checkpoint = checkpoint.milestone_index;
+            self.consuming = checkpoint.consuming;
+        }
+        Ok(checkpoint)
+    }
+    /// Apply the diffs which directly follow the checkpoint, rebuilding them from the database when not pending
+    pub(crate) async fn try_advance(&mut self) -> Result<(), LedgerError> {
+        loop {
+            // A diff which is already queued must not be rebuilt from a cone which is still being written
+            self.drain_inbox();
+            let next = self.checkpoint + 1;
+            let diff = match self.pending.remove(&next) {
+                Some(diff) => diff,
+                None => match self.fetch_diff(next).await? {
+                    Some(diff) => diff,
+                    None => break,
+                },
+            };
+            self.apply(diff).await?;
+        }
+        Ok(())
+    }
+    /// Buffer a diff received from the solidifiers until the ledger gets to its milestone
+    pub(crate) fn buffer(&mut self, event: LedgerEvent) {
+        match event {
+            LedgerEvent::MilestoneDiff(milestone_diff) => {
+                let milestone_index = milestone_diff.milestone_index();
+                // Diffs beyond the buffer are rebuilt from the database once the ledger gets to them
+                if milestone_index > self.checkpoint && self.pending.len() < MAX_PENDING_DIFFS {
+                    self.pending.insert(milestone_index, milestone_diff);
+                }
+            }
+        }
+    }
+    /// Buffer the diffs which are already queued in the inbox, without waiting for new ones
+    fn drain_inbox(&mut self) {
+        while let Some(Some(event)) = self.inbox.rx.recv().now_or_never() {
+            self.buffer(event);
+        }
+    }
+    /// Rebuild the diff of a milestone from its persisted cone, once the whole cone is in the database.
+    /// The cone messages are looked up in every keyspace messages are stored in; a milestone whose messages
+    /// were dropped by the filter can only be applied from the diff of its solidifier.
+    async fn fetch_diff(&self, milestone_index: u32) -> anyhow::Result<Option<MilestoneDiff>> {
+        // The milestone is only synced once all of its messages are in the database
+        match self.query::<_, SyncRecord>(MilestoneIndex(milestone_index)).await? {
+            Some(SyncRecord { synced_by: Some(_), .. }) => (),
+            _ => return Ok(None),
+        }
+        // The cone rows are written alongside the sync record, so the cone is only complete once it holds as many
+        // messages as the analytic record of the milestone counts
+        let message_count = match self.query::<_, AnalyticRecord>(MilestoneIndex(milestone_index)).await? {
+            Some(record) => record.message_count.0 as usize,
+            None => return Ok(None),
+        };
+        let partition_id = self.partition_config.partition_id(milestone_index);
+        let key = Partitioned::new(MilestoneIndex(milestone_index), partition_id, milestone_index);
+        let mut message_ids = Vec::new();
+        let mut paging_state = None;
+        loop {
+            let mut paged = self
+                .query_paged::<_, Paged<Vec<MessageId>>>(key.clone(), 1000, paging_state)
+                .await?
+                .ok_or_else(|| anyhow!("No milestone messages page for milestone {}", milestone_index))?;
+            message_ids.append(&mut *paged);
+            paging_state = paged.paging_state.take();
+            if paging_state.is_none() {
+                break;
+            }
+        }
+        if message_ids.len() != message_count {
+            return Ok(None);
+        }
+        let mut messages = Vec::with_capacity(message_ids.len());
+        for message_id in message_ids {
+            match self.query_message(message_id).await? {
+                Some(message) => messages.push(message),
+                None => bail!(
+                    "Missing message {} of milestone {} in keyspaces: {:?}",
+                    message_id,
+                    milestone_index,
+                    self.message_keyspaces.iter().map(|k| k.name()).collect::<Vec<_>>()
+                ),
+            }
+        }
+        MilestoneDiff::new(
+            milestone_index,
+            messages.iter().map(|(message, metadata)| (message, metadata)),
+        )
+        .map(Some)
+    }
+    /// Look up a message and its metadata in the keyspaces messages are stored in
+    async fn query_message(&self, message_id: MessageId) -> anyhow::Result<Option<(Message, MessageMetadata)>> {
+        for keyspace in self.message_keyspaces.iter() {
+            if let Some((Some(message), Some(metadata))) = self
+                .query_in::<_, (Option<Message>, Option<MessageMetadata>)>(keyspace, message_id)
+                .await?
+            {
+                return Ok(Some((message, metadata)));
+            }
+        }
+        Ok(None)
+    }
+    /// Apply a milestone diff on top of the previous milestone, then checkpoint it.
+    /// Applying a diff which was partially applied before a restart results in the same state.
+    pub(crate) async fn apply(&mut self, diff: MilestoneDiff) -> Result<(), LedgerError> {
+        let milestone_index = diff.milestone_index();
+        // The created outputs and balances are already written if the restart happened while consuming
+        if self.consuming != Some(milestone_index) {
+            // Resolve the consumed outputs before any write, so a divergence leaves the ledger untouched
+            let mut consumed = Vec::with_capacity(diff.consumed().len());
+            for (output_id, spent_by) in diff.consumed() {
+                if let Some(record) = diff.created().get(output_id) {
+                    consumed.push(record.clone());
+                } else if let Some(record) = self.query::<_, LedgerOutputRecord>(*output_id).await? {
+                    consumed.push(record);
+                } else {
+                    return Err(LedgerError::MissingInput {
+                        milestone_index,
+                        output_id: *output_id,
+                        spent_by: *spent_by,
+                    });
+                }
+            }
+            self.write_created_and_balances(&diff, consumed).await?;
+            self.write_checkpoint(LedgerCheckpoint::new(self.checkpoint, Some(milestone_index)))
+                .await?;
+            self.consuming = Some(milestone_index);
+        }
+        // Remove the consumed outputs from the unspent outputs
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut writes = 0;
+        for output_id in diff.consumed().keys() {
+            if diff.created().contains_key(output_id) {
+                continue;
+            }
+            self.delete::<_, LedgerOutputRecord>(*output_id, &tx)?;
+            writes += 1;
+        }
+        Self::await_writes(rx, writes).await?;
+        self.write_checkpoint(LedgerCheckpoint::new(milestone_index, None))
+            .await?;
+        self.checkpoint = milestone_index;
+        self.consuming = None;
+        debug!("Ledger applied milestone: {}", milestone_index);
+        Ok(())
+    }
+    /// Write the ledger checkpoint
+    pub(crate) async fn write_checkpoint(&self, checkpoint: LedgerCheckpoint) -> anyhow::Result<()> {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        self.insert(Synckey, checkpoint, &tx)?;
+        Self::await_writes(rx, 1).await
+    }
+    /// Write the created outputs and the balances of the touched addresses
+    async fn write_created_and_balances(
+        &self,
+        diff: &MilestoneDiff,
+        consumed: Vec<LedgerOutputRecord>,
+    ) -> Result<(), LedgerError> {
+        let milestone_index = diff.milestone_index();
+        let deltas = balance_deltas(diff, &consumed);
+        // The balances are computed from the previous milestone, so rewriting them is idempotent
+        let mut balances = Vec::with_capacity(deltas.len());
+        for (address, delta) in deltas {
+            let previous = self
+                .query::<_, BalanceRecord>((address, MilestoneIndex(milestone_index - 1)))
+                .await?;
+            let record = apply_balance_delta(milestone_index, address, previous.as_ref(), delta)?;
+            balances.push((address, record));
+        }
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut writes = 0;
+        for (output_id, record) in diff.created() {
+            // Outputs created and consumed by the same milestone never become unspent
+            if diff.consumed().contains_key(output_id) {
+                continue;
+            }
+            self.insert(*output_id, record.clone(), &tx)?;
+            writes += 1;
+        }
+        for (address, record) in balances {
+            self.insert(address, record, &tx)?;
+            writes += 1;
+        }
+        Self::await_writes(rx, writes).await?;
+        Ok(())
+    }
+    /// Await the outcome of the given number of writes
+    pub(crate) async fn await_writes(
+        mut rx: tokio::sync::mpsc::UnboundedReceiver<anyhow::Result<()>>,
+        mut writes: usize,
+    ) -> anyhow::Result<()> {
+        while writes > 0 {
+            rx.recv()
+                .await
+                .ok_or_else(|| anyhow!("Ledger write workers dropped without a response"))??;
+            writes -= 1;
+        }
+        Ok(())
+    }
+    /// Select a value from the `Chronicle` keyspace
+    pub(crate) async fn query<K, V>(&self, key: K) -> anyhow::Result<Option<V>>
+    where
+        ChronicleKeyspace: Select<K, V>,
+        K: 'static + Send + Clone,
+        V: 'static + Send + Clone,
+    {
+        self.query_in(&self.keyspace, key).await
+    }
+    /// Select a value from the given keyspace
+    async fn query_in<K, V>(&self, keyspace: &ChronicleKeyspace, key: K) -> anyhow::Result<Option<V>>
+    where
+        ChronicleKeyspace: Select<K, V>,
+        K: 'static + Send + Clone,
+        V: 'static + Send + Clone,
+    {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let request = keyspace.select::<V>(&key).consistency(LEDGER_CONSISTENCY).build()?;
+        let worker = ValueWorker::new(
+            tx,
+            keyspace.clone(),
+            key,
+            self.retries_per_query,
+            std::marker::PhantomData,
+        );
+        request.send_local(Box::new(worker));
+        Ok(rx
+            .recv()
+            .await
+            .ok_or_else(|| anyhow!("Ledger select worker dropped without a response"))??)
+    }
+    /// Select a page from the `Chronicle` keyspace
+    async fn query_paged<K, V>(
+        &self,
+        key: K,
+        page_size: i32,
+        paging_state: Option<Vec<u8>>,
+    ) -> anyhow::Result<Option<V>>
+    where
+        ChronicleKeyspace: Select<K, V>,
+        K: 'static + Send + Clone,
+        V: 'static + Send + Clone,
+    {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let request = self
+            .keyspace
+            .select::<V>(&key)
+            .consistency(LEDGER_CONSISTENCY)
+            .page_size(page_size)
+            .paging_state(&paging_state)
+            .build()?;
+        let worker = ValueWorker::new(
+            tx,
+            self.keyspace.clone(),
+            key,
+            self.retries_per_query,
+            std::marker::PhantomData,
+        )
+        .with_paging(page_size, paging_state);
+        request.send_local(Box::new(worker));
+        Ok(rx
+            .recv()
+            .await
+            .ok_or_else(|| anyhow!("Ledger select worker dropped without a response"))??)
+    }
+    /// Insert the key value pair in the `Chronicle` keyspace, reporting the outcome to the given sender
+    pub(crate) fn insert<K, V>(&self, key: K, value: V, tx: &LedgerWriteSender) -> anyhow::Result<()>
+    where
+        ChronicleKeyspace: Insert<K, V>,
+        K: 'static + Send + Clone,
+        V: 'static + Send + Clone,
+    {
+        let request = self
+            .keyspace
+            .insert(&key, &value)
+            .consistency(LEDGER_CONSISTENCY)
+            .build()?;
+        let worker = LedgerInsertWorker::boxed(tx.clone(), self.keyspace.clone(), key, value, self.retries_per_query);
+        request.send_local(worker);
+        Ok(())
+    }
+    /// Delete the key in the `Chronicle` keyspace, reporting the outcome to the given sender
+    fn delete<K, V>(&self, key: K, tx: &LedgerWriteSender) -> anyhow::Result<()>
+    where
+        ChronicleKeyspace: Delete<K, V>,
+        K: 'static + Send + Clone,
+        V: 'static + Send + Clone,
+    {
+        let request = self
+            .keyspace
+            .delete::<V>(&key)
+            .consistency(LEDGER_CONSISTENCY)
+            .build()?;
+        let worker =
+            LedgerDeleteWorker::<_, _, V>::boxed(tx.clone(), self.keyspace.clone(), key, self.retries_per_query);
+        request.send_local(worker);
+        Ok(())
+    }
+}
+
+/// The changes of the balance, dust allowance and output counts of an address
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct BalanceDelta {
+    pub balance: i128,
+    pub dust_allowance: i128,
+    pub output_count: i128,
+    pub dust_output_count: i128,
+}
+
+impl BalanceDelta {
+    /// Add an output to the delta with the given sign, outputs which aren't owned by an address are ignored
+    pub(crate) fn add(&mut self, output: &Output, sign: i128) {
+        let (amount, is_dust_allowance, is_dust) = match output {
+            Output::SignatureLockedSingle(output) => (output.amount(), false, output.amount() < DUST_THRESHOLD),
+            Output::SignatureLockedDustAllowance(output) => (output.amount(), true, false),
+            _ => return,
+        };
+        self.balance += sign * amount as i128;
+        self.output_count += sign;
+        if is_dust_allowance {
+            self.dust_allowance += sign * amount as i128;
+        }
+        if is_dust {
+            self.dust_output_count += sign;
+        }
+    }
+    /// Add another delta to this one with the given sign
+    pub(crate) fn add_delta(&mut self, other: &BalanceDelta, sign: i128) {
+        self.balance += sign * other.balance;
+        self.dust_allowance += sign * other.dust_allowance;
+        self.output_count += sign * other.output_count;
+        self.dust_output_count += sign * other.dust_output_count;
+    }
+    /// Check that none of the sums went negative
+    pub(crate) fn is_valid(&self) -> bool {
+        self.balance >= 0 && self.dust_allowance >= 0 && self.output_count >= 0 && self.dust_output_count >= 0
+    }
+    /// Turn a valid delta, which holds the sums of the unspent outputs of an address, into its balance record
+    pub(crate) fn to_record(&self, milestone_index: u32) -> BalanceRecord {
+        BalanceRecord::new(
+            MilestoneIndex(milestone_index),
+            self.balance as u64,
+            self.dust_allowance as u64,
+            self.output_count as u64,
+            self.dust_output_count as u64,
+        )
+    }
+}
+
+impl From<&BalanceRecord> for BalanceDelta {
+    fn from(record: &BalanceRecord) -> Self {
+        Self {
+            balance: record.balance as i128,
+            dust_allowance: record.dust_allowance as i128,
+            output_count: record.output_count as i128,
+            dust_output_count: record.dust_output_count as i128,
+        }
+    }
+}
+
+/// Sum up the balance, dust allowance and output count changes of the addresses touched by a milestone
+pub(crate) fn balance_deltas(
+    diff: &MilestoneDiff,
+    consumed: &[LedgerOutputRecord],
+) -> HashMap<Ed25519Address, BalanceDelta> {
+    let mut deltas: HashMap<Ed25519Address, BalanceDelta> = HashMap::new();
+    for record in diff.created().values() {
+        if let Some(address) = record.address() {
+            deltas.entry(*address).or_default().add(&record.output, 1);
+        }
+    }
+    for record in consumed.iter() {
+        if let Some(address) = record.address() {
+            deltas.entry(*address).or_default().add(&record.output, -1);
+        }
+    }
+    deltas
+}
+
+/// Apply the balance changes of a milestone on top of the balance of the address as of the previous milestone
+pub(crate) fn apply_balance_delta(
+    milestone_index: u32,
+    address: Ed25519Address,
+    previous: Option<&BalanceRecord>,
+    delta: BalanceDelta,
+) -> Result<BalanceRecord, LedgerError> {
+    let mut balance = previous.map(BalanceDelta::from).unwrap_or_default();
+    balance.add_delta(&delta, 1);
+    if !balance.is_valid() {
+        return Err(LedgerError::NegativeBalance {
+            milestone_index,
+            address,
+        });
+    }
+    Ok(balance.to_record(milestone_index))
+}
+
+impl<H: ChronicleBrokerScope> ActorBuilder<BrokerHandle<H>> for LedgerBuilder {}
+
+/// implementation of builder
+impl Builder for LedgerBuilder {
+    type State = Ledger;
+    fn build(self) -> Self::State {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let handle = Some(LedgerHandle { tx });
+        let inbox = LedgerInbox { rx };
+        let keyspace = self.keyspace.unwrap();
+        Self::State {
+            service: Service::new(),
+            message_keyspaces: self.message_keyspaces.unwrap_or_else(|| vec![keyspace.clone()]),
+            keyspace,
+            partition_config: self.partition_config.unwrap_or_default(),
+            retries_per_query: self.retries_per_query.unwrap_or(10),
+            checkpoint: 0,
+            consuming: None,
+            pending: BTreeMap::new(),
+            diverged_at: None,
+            handle,
+            inbox,
+        }
+        .set_name()
+    }
+}
+
+/// impl name of the Ledger
+impl Name for Ledger {
+    fn set_name(mut self) -> Self {
+        self.service.update_name("Ledger".to_string());
+        self
+    }
+    fn get_name(&self) -> String {
+        self.service.get_name()
+    }
+}
+
+#[async_trait::async_trait]
+impl<H: ChronicleBrokerScope> AknShutdown<Ledger> for BrokerHandle<H> {
+    async fn aknowledge_shutdown(self, mut _state: Ledger, _status: Result<(), Need>) {
+        _state.service.update_status(ServiceStatus::Stopped);
+        let event = BrokerEvent::Children(BrokerChild::Ledger(_state.service.clone(), _status));
+        let _ = self.send(event);
+    }
+}
+
+/// Scylla worker which reports the outcome of a ledger insert
+#[derive(Clone)]
+pub struct LedgerInsertWorker<S, K, V>
+where
+    S: 'static + Insert<K, V>,
+    K: 'static + Send,
+    V: 'static + Send,
+{
+    tx: LedgerWriteSender,
+    keyspace: S,
+    key: K,
+    value: V,
+    retries: usize,
+}
+
+impl<S, K, V> LedgerInsertWorker<S, K, V>
+where
+    S: 'static + Insert<K, V>,
+    K: 'static + Send,
+    V: 'static + Send,
+{
+    /// Create a new ledger insert worker with a sender and retries
+    pub fn new(tx: LedgerWriteSender, keyspace: S, key: K, value: V, retries: usize) -> Self {
+        Self {
+            tx,
+            keyspace,
+            key,
+            value,
+            retries,
+        }
+    }
+    /// Create a new boxed ledger insert worker with a sender and retries
+    pub fn boxed(tx: LedgerWriteSender, keyspace: S, key: K, value: V, retries: usize) -> Box<Self> {
+        Box::new(Self::new(tx, keyspace, key, value, retries))
+    }
+}
+
+impl<S, K, V> Worker for LedgerInsertWorker<S, K, V>
+where
+    S: 'static + Insert<K, V>,
+    K: 'static + Send + Clone,
+    V: 'static + Send + Clone,
+{
+    fn handle_response(self: Box<Self>, giveload: Vec<u8>) -> anyhow::Result<()> {
+        let res = Decoder::try_from(giveload).and_then(|decoder| decoder.get_void());
+        let _ = self.tx.send(res);
+        Ok(())
+    }
+    fn handle_error(
+        mut self: Box<Self>,
+        mut error: WorkerError,
+        reporter: &Option<ReporterHandle>,
+    ) -> anyhow::Result<()> {
+        if let WorkerError::Cql(ref mut cql_error) = error {
+            if let (Some(id), Some(reporter)) = (cql_error.take_unprepared_id(), reporter) {
+                handle_insert_unprepared_error(&self, &self.keyspace, &self.key, &self.value, id, reporter)?;
+                return Ok(());
+            }
+        }
+        if self.retries > 0 {
+            self.retries -= 1;
+            // currently we assume all cql/worker errors are retryable, but we might change this in future
+            match self
+                .keyspace
+                .insert_query(&self.key, &self.value)
+                .consistency(LEDGER_CONSISTENCY)
+                .build()
+            {
+                Ok(req) => {
+                    tokio::spawn(async { req.send_global(self) });
+                }
+                Err(e) => {
+                    let _ = self.tx.send(Err(e));
+                }
+            }
+        } else {
+            // no more retries
+            let _ = self.tx.send(Err(anyhow!("Ledger insert failed: {:?}", error)));
+        }
+        Ok(())
+    }
+}
+
+/// Scylla worker which reports the outcome of a ledger delete
+#[derive(Clone)]
+pub struct LedgerDeleteWorker<S, K, V>
+where
+    S: 'static + Delete<K, V>,
+    K: 'static + Send,
+    V: 'static,
+{
+    tx: LedgerWriteSender,
+    keyspace: S,
+    key: K,
+    retries: usize,
+    _marker: std::marker::PhantomData<fn(V) -> V>,
+}
+
+impl<S, K, V> LedgerDeleteWorker<S, K, V>
+where
+    S: 'static + Delete<K, V>,
+    K: 'static + Send,
+    V: 'static,
+{
+    /// Create a new ledger delete worker with a sender and retries
+    pub fn new(tx: LedgerWriteSender, keyspace: S, key: K, retries: usize) -> Self {
+        Self {
+            tx,
+            keyspace,
+            key,
+            retries,
+            _marker: std::marker::PhantomData,
+        }
+    }
+    /// Create a new boxed ledger delete worker with a sender and retries
+    pub fn boxed(tx: LedgerWriteSender, keyspace: S, key: K, retries: usize) -> Box<Self> {
+        Box::new(Self::new(tx, keyspace, key, retries))
+    }
+}
+
+impl<S, K, V> Worker for LedgerDeleteWorker<S, K, V>
+where
+    S: 'static + Delete<K, V>,
+    K: 'static + Send + Clone,
+    V: 'static + Send + Clone,
+{
+    fn handle_response(self: Box<Self>, giveload: Vec<u8>) -> anyhow::Result<()> {
+        let res = Decoder::try_from(giveload).and_then(|decoder| decoder.get_void());
+        let _ = self.tx.send(res);
+        Ok(())
+    }
+    fn handle_error(
+        mut self: Box<Self>,
+        mut error: WorkerError,
+        reporter: &Option<ReporterHandle>,
+    ) -> anyhow::Result<()> {
+        if let WorkerError::Cql(ref mut cql_error) = error {
+            if let (Some(id), Some(reporter)) = (cql_error.take_unprepared_id(), reporter) {
+                handle_delete_unprepared_error(&self, &self.keyspace, &self.key, id, reporter)?;
+                return Ok(());
+            }
+        }
+        if self.retries > 0 {
+            self.retries -= 1;
+            // currently we assume all cql/worker errors are retryable, but we might change this in future
+            match self
+                .keyspace
+                .delete_query::<V>(&self.key)
+                .consistency(LEDGER_CONSISTENCY)
+                .build()
+            {
+                Ok(req) => {
+                    tokio::spawn(async { req.send_global(self) });
+                }
+                Err(e) => {
+                    let _ = self.tx.send(Err(e));
+                }
+            }
+        } else {
+            // no more retries
+            let _ = self.tx.send(Err(anyhow!("Ledger delete failed: {:?}", error)));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bee_message::prelude::{
+        Address,
+        Output,
+        SignatureLockedDustAllowanceOutput,
+        SignatureLockedSingleOutput,
+    };
+
+    fn output_id(index: u16) -> OutputId {
+        OutputId::new(TransactionId::new([index as u8; 32]), 0).unwrap()
+    }
+
+    fn single(address: [u8; 32], amount: u64) -> LedgerOutputRecord {
+        let output = SignatureLockedSingleOutput::new(Address::Ed25519(Ed25519Address::new(address)), amount).unwrap();
+        LedgerOutputRecord::new(
+            MessageId::new([0; 32]),
+            Output::SignatureLockedSingle(output),
+            MilestoneIndex(1),
+        )
+    }
+
+    fn dust_allowance(address: [u8; 32], amount: u64) -> LedgerOutputRecord {
+        let output =
+            SignatureLockedDustAllowanceOutput::new(Address::Ed25519(Ed25519Address::new(address)), amount).unwrap();
+        LedgerOutputRecord::new(
+            MessageId::new([0; 32]),
+            Output::SignatureLockedDustAllowance(output),
+            MilestoneIndex(1),
+        )
+    }
+
+    fn delta(balance: i128, dust_allowance: i128, output_count: i128, dust_output_count: i128) -> BalanceDelta {
+        BalanceDelta {
+            balance,
+            dust_allowance,
+            output_count,
+            dust_output_count,
+        }
+    }
+
+    #[test]
+    fn balance_deltas_of_a_milestone() {
+        let mut created = HashMap::new();
+        created.insert(output_id(1), single([1; 32], 1_000_000));
+        created.insert(output_id(2), dust_allowance([1; 32], 1_000_000));
+        created.insert(output_id(3), single([2; 32], 2_000_000));
+        // Created and consumed by the same milestone
+        created.insert(output_id(4), single([3; 32], 5_000_000));
+        let mut consumed = HashMap::new();
+        consumed.insert(output_id(4), TransactionId::new([3; 32]));
+        consumed.insert(output_id(5), TransactionId::new([3; 32]));
+        let diff = MilestoneDiff::from_parts(2, created.clone(), consumed);
+        let consumed = vec![created[&output_id(4)].clone(), dust_allowance([2; 32], 2_000_000)];
+
+        let deltas = balance_deltas(&diff, &consumed);
+        assert_eq!(deltas.len(), 3);
+        assert_eq!(deltas[&Ed25519Address::new([1; 32])], delta(2_000_000, 1_000_000, 2, 0));
+        assert_eq!(deltas[&Ed25519Address::new([2; 32])], delta(0, -2_000_000, 0, 0));
+        assert_eq!(deltas[&Ed25519Address::new([3; 32])], delta(0, 0, 0, 0));
+    }
+
+    #[test]
+    fn balance_deltas_count_dust_outputs() {
+        let mut created = HashMap::new();
+        created.insert(output_id(1), single([1; 32], 999_999));
+        created.insert(output_id(2), single([1; 32], 1_000_000));
+        let diff = MilestoneDiff::from_parts(2, created, HashMap::new());
+        let consumed = vec![single([1; 32], 1)];
+
+        let deltas = balance_deltas(&diff, &consumed);
+        assert_eq!(deltas[&Ed25519Address::new([1; 32])], delta(1_999_998, 0, 1, 0));
+    }
+
+    #[test]
+    fn apply_balance_deltas() {
+        let address = Ed25519Address::new([1; 32]);
+        let record = apply_balance_delta(2, address, None, delta(2_500_000, 1_000_000, 3, 1)).unwrap();
+        assert_eq!(record.milestone_index, MilestoneIndex(2));
+        assert_eq!((record.balance, record.dust_allowance), (2_500_000, 1_000_000));
+        assert_eq!((record.output_count, record.dust_output_count), (3, 1));
+
+        let record = apply_balance_delta(3, address, Some(&record), delta(-1_500_000, -1_000_000, -2, -1)).unwrap();
+        assert_eq!(record.milestone_index, MilestoneIndex(3));
+        assert_eq!((record.balance, record.dust_allowance), (1_000_000, 0));
+        assert_eq!((record.output_count, record.dust_output_count), (1, 0));
+
+        // Neither the balance, the dust allowance nor the output counts can be overdrawn
+        for overdraft in [
+            delta(-1_000_001, 0, 0, 0),
+            delta(0, -1, 0, 0),
+            delta(0, 0, -2, 0),
+            delta(0, 0, 0, -1),
+        ]
+        .iter()
+        {
+            assert!(matches!(
+                apply_balance_delta(4, address, Some(&record), *overdraft),
+                Err(LedgerError::NegativeBalance { milestone_index: 4, .. })
+            ));
+        }
+        assert!(matches!(
+            apply_balance_delta(4, address, None, delta(-1, 0, 0, 0)),
+            Err(LedgerError::NegativeBalance { milestone_index: 4, .. })
+        ));
+    }
+}
@@ -0,0 +1,18 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[async_trait::async_trait]
+impl<H: ChronicleBrokerScope> Terminating<BrokerHandle<H>> for Ledger {
+    async fn terminating(
+        &mut self,
+        status: Result<(), Need>,
+        supervisor: &mut Option<BrokerHandle<H>>,
+    ) -> Result<(), Need> {
+        self.service.update_status(ServiceStatus::Stopping);
+        let event = BrokerEvent::Children(BrokerChild::Ledger(self.service.clone(), status));
+        let _ = supervisor.as_mut().unwrap().send(event);
+        status
+    }
+}
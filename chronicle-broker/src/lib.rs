@@ -15,6 +15,9 @@ pub mod collector;
 /// The importer, which enables to import write-ahead-logs
 #[cfg(feature = "application")]
 pub mod importer;
+/// The ledger, which maintains the unspent outputs and balances of the confirmed milestones
+#[cfg(feature = "application")]
+pub mod ledger;
 /// The listener, which receives incoming connections
 #[cfg(feature = "application")]
 pub mod listener;
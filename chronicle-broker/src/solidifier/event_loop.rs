@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::*;
-use bee_message::prelude::MilestoneIndex;
+use bee_message::{
+    payload::Payload,
+    prelude::MilestoneIndex,
+};
 use chronicle_common::Synckey;
 
 #[async_trait::async_trait]
@@ -175,6 +178,15 @@ impl Solidifier {
             let _ = self.syncer_handle.send(SyncerEvent::Unreachable(milestone_index));
             return ();
         }
+        // a quarantined milestone fails verification again, so it's skipped right away
+        if self.quarantined.get(&milestone_index).is_some() {
+            error!(
+                "Solidifier id: {}, is skipping the quarantined milestone_index: {}",
+                self.partition_id, milestone_index
+            );
+            let _ = self.syncer_handle.send(SyncerEvent::Unreachable(milestone_index));
+            return ();
+        }
         // remove it from unreachable (if we already tried to solidify it before)
         self.unreachable.pop(&milestone_index);
         info!(
@@ -248,14 +260,25 @@ impl Solidifier {
             .milestones_data
             .remove(&milestone_index)
             .expect("Expected milestone data for milestone_index");
+        if let Err(error) = self.verify(&milestone_data) {
+            return self.quarantine(milestone_data, error);
+        }
         let analytic_record = milestone_data.get_analytic_record()?;
         self.insert_analytic(milestone_index, analytic_record)?;
+        self.insert_extended_analytic(milestone_data.get_extended_analytic_record()?)?;
+        self.push_to_ledger(&milestone_data)?;
         // Update in_database
         let in_database = self
             .in_database
             .entry(milestone_index)
             .or_insert_with(|| InDatabase::from(&milestone_data));
         in_database.set_messages_len(milestone_data.messages().len());
+        // Only build the milestone event if anyone is subscribed
+        if let Some(milestone_events) = self.milestone_events.as_ref() {
+            if milestone_events.receiver_count() > 0 {
+                in_database.set_milestone_event(self.milestone_event(&milestone_data));
+            }
+        }
         if in_database.check_if_all_in_database() {
             // Insert record into sync table
             self.handle_in_database(milestone_index)?;
@@ -280,14 +303,25 @@ impl Solidifier {
             .milestones_data
             .remove(&milestone_index)
             .expect("Expected milestone data for milestone_index");
+        if let Err(error) = self.verify(&milestone_data) {
+            return self.quarantine(milestone_data, error);
+        }
         let analytic_record = milestone_data.get_analytic_record()?;
         self.insert_analytic(milestone_index, analytic_record)?;
+        self.insert_extended_analytic(milestone_data.get_extended_analytic_record()?)?;
+        self.push_to_ledger(&milestone_data)?;
         // Update in_database
         let in_database = self
             .in_database
             .entry(milestone_index)
             .or_insert_with(|| InDatabase::from(&milestone_data));
         in_database.set_messages_len(milestone_data.messages().len());
+        // Only build the milestone event if anyone is subscribed
+        if let Some(milestone_events) = self.milestone_events.as_ref() {
+            if milestone_events.receiver_count() > 0 {
+                in_database.set_milestone_event(self.milestone_event(&milestone_data));
+            }
+        }
         if in_database.check_if_all_in_database() {
             // Insert record into sync table
             self.handle_in_database(milestone_index)?;
@@ -296,8 +330,141 @@ impl Solidifier {
         let _ = self.syncer_handle.send(syncer_event);
         Ok(())
     }
-    fn handle_in_database(&mut self, milestone_index: u32) -> anyhow::Result<()> {
+    fn verify(&self, milestone_data: &MilestoneData) -> Result<(), MilestoneVerificationError> {
+        if let Some(coordinator) = self.coordinator.as_ref() {
+            verify_milestone(milestone_data, coordinator)?;
+        }
+        Ok(())
+    }
+    fn quarantine(&mut self, milestone_data: MilestoneData, error: MilestoneVerificationError) -> anyhow::Result<()> {
+        let milestone_index = milestone_data.milestone_index();
+        error!(
+            "Solidifier id: {}, is quarantining milestone_index: {}, {}",
+            self.partition_id, milestone_index, error
+        );
+        // neither archive nor sync the milestone, so it doesn't get treated as solidified
+        self.lru_in_database.pop(&milestone_index);
         self.in_database.remove(&milestone_index);
+        self.unreachable.put(milestone_index, ());
+        // remember it, so the syncer gets told to skip it once it asks for it again
+        self.quarantined.put(milestone_index, ());
+        if milestone_data.created_by.eq(&CreatedBy::Syncer) {
+            // tell syncer to skip it
+            let _ = self.syncer_handle.send(SyncerEvent::Unreachable(milestone_index));
+        }
+        self.delete_milestone_rows(&milestone_data)?;
+        if let Some(milestone) = milestone_data.milestone {
+            let quarantined_record =
+                QuarantinedMilestoneRecord::new(MilestoneIndex(milestone_index), milestone, error.to_string());
+            let request = self
+                .keyspace
+                .insert(&Synckey, &quarantined_record)
+                .consistency(Consistency::One)
+                .build()?;
+            let worker = InsertWorker::boxed(
+                self.keyspace.clone(),
+                Synckey,
+                quarantined_record,
+                self.retries as usize,
+            );
+            request.send_local(worker);
+        }
+        Ok(())
+    }
+    /// Delete the rows the collector derived from the milestone message(s) of a quarantined milestone, which are the
+    /// milestone, its timestamp and its receipt. They get deleted again whenever the milestone is quarantined again.
+    fn delete_milestone_rows(&self, milestone_data: &MilestoneData) -> anyhow::Result<()> {
+        let milestone_index = milestone_data.milestone_index();
+        for full_message in milestone_data.messages().values() {
+            let milestone = match full_message.message().payload() {
+                Some(Payload::Milestone(milestone)) if milestone.essence().index().0 == milestone_index => milestone,
+                _ => continue,
+            };
+            #[cfg(feature = "filter")]
+            let keyspace = match self.get_keyspace_for_message(
+                full_message.message(),
+                full_message.metadata().referenced_by_milestone_index,
+            ) {
+                Some(keyspace) => keyspace,
+                // the collector dropped it, so there is nothing to delete
+                None => continue,
+            };
+            #[cfg(not(feature = "filter"))]
+            let keyspace = self.keyspace.clone();
+            let ms_index = MilestoneIndex(milestone_index);
+            self.delete::<_, (MessageId, Box<MilestonePayload>)>(
+                &keyspace,
+                MilestonePK::new(ms_index, *full_message.message_id()),
+            )?;
+            self.delete::<_, (MilestoneIndex, MessageId)>(
+                &keyspace,
+                MilestoneTimestampPK::new(MilestoneTimestamp(milestone.essence().timestamp()), ms_index),
+            )?;
+            if let Some(rows) = ReceiptRows::new(milestone) {
+                for (tail_transaction_hash, _) in rows.migrated_funds {
+                    self.delete::<_, MigratedFundsRecord>(
+                        &keyspace,
+                        MigratedFundsPK::new(tail_transaction_hash, ms_index),
+                    )?;
+                }
+                if rows.treasury.is_some() {
+                    self.delete::<_, TreasuryRecord>(&keyspace, ms_index)?;
+                }
+                self.delete::<_, ReceiptRecord>(
+                    &keyspace,
+                    ReceiptPK::new(rows.receipt.receipt.migrated_at(), ms_index),
+                )?;
+            }
+        }
+        Ok(())
+    }
+    /// Get the `Chronicle` keyspace of a message, or None if the message is dropped
+    #[cfg(feature = "filter")]
+    fn get_keyspace_for_message(&self, message: &Message, milestone_index: Option<u32>) -> Option<ChronicleKeyspace> {
+        let res = chronicle_filter::filter_with(&self.filter_config, self.keyspace.name(), message, milestone_index)?;
+        Some(ChronicleKeyspace::new(res.keyspace.into_owned()))
+    }
+    /// Get the milestone event of the milestone data, with its messages tagged by the keyspace they were stored in
+    fn milestone_event(&self, milestone_data: &MilestoneData) -> MilestoneEvent {
+        #[cfg(feature = "filter")]
+        let keyspace_of = |full_message: &FullMessage| {
+            self.get_keyspace_for_message(full_message.message(), full_message.ref_ms())
+                .map(|keyspace| keyspace.name().to_string())
+        };
+        #[cfg(not(feature = "filter"))]
+        let keyspace_of = |_: &FullMessage| Some(self.keyspace.name().to_string());
+        milestone_data.milestone_event(keyspace_of)
+    }
+    /// Delete the key in the provided `Chronicle` keyspace
+    fn delete<K, V>(&self, keyspace: &ChronicleKeyspace, key: K) -> anyhow::Result<()>
+    where
+        ChronicleKeyspace: Delete<K, V>,
+        K: 'static + Send + Clone,
+        V: 'static + Send + Clone,
+    {
+        let request = keyspace.delete(&key).consistency(Consistency::One).build()?;
+        let worker = DeleteWorker::boxed(keyspace.clone(), key, self.retries as usize);
+        request.send_local(worker);
+        Ok(())
+    }
+    fn push_to_ledger(&self, milestone_data: &MilestoneData) -> anyhow::Result<()> {
+        if let Some(ledger_handle) = self.ledger_handle.as_ref() {
+            let ledger_event = LedgerEvent::MilestoneDiff(milestone_data.ledger_diff()?);
+            let _ = ledger_handle.send(ledger_event);
+        }
+        Ok(())
+    }
+    fn handle_in_database(&mut self, milestone_index: u32) -> anyhow::Result<()> {
+        if let Some(mut in_database) = self.in_database.remove(&milestone_index) {
+            // Persist the milestone cone, now that all of its messages are in the database
+            self.insert_milestone_messages(milestone_index, in_database.message_ids())?;
+            // Notify the subscribers, it's fine if there are none left
+            if let (Some(milestone_events), Some(milestone_event)) =
+                (self.milestone_events.as_ref(), in_database.take_milestone_event())
+            {
+                milestone_events.send(std::sync::Arc::new(milestone_event)).ok();
+            }
+        }
         self.lru_in_database.put(milestone_index, ());
         let sync_key = Synckey;
         let synced_by = Some(self.chronicle_id);
@@ -318,6 +485,24 @@ impl Solidifier {
         request.send_local(worker);
         Ok(())
     }
+    fn insert_milestone_messages<'a>(
+        &self,
+        milestone_index: u32,
+        message_ids: impl Iterator<Item = &'a MessageId>,
+    ) -> anyhow::Result<()> {
+        let partition_id = self.partition_config.partition_id(milestone_index);
+        let key = Partitioned::new(MilestoneIndex(milestone_index), partition_id, milestone_index);
+        for message_id in message_ids {
+            let request = self
+                .keyspace
+                .insert(&key, message_id)
+                .consistency(Consistency::One)
+                .build()?;
+            let worker = InsertWorker::boxed(self.keyspace.clone(), key.clone(), *message_id, self.retries as usize);
+            request.send_local(worker);
+        }
+        Ok(())
+    }
     fn insert_analytic(&self, milestone_index: u32, analytic_record: AnalyticRecord) -> anyhow::Result<()> {
         let sync_key = Synckey;
         let request = self
@@ -336,6 +521,21 @@ impl Solidifier {
         request.send_local(worker);
         Ok(())
     }
+    fn insert_extended_analytic(&self, extended_analytic_record: ExtendedAnalyticRecord) -> anyhow::Result<()> {
+        let request = self
+            .keyspace
+            .insert(&Synckey, &extended_analytic_record)
+            .consistency(Consistency::One)
+            .build()?;
+        let worker = InsertWorker::boxed(
+            self.keyspace.clone(),
+            Synckey,
+            extended_analytic_record,
+            self.retries as usize,
+        );
+        request.send_local(worker);
+        Ok(())
+    }
     fn handle_milestone_msg(
         &mut self,
         MilestoneMessage(_message_id, milestone_payload, message, metadata): MilestoneMessage,
@@ -11,6 +11,10 @@ use super::{
         CollectorHandle,
         MessageIdPartitioner,
     },
+    ledger::{
+        LedgerEvent,
+        LedgerHandle,
+    },
     syncer::{
         SyncerEvent,
         SyncerHandle,
@@ -18,6 +22,11 @@ use super::{
     *,
 };
 use bee_message::prelude::MilestonePayload;
+use chronicle_common::config::{
+    CoordinatorConfig,
+    FilterConfig,
+    PartitionConfig,
+};
 
 use std::{
     ops::{
@@ -30,6 +39,9 @@ use std::{
 mod event_loop;
 mod init;
 mod terminating;
+mod verification;
+
+pub use verification::*;
 
 // Solidifier builder
 builder!(SolidifierBuilder {
@@ -39,12 +51,17 @@ builder!(SolidifierBuilder {
     lru_capacity: usize,
     syncer_handle: SyncerHandle,
     archiver_handle: ArchiverHandle,
+    ledger_handle: LedgerHandle,
+    coordinator: CoordinatorConfig,
     handle: SolidifierHandle,
     inbox: SolidifierInbox,
     gap_start: u32,
     retries: u16,
     collector_handles: HashMap<u8, CollectorHandle>,
-    collector_count: u8
+    collector_count: u8,
+    partition_config: PartitionConfig,
+    filter_config: FilterConfig,
+    milestone_events: MilestoneEventSender
 });
 
 /// A milestone message payload
@@ -67,6 +84,7 @@ struct InDatabase {
     analyzed: bool,
     messages_len: usize,
     in_database: HashMap<MessageId, ()>,
+    milestone_event: Option<MilestoneEvent>,
 }
 
 impl InDatabase {
@@ -76,6 +94,7 @@ impl InDatabase {
             analyzed: false,
             messages_len: usize::MAX,
             in_database: HashMap::new(),
+            milestone_event: None,
         }
     }
     fn set_messages_len(&mut self, message_len: usize) {
@@ -90,6 +109,15 @@ impl InDatabase {
     fn check_if_all_in_database(&self) -> bool {
         self.messages_len == self.in_database.len() && self.analyzed
     }
+    fn message_ids(&self) -> impl Iterator<Item = &MessageId> {
+        self.in_database.keys()
+    }
+    fn set_milestone_event(&mut self, milestone_event: MilestoneEvent) {
+        self.milestone_event.replace(milestone_event);
+    }
+    fn take_milestone_event(&mut self) -> Option<MilestoneEvent> {
+        self.milestone_event.take()
+    }
 }
 
 impl From<&MilestoneData> for InDatabase {
@@ -185,10 +213,19 @@ pub struct Solidifier {
     in_database: HashMap<u32, InDatabase>,
     lru_in_database: lru::LruCache<u32, ()>,
     unreachable: lru::LruCache<u32, ()>,
+    /// The milestones which failed verification, they are skipped once the syncer asks for them again
+    quarantined: lru::LruCache<u32, ()>,
     collector_handles: HashMap<u8, CollectorHandle>,
     collector_count: u8,
+    partition_config: PartitionConfig,
+    /// The filter config, used to find the keyspace of the messages of a milestone
+    #[cfg(feature = "filter")]
+    filter_config: FilterConfig,
     syncer_handle: SyncerHandle,
     archiver_handle: Option<ArchiverHandle>,
+    ledger_handle: Option<LedgerHandle>,
+    coordinator: Option<CoordinatorConfig>,
+    milestone_events: Option<MilestoneEventSender>,
     message_id_partitioner: MessageIdPartitioner,
     first: Option<u32>,
     gap_start: u32,
@@ -213,11 +250,18 @@ impl Builder for SolidifierBuilder {
             in_database: HashMap::new(),
             lru_in_database: lru::LruCache::new(100),
             unreachable: lru::LruCache::new(100),
+            quarantined: lru::LruCache::new(100),
             milestones_data: HashMap::new(),
             collector_handles: self.collector_handles.unwrap(),
             collector_count,
+            partition_config: self.partition_config.unwrap_or_default(),
+            #[cfg(feature = "filter")]
+            filter_config: self.filter_config.unwrap_or_default(),
             syncer_handle: self.syncer_handle.unwrap(),
             archiver_handle: self.archiver_handle,
+            ledger_handle: self.ledger_handle,
+            coordinator: self.coordinator,
+            milestone_events: self.milestone_events,
             message_id_partitioner: MessageIdPartitioner::new(collector_count),
             first: None,
             gap_start: self.gap_start.unwrap(),
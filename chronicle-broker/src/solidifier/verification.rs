@@ -0,0 +1,301 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use bee_message::payload::Payload;
+use blake2::{
+    digest::{
+        Update,
+        VariableOutput,
+    },
+    VarBlake2b,
+};
+use chronicle_common::config::CoordinatorConfig;
+use std::collections::HashSet;
+use thiserror::Error;
+
+/// The prefix of the merkle tree leaf hashes
+const LEAF_HASH_PREFIX: u8 = 0;
+/// The prefix of the merkle tree node hashes
+const NODE_HASH_PREFIX: u8 = 1;
+
+/// Errors which fail the verification of a solidified milestone
+#[derive(Error, Debug)]
+pub enum MilestoneVerificationError {
+    /// The milestone payload is missing from the milestone data
+    #[error("Milestone {0} has no milestone payload")]
+    MissingMilestone(u32),
+    /// The milestone isn't signed by enough valid coordinator keys
+    #[error("Milestone {milestone_index} has invalid signatures: {reason}")]
+    InvalidSignatures {
+        /// The milestone index
+        milestone_index: u32,
+        /// Why the signatures are invalid
+        reason: String,
+    },
+    /// The inclusion merkle proof doesn't match the included transactions of the cone
+    #[error(
+        "Milestone {milestone_index} has inclusion merkle proof {expected}, but its included transactions compute to {computed}"
+    )]
+    MerkleProofMismatch {
+        /// The milestone index
+        milestone_index: u32,
+        /// The hex encoded merkle proof of the milestone
+        expected: String,
+        /// The hex encoded merkle root computed from the cone
+        computed: String,
+    },
+    /// Computing the merkle root failed
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+/// Verify the signatures and the inclusion merkle proof of a solidified milestone
+pub fn verify_milestone(
+    milestone_data: &MilestoneData,
+    coordinator: &CoordinatorConfig,
+) -> Result<(), MilestoneVerificationError> {
+    let milestone_index = milestone_data.milestone_index();
+    let milestone = milestone_data
+        .milestone
+        .as_ref()
+        .ok_or(MilestoneVerificationError::MissingMilestone(milestone_index))?;
+    milestone
+        .validate(&coordinator.public_keys(milestone_index), coordinator.public_key_count)
+        .map_err(|e| MilestoneVerificationError::InvalidSignatures {
+            milestone_index,
+            reason: format!("{:?}", e),
+        })?;
+    let included = white_flag_included(milestone_data, milestone.essence().parents());
+    let computed = merkle_root(&included)?;
+    if milestone.essence().merkle_proof() != &computed[..] {
+        return Err(MilestoneVerificationError::MerkleProofMismatch {
+            milestone_index,
+            expected: hex::encode(milestone.essence().merkle_proof()),
+            computed: hex::encode(computed),
+        });
+    }
+    Ok(())
+}
+
+/// Collect the included transaction messages of the milestone cone in white-flag order, which is the post-order
+/// of the depth first traversal of the milestone parents, visiting the parents of every message in order
+fn white_flag_included(milestone_data: &MilestoneData, parents: &[MessageId]) -> Vec<MessageId> {
+    let messages = milestone_data.messages();
+    let mut visited = HashSet::new();
+    let mut included = Vec::new();
+    for parent in parents {
+        let mut stack = vec![*parent];
+        while let Some(message_id) = stack.last().copied() {
+            // Messages outside of the cone were referenced by older milestones
+            let full_message = match messages.get(&message_id) {
+                Some(full_message) if !visited.contains(&message_id) => full_message,
+                _ => {
+                    stack.pop();
+                    continue;
+                }
+            };
+            let next_parent = full_message
+                .message()
+                .parents()
+                .iter()
+                .find(|parent_id| messages.contains_key(*parent_id) && !visited.contains(*parent_id));
+            if let Some(parent_id) = next_parent {
+                stack.push(*parent_id);
+                continue;
+            }
+            stack.pop();
+            visited.insert(message_id);
+            let is_transaction = matches!(full_message.message().payload(), Some(Payload::Transaction(_)));
+            let is_included = matches!(
+                full_message.metadata().ledger_inclusion_state,
+                Some(LedgerInclusionState::Included)
+            );
+            if is_transaction && is_included {
+                included.push(message_id);
+            }
+        }
+    }
+    included
+}
+
+/// Compute the merkle tree hash of the message ids, where the tree splits at the largest power of two lower than
+/// the number of leaves
+fn merkle_root(message_ids: &[MessageId]) -> anyhow::Result<Vec<u8>> {
+    match message_ids.len() {
+        0 => hash(&[]),
+        1 => hash(&[&[LEAF_HASH_PREFIX][..], message_ids[0].as_ref()]),
+        len => {
+            let split = len.next_power_of_two() / 2;
+            let left = merkle_root(&message_ids[..split])?;
+            let right = merkle_root(&message_ids[split..])?;
+            hash(&[&[NODE_HASH_PREFIX][..], &left[..], &right[..]])
+        }
+    }
+}
+
+/// Compute the BLAKE2b-256 hash of the concatenated chunks
+fn hash(chunks: &[&[u8]]) -> anyhow::Result<Vec<u8>> {
+    let mut hasher = VarBlake2b::new(32)?;
+    for chunk in chunks {
+        hasher.update(chunk);
+    }
+    Ok(hasher.finalize_boxed().into_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bee_message::prelude::*;
+    use bee_pow::providers::miner::Miner;
+
+    /// The message ids of the RFC-0012 test vector, in white-flag order
+    const MESSAGE_IDS: [&str; 7] = [
+        "52fdfc072182654f163f5f0f9a621d729566c74d10037c4d7bbb0407d1e2c649",
+        "81855ad8681d0d86d1e91e00167939cb6694d2c422acd208a0072939487f6999",
+        "eb9d18a44784045d87f3c67cf22746e995af5a25367951baa2ff6cd471c483f1",
+        "5fb90badb37c5821b6d95526a41a9504680b4e7c8b763a1b1d49d4955c848621",
+        "6325253fec738dd7a9e28bf921119c160f0702448615bbda08313f6a8eb668d2",
+        "0bf5059875921e668a5bdf2c7fc4844592d2572bcd0668d2d6c52f5054e2d083",
+        "6bf84c7174cb7476364cc3dbd968b0f7172ed85794bb358b0c3b525da1786f9f",
+    ];
+
+    fn merkle_root_of(leaves: usize) -> String {
+        let message_ids = MESSAGE_IDS[..leaves]
+            .iter()
+            .map(|id| id.parse::<MessageId>().unwrap())
+            .collect::<Vec<_>>();
+        hex::encode(merkle_root(&message_ids).unwrap())
+    }
+
+    #[test]
+    fn merkle_root_test_vectors() {
+        assert_eq!(
+            merkle_root_of(0),
+            "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8"
+        );
+        assert_eq!(
+            merkle_root_of(1),
+            "3d1399c64ff0ae6a074afa4cd2ce4eab8d5c499c1da6afdd1d84b7447cc00544"
+        );
+        assert_eq!(
+            merkle_root_of(2),
+            "ae4505f4cfae93586e23958ca88d35d2f34d43def49786b6d0d4224b819f4cda"
+        );
+        assert_eq!(
+            merkle_root_of(3),
+            "0533872a888af35b5f18d1e616445084f5a6a0bf3de52ccb1b67ffee830099b7"
+        );
+        assert_eq!(
+            merkle_root_of(5),
+            "632ce808b3b006d8305c93fb74ce34007276dca2d83a93e871de526ca3ab6b34"
+        );
+        assert_eq!(
+            merkle_root_of(7),
+            "bf67ce7ba23e8c0951b5abaec4f5524360d2c26d971ff226d3359fa70cdb0beb"
+        );
+    }
+
+    fn transaction(seed: u8) -> Payload {
+        let input = Input::Utxo(UtxoInput::new(TransactionId::new([seed; 32]), 0).unwrap());
+        let output = Output::SignatureLockedSingle(
+            SignatureLockedSingleOutput::new(Address::Ed25519(Ed25519Address::new([seed; 32])), 1_000_000).unwrap(),
+        );
+        let essence = RegularEssenceBuilder::new()
+            .with_inputs(vec![input])
+            .with_outputs(vec![output])
+            .finish()
+            .unwrap();
+        let signature = Ed25519Signature::new([seed; 32], Box::new([0; 64]));
+        let unlock_blocks =
+            UnlockBlocks::new(vec![UnlockBlock::Signature(SignatureUnlock::Ed25519(signature))]).unwrap();
+        Payload::Transaction(Box::new(
+            TransactionPayloadBuilder::new()
+                .with_essence(Essence::Regular(essence))
+                .with_unlock_blocks(unlock_blocks)
+                .finish()
+                .unwrap(),
+        ))
+    }
+
+    /// Add a message to the milestone data, returning its id
+    fn add(
+        milestone_data: &mut MilestoneData,
+        mut parents: Vec<MessageId>,
+        payload: Payload,
+        ledger_inclusion_state: LedgerInclusionState,
+    ) -> MessageId {
+        parents.sort();
+        let message = MessageBuilder::<Miner>::new()
+            .with_network_id(0)
+            .with_parents(Parents::new(parents.clone()).unwrap())
+            .with_payload(payload)
+            .finish()
+            .unwrap();
+        let message_id = message.id().0;
+        let metadata = MessageMetadata {
+            message_id,
+            parent_message_ids: parents,
+            is_solid: true,
+            referenced_by_milestone_index: Some(1),
+            ledger_inclusion_state: Some(ledger_inclusion_state),
+            should_promote: None,
+            should_reattach: None,
+            conflict_reason: None,
+        };
+        milestone_data.add_full_message(FullMessage::new(message, metadata));
+        message_id
+    }
+
+    #[test]
+    fn white_flag_order_of_a_diamond() {
+        let mut milestone_data = MilestoneData::new(1, CreatedBy::Incoming);
+        // Referenced by an older milestone, so outside of the cone
+        let outside = MessageId::new([1; 32]);
+        let root = add(
+            &mut milestone_data,
+            vec![outside],
+            transaction(1),
+            LedgerInclusionState::Included,
+        );
+        let left = add(
+            &mut milestone_data,
+            vec![root, outside],
+            transaction(2),
+            LedgerInclusionState::Included,
+        );
+        let right = add(
+            &mut milestone_data,
+            vec![root],
+            transaction(3),
+            LedgerInclusionState::Included,
+        );
+        let index = Payload::Indexation(Box::new(IndexationPayload::new(b"Chronicle", &[]).unwrap()));
+        let tip = add(
+            &mut milestone_data,
+            vec![left, right],
+            index,
+            LedgerInclusionState::NoTransaction,
+        );
+        let conflicting = add(
+            &mut milestone_data,
+            vec![root],
+            transaction(4),
+            LedgerInclusionState::Conflicting,
+        );
+        let mut parents = vec![tip, conflicting];
+        parents.sort();
+
+        // The root comes first, then the parents of the tip in their order, the others aren't included transactions
+        let (first, second) = if left < right { (left, right) } else { (right, left) };
+        assert_eq!(
+            white_flag_included(&milestone_data, &parents),
+            vec![root, first, second]
+        );
+        // Every message is visited once, even if it's referenced by both milestone parents
+        assert_eq!(
+            white_flag_included(&milestone_data, &[left, right]),
+            vec![root, left, right]
+        );
+    }
+}
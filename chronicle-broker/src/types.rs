@@ -3,21 +3,43 @@
 
 use bee_message::{
     prelude::{
+        Address,
+        Ed25519Address,
         Essence,
+        Input,
+        MilestoneIndex,
         MilestonePayload,
         Output,
+        OutputId,
         Payload,
+        SignatureLockedSingleOutput,
+        SignatureUnlock,
+        TransactionId,
+        UnlockBlock,
     },
     Message,
     MessageId,
 };
+use blake2::{
+    digest::{
+        Update,
+        VariableOutput,
+    },
+    VarBlake2b,
+};
 use chronicle_storage::access::{
     AnalyticRecord,
+    ExtendedAnalyticRecord,
     LedgerInclusionState,
+    LedgerOutputRecord,
+    LegacyTailTransactionHash,
     MessageCount,
     MessageMetadata,
+    MigratedFundsRecord,
+    ReceiptRecord,
     TransactionCount,
     TransferredTokens,
+    TreasuryRecord,
 };
 #[cfg(feature = "scylla-rs")]
 use scylla_rs::cql::Rows;
@@ -26,7 +48,11 @@ use serde::{
     Serialize,
 };
 use std::{
-    collections::HashMap,
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    convert::TryInto,
     ops::Range,
     path::PathBuf,
 };
@@ -63,7 +89,7 @@ pub enum BrokerTopology {
     RemoveMqttMessagesReferenced(Url),
     /// Import a log file using the given url
     Import {
-        /// File or dir path which supposed to contain LogFiles
+        /// File or dir path which supposed to contain LogFiles, or snapshot files
         path: PathBuf,
         /// Resume the importing process
         resume: bool,
@@ -83,6 +109,8 @@ pub enum ImportType {
     All,
     /// Import only Analytics data
     Analytics,
+    /// Import the ledger state of Chrysalis full and delta snapshots
+    Snapshot,
 }
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
@@ -162,6 +190,133 @@ impl MilestoneData {
         // Return the analytic record
         Ok(analytic_record)
     }
+    /// Get the extended analytics from the collected messages
+    pub fn get_extended_analytic_record(&self) -> anyhow::Result<ExtendedAnalyticRecord> {
+        if !self.check_if_completed() {
+            anyhow::bail!("cannot get extended analytics for uncompleted milestone data")
+        }
+        let mut record = ExtendedAnalyticRecord::new(bee_message::milestone::MilestoneIndex(self.milestone_index()));
+        let mut sending_addresses = HashSet::new();
+        let mut receiving_addresses = HashSet::new();
+        for (_, FullMessage(message, metadata)) in &self.messages {
+            // Accumulate the payload mix of the whole cone
+            match message.payload() {
+                Some(Payload::Indexation(_)) => record.indexation_payloads += 1,
+                Some(Payload::Transaction(_)) => record.transaction_payloads += 1,
+                Some(Payload::Milestone(milestone)) => {
+                    record.milestone_payloads += 1;
+                    if milestone.essence().receipt().is_some() {
+                        record.receipt_payloads += 1;
+                    }
+                }
+                Some(Payload::Receipt(_)) => record.receipt_payloads += 1,
+                _ => (),
+            }
+            match metadata.ledger_inclusion_state {
+                Some(LedgerInclusionState::Conflicting) => record.conflicts.count(metadata.conflict_reason),
+                Some(LedgerInclusionState::Included) => {
+                    if let Some(Payload::Transaction(payload)) = message.payload() {
+                        let Essence::Regular(regular_essence) = payload.essence();
+                        // The sending addresses are the ones whose keys signed the inputs
+                        let unlock_blocks = payload.unlock_blocks();
+                        for input_index in 0..regular_essence.inputs().len() {
+                            if let UnlockBlock::Signature(SignatureUnlock::Ed25519(signature)) =
+                                &unlock_blocks[input_index]
+                            {
+                                let mut hasher = VarBlake2b::new(32)?;
+                                hasher.update(signature.public_key());
+                                let mut address = [0u8; 32];
+                                hasher.finalize_variable(|hash| address.copy_from_slice(hash));
+                                sending_addresses.insert(Ed25519Address::new(address));
+                            }
+                        }
+                        for output in regular_essence.outputs() {
+                            match output {
+                                Output::SignatureLockedSingle(output) => {
+                                    let Address::Ed25519(address) = output.address();
+                                    receiving_addresses.insert(*address);
+                                }
+                                Output::SignatureLockedDustAllowance(output) => {
+                                    let Address::Ed25519(address) = output.address();
+                                    receiving_addresses.insert(*address);
+                                    record.dust_allowance_outputs += 1;
+                                }
+                                _ => anyhow::bail!("Unexpected Output variant in transaction payload"),
+                            }
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        record.sending_addresses = sending_addresses.len() as u32;
+        record.receiving_addresses = receiving_addresses.len() as u32;
+        Ok(record)
+    }
+    /// Get the ledger mutations of the milestone
+    pub fn ledger_diff(&self) -> anyhow::Result<MilestoneDiff> {
+        if !self.check_if_completed() {
+            anyhow::bail!("cannot get the ledger diff of uncompleted milestone data")
+        }
+        MilestoneDiff::new(
+            self.milestone_index,
+            self.messages
+                .values()
+                .map(|FullMessage(message, metadata)| (message, metadata)),
+        )
+    }
+    /// Get the event which announces the milestone once it is in the database, with every message tagged by
+    /// the keyspace it was stored in. Messages without a keyspace were dropped, so they are left out.
+    pub fn milestone_event(&self, keyspace_of: impl Fn(&FullMessage) -> Option<String>) -> MilestoneEvent {
+        let messages = self
+            .messages
+            .values()
+            .filter_map(|full_message| keyspace_of(full_message).map(|keyspace| (keyspace, full_message)))
+            .map(|(keyspace, FullMessage(message, metadata))| {
+                let mut index = None;
+                let mut addresses = Vec::new();
+                match message.payload() {
+                    Some(Payload::Indexation(indexation)) => index = Some(hex::encode(indexation.index())),
+                    Some(Payload::Transaction(payload)) => {
+                        let Essence::Regular(regular_essence) = payload.essence();
+                        if let Some(Payload::Indexation(indexation)) = regular_essence.payload() {
+                            index = Some(hex::encode(indexation.index()));
+                        }
+                        for output in regular_essence.outputs() {
+                            match output {
+                                Output::SignatureLockedSingle(output) => {
+                                    let Address::Ed25519(address) = output.address();
+                                    addresses.push(*address);
+                                }
+                                Output::SignatureLockedDustAllowance(output) => {
+                                    let Address::Ed25519(address) = output.address();
+                                    addresses.push(*address);
+                                }
+                                _ => (),
+                            }
+                        }
+                    }
+                    _ => (),
+                }
+                MessageEvent {
+                    keyspace,
+                    message_id: metadata.message_id,
+                    ledger_inclusion_state: metadata.ledger_inclusion_state,
+                    index,
+                    addresses,
+                }
+            })
+            .collect();
+        MilestoneEvent {
+            milestone_index: self.milestone_index,
+            timestamp: self
+                .milestone
+                .as_ref()
+                .map(|milestone| milestone.essence().timestamp())
+                .unwrap_or_default(),
+            messages,
+        }
+    }
     pub(crate) fn set_milestone(&mut self, boxed_milestone_payload: Box<MilestonePayload>) {
         self.milestone.replace(boxed_milestone_payload);
     }
@@ -200,6 +355,96 @@ impl MilestoneData {
     }
 }
 
+/// The ledger mutations of a milestone, which are the outputs consumed and created by its included transactions
+#[derive(Clone, Debug)]
+pub struct MilestoneDiff {
+    milestone_index: u32,
+    created: HashMap<OutputId, LedgerOutputRecord>,
+    consumed: HashMap<OutputId, TransactionId>,
+}
+
+impl MilestoneDiff {
+    /// Compute the ledger mutations of a milestone from the messages it referenced
+    pub fn new<'a>(
+        milestone_index: u32,
+        messages: impl IntoIterator<Item = (&'a Message, &'a MessageMetadata)>,
+    ) -> anyhow::Result<Self> {
+        let mut created = HashMap::new();
+        let mut consumed = HashMap::new();
+        for (message, metadata) in messages {
+            match message.payload() {
+                Some(Payload::Transaction(payload)) => {
+                    if let Some(LedgerInclusionState::Included) = metadata.ledger_inclusion_state {
+                        let transaction_id = payload.id();
+                        let Essence::Regular(regular_essence) = payload.essence();
+                        for input in regular_essence.inputs() {
+                            // Treasury inputs are not part of the unspent outputs
+                            if let Input::Utxo(utxo_input) = input {
+                                consumed.insert(*utxo_input.output_id(), transaction_id);
+                            }
+                        }
+                        for (output_index, output) in regular_essence.outputs().iter().enumerate() {
+                            let output_id = OutputId::new(transaction_id, output_index as u16)?;
+                            let record = LedgerOutputRecord::new(
+                                metadata.message_id,
+                                output.clone(),
+                                MilestoneIndex(milestone_index),
+                            );
+                            created.insert(output_id, record);
+                        }
+                    }
+                }
+                Some(Payload::Milestone(milestone)) if *milestone.essence().index() == milestone_index => {
+                    // The migrated funds of a receipt are created as outputs of the milestone itself
+                    if let Some(Payload::Receipt(receipt)) = milestone.essence().receipt() {
+                        let transaction_id = TransactionId::new(milestone.id().as_ref().try_into()?);
+                        for (output_index, funds) in receipt.funds().iter().enumerate() {
+                            let output_id = OutputId::new(transaction_id, output_index as u16)?;
+                            let output: SignatureLockedSingleOutput = funds.output().clone();
+                            let record = LedgerOutputRecord::new(
+                                metadata.message_id,
+                                Output::SignatureLockedSingle(output),
+                                MilestoneIndex(milestone_index),
+                            );
+                            created.insert(output_id, record);
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        Ok(Self {
+            milestone_index,
+            created,
+            consumed,
+        })
+    }
+    /// Create a milestone diff from already known ledger mutations, such as the ones stored in a snapshot
+    pub fn from_parts(
+        milestone_index: u32,
+        created: HashMap<OutputId, LedgerOutputRecord>,
+        consumed: HashMap<OutputId, TransactionId>,
+    ) -> Self {
+        Self {
+            milestone_index,
+            created,
+            consumed,
+        }
+    }
+    /// Get the milestone index of the diff
+    pub fn milestone_index(&self) -> u32 {
+        self.milestone_index
+    }
+    /// Get the outputs created by the milestone
+    pub fn created(&self) -> &HashMap<OutputId, LedgerOutputRecord> {
+        &self.created
+    }
+    /// Get the outputs consumed by the milestone, along with the transactions which spent them
+    pub fn consumed(&self) -> &HashMap<OutputId, TransactionId> {
+        &self.consumed
+    }
+}
+
 impl std::iter::IntoIterator for MilestoneData {
     type Item = (MessageId, FullMessage);
     type IntoIter = std::collections::hash_map::IntoIter<MessageId, FullMessage>;
@@ -208,6 +453,36 @@ impl std::iter::IntoIterator for MilestoneData {
     }
 }
 
+/// Published by the solidifiers once all the data of a milestone is in the database
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MilestoneEvent {
+    /// The milestone index
+    pub milestone_index: u32,
+    /// The milestone timestamp
+    pub timestamp: u64,
+    /// The messages referenced by the milestone
+    pub messages: Vec<MessageEvent>,
+}
+
+/// The sender of the channel which broadcasts `MilestoneEvent`s to their subscribers
+#[cfg(feature = "tokio")]
+pub type MilestoneEventSender = tokio::sync::broadcast::Sender<std::sync::Arc<MilestoneEvent>>;
+
+/// A message referenced by the milestone of a `MilestoneEvent`
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MessageEvent {
+    /// The keyspace the message was stored in
+    pub keyspace: String,
+    /// The message id
+    pub message_id: MessageId,
+    /// The ledger inclusion state of the message
+    pub ledger_inclusion_state: Option<LedgerInclusionState>,
+    /// The hex encoded index of the message's indexation payload, if any
+    pub index: Option<String>,
+    /// The ed25519 addresses of the message's transaction outputs
+    pub addresses: Vec<Ed25519Address>,
+}
+
 /// Created by sources
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[repr(u8)]
@@ -253,6 +528,55 @@ impl FullMessage {
     }
 }
 
+/// The rows which store the receipt carried by a milestone, along with its migrated funds and the
+/// treasury output it created
+#[derive(Debug)]
+pub(crate) struct ReceiptRows {
+    /// The migrated funds, by the tail transaction hash of their legacy bundle
+    pub(crate) migrated_funds: Vec<(LegacyTailTransactionHash, MigratedFundsRecord)>,
+    /// The treasury output created by the receipt's treasury transaction
+    pub(crate) treasury: Option<TreasuryRecord>,
+    /// The receipt
+    pub(crate) receipt: ReceiptRecord,
+}
+
+impl ReceiptRows {
+    /// Get the rows of the receipt carried by the milestone, if any
+    pub(crate) fn new(milestone: &MilestonePayload) -> Option<Self> {
+        if let Some(Payload::Receipt(receipt)) = milestone.essence().receipt() {
+            let milestone_index = MilestoneIndex(*milestone.essence().index());
+            let migrated_funds = receipt
+                .funds()
+                .iter()
+                .map(|funds| {
+                    (
+                        LegacyTailTransactionHash(funds.tail_transaction_hash().to_string()),
+                        MigratedFundsRecord::new(milestone_index, receipt.migrated_at(), funds.output().clone()),
+                    )
+                })
+                .collect();
+            let treasury = match receipt.transaction() {
+                Payload::TreasuryTransaction(treasury_transaction) => match treasury_transaction.output() {
+                    Output::Treasury(treasury_output) => Some(TreasuryRecord::new(
+                        milestone_index,
+                        milestone.id(),
+                        treasury_output.amount(),
+                    )),
+                    _ => None,
+                },
+                _ => None,
+            };
+            Some(Self {
+                migrated_funds,
+                treasury,
+                receipt: ReceiptRecord::new(milestone_index, (**receipt).clone()),
+            })
+        } else {
+            None
+        }
+    }
+}
+
 /// Enum used by importer to keep the sockets up to date with most recent progress.
 #[derive(Deserialize, Serialize, Debug)]
 pub enum ImporterSession {
@@ -380,6 +704,36 @@ mod sync {
                 Ok(sync_data)
             }
         }
+        /// Get the completed(synced and logged) ranges, from the most recent to the oldest
+        pub fn completed(&self) -> &[Range<u32>] {
+            &self.completed
+        }
+        /// Get the synced but unlogged ranges, from the most recent to the oldest
+        pub fn synced_but_unlogged(&self) -> &[Range<u32>] {
+            &self.synced_but_unlogged
+        }
+        /// Get the gaps, from the most recent to the oldest
+        pub fn gaps(&self) -> &[Range<u32>] {
+            &self.gaps
+        }
+        /// Get the most recent synced milestone index, whether it was logged or not
+        pub fn latest_synced(&self) -> Option<u32> {
+            self.completed
+                .first()
+                .into_iter()
+                .chain(self.synced_but_unlogged.first())
+                .map(|range| range.end - 1)
+                .max()
+        }
+        /// Get the oldest synced milestone index, whether it was logged or not
+        pub fn earliest_synced(&self) -> Option<u32> {
+            self.completed
+                .last()
+                .into_iter()
+                .chain(self.synced_but_unlogged.last())
+                .map(|range| range.start)
+                .min()
+        }
         /// Takes the lowest gap from the sync_data
         pub fn take_lowest_gap(&mut self) -> Option<Range<u32>> {
             self.gaps.pop()
@@ -604,6 +958,33 @@ mod analytic {
             }
             Ok(analytics_data)
         }
+        /// Try to fetch the analytic record of every milestone in the sync range, from the most recent to the oldest
+        pub async fn try_fetch_records<S: 'static + Select<SyncRange, Iter<AnalyticRecord>>>(
+            keyspace: &S,
+            sync_range: &SyncRange,
+            retries: usize,
+            page_size: i32,
+        ) -> anyhow::Result<Vec<AnalyticRecord>> {
+            let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+            Self::query_analytics_table(keyspace, sync_range, retries, tx.clone(), page_size, None)?;
+            let mut analytic_records = Vec::new();
+            while let Some(mut records) = rx
+                .recv()
+                .await
+                .ok_or_else(|| anyhow::anyhow!("Unable to fetch the analytics response"))??
+            {
+                let has_more_pages = records.has_more_pages();
+                if has_more_pages {
+                    let paging_state = records.take_paging_state();
+                    Self::query_analytics_table(keyspace, sync_range, retries, tx.clone(), page_size, paging_state)?;
+                }
+                analytic_records.extend(&mut records);
+                if !has_more_pages {
+                    break;
+                }
+            }
+            Ok(analytic_records)
+        }
         async fn try_trigger(&mut self, analytics_rows: &mut Iter<AnalyticRecord>) {
             if let Some(analytic_record) = analytics_rows.next() {
                 self.process(analytic_record, analytics_rows).await;
@@ -651,4 +1032,433 @@ mod analytic {
             self.analytics.push(analytic_data);
         }
     }
+
+    /// The extended analytics of a sync range, summed over its milestones
+    #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+    pub struct ExtendedAnalyticsData {
+        indexation_payloads: u64,
+        transaction_payloads: u64,
+        milestone_payloads: u64,
+        receipt_payloads: u64,
+        conflicts: ConflictTotals,
+        /// The distinct sending addresses of each milestone, summed
+        sending_addresses_per_milestone_sum: u64,
+        /// The distinct receiving addresses of each milestone, summed
+        receiving_addresses_per_milestone_sum: u64,
+        dust_allowance_outputs: u64,
+    }
+
+    /// The conflicting transactions of a sync range, by conflict reason
+    #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+    pub struct ConflictTotals {
+        input_utxo_already_spent: u64,
+        input_utxo_already_spent_in_this_milestone: u64,
+        input_utxo_not_found: u64,
+        input_output_sum_mismatch: u64,
+        invalid_signature: u64,
+        invalid_dust_allowance: u64,
+        semantic_validation_failed: u64,
+        unknown: u64,
+    }
+
+    impl ExtendedAnalyticsData {
+        /// Try to fetch the extended analytics of the provided keyspace and sync range
+        pub async fn try_fetch<S: 'static + Select<SyncRange, Iter<ExtendedAnalyticRecord>>>(
+            keyspace: &S,
+            sync_range: &SyncRange,
+            retries: usize,
+            page_size: i32,
+        ) -> anyhow::Result<ExtendedAnalyticsData> {
+            let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+            Self::query_extended_analytics_table(keyspace, sync_range, retries, tx.clone(), page_size, None)?;
+            let mut extended_analytics_data = ExtendedAnalyticsData::default();
+            while let Some(mut records) = rx
+                .recv()
+                .await
+                .ok_or_else(|| anyhow::anyhow!("Unable to fetch the extended analytics response"))??
+            {
+                let has_more_pages = records.has_more_pages();
+                if has_more_pages {
+                    let paging_state = records.take_paging_state();
+                    Self::query_extended_analytics_table(
+                        keyspace,
+                        sync_range,
+                        retries,
+                        tx.clone(),
+                        page_size,
+                        paging_state,
+                    )?;
+                }
+                for record in &mut records {
+                    extended_analytics_data.add(record);
+                }
+                if !has_more_pages {
+                    break;
+                }
+            }
+            Ok(extended_analytics_data)
+        }
+        /// Try to fetch the extended analytic record of every milestone in the sync range
+        pub async fn try_fetch_records<S: 'static + Select<SyncRange, Iter<ExtendedAnalyticRecord>>>(
+            keyspace: &S,
+            sync_range: &SyncRange,
+            retries: usize,
+            page_size: i32,
+        ) -> anyhow::Result<Vec<ExtendedAnalyticRecord>> {
+            let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+            Self::query_extended_analytics_table(keyspace, sync_range, retries, tx.clone(), page_size, None)?;
+            let mut extended_analytic_records = Vec::new();
+            while let Some(mut records) = rx
+                .recv()
+                .await
+                .ok_or_else(|| anyhow::anyhow!("Unable to fetch the extended analytics response"))??
+            {
+                let has_more_pages = records.has_more_pages();
+                if has_more_pages {
+                    let paging_state = records.take_paging_state();
+                    Self::query_extended_analytics_table(
+                        keyspace,
+                        sync_range,
+                        retries,
+                        tx.clone(),
+                        page_size,
+                        paging_state,
+                    )?;
+                }
+                extended_analytic_records.extend(&mut records);
+                if !has_more_pages {
+                    break;
+                }
+            }
+            Ok(extended_analytic_records)
+        }
+        /// Add the extended analytic record of one milestone to the sums
+        pub fn add(&mut self, record: ExtendedAnalyticRecord) {
+            self.indexation_payloads += record.indexation_payloads as u64;
+            self.transaction_payloads += record.transaction_payloads as u64;
+            self.milestone_payloads += record.milestone_payloads as u64;
+            self.receipt_payloads += record.receipt_payloads as u64;
+            let conflicts = &mut self.conflicts;
+            conflicts.input_utxo_already_spent += record.conflicts.input_utxo_already_spent as u64;
+            conflicts.input_utxo_already_spent_in_this_milestone +=
+                record.conflicts.input_utxo_already_spent_in_this_milestone as u64;
+            conflicts.input_utxo_not_found += record.conflicts.input_utxo_not_found as u64;
+            conflicts.input_output_sum_mismatch += record.conflicts.input_output_sum_mismatch as u64;
+            conflicts.invalid_signature += record.conflicts.invalid_signature as u64;
+            conflicts.invalid_dust_allowance += record.conflicts.invalid_dust_allowance as u64;
+            conflicts.semantic_validation_failed += record.conflicts.semantic_validation_failed as u64;
+            conflicts.unknown += record.conflicts.unknown as u64;
+            self.sending_addresses_per_milestone_sum += record.sending_addresses as u64;
+            self.receiving_addresses_per_milestone_sum += record.receiving_addresses as u64;
+            self.dust_allowance_outputs += record.dust_allowance_outputs as u64;
+        }
+        fn query_extended_analytics_table<S: 'static + Select<SyncRange, Iter<ExtendedAnalyticRecord>>>(
+            keyspace: &S,
+            sync_range: &SyncRange,
+            retries: usize,
+            tx: tokio::sync::mpsc::UnboundedSender<
+                Result<Option<Iter<ExtendedAnalyticRecord>>, scylla_rs::app::WorkerError>,
+            >,
+            page_size: i32,
+            paging_state: Option<Vec<u8>>,
+        ) -> anyhow::Result<()> {
+            let req = keyspace
+                .select(sync_range)
+                .consistency(Consistency::One)
+                .page_size(page_size)
+                .paging_state(&paging_state)
+                .build()?;
+            let worker = ValueWorker::new(
+                tx,
+                keyspace.clone(),
+                sync_range.clone(),
+                retries,
+                std::marker::PhantomData,
+            )
+            .with_paging(page_size, paging_state);
+            req.send_local(Box::new(worker));
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bee_message::prelude::*;
+    use bee_pow::providers::miner::Miner;
+
+    fn message(payload: Option<Payload>) -> Message {
+        let builder = MessageBuilder::<Miner>::new()
+            .with_network_id(0)
+            .with_parents(Parents::new(vec![MessageId::new([1; 32]), MessageId::new([2; 32])]).unwrap());
+        match payload {
+            Some(payload) => builder.with_payload(payload),
+            None => builder,
+        }
+        .finish()
+        .unwrap()
+    }
+
+    fn metadata(
+        message_id: MessageId,
+        ledger_inclusion_state: Option<LedgerInclusionState>,
+        conflict_reason: Option<u8>,
+    ) -> MessageMetadata {
+        MessageMetadata {
+            message_id,
+            parent_message_ids: vec![MessageId::new([1; 32]), MessageId::new([2; 32])],
+            is_solid: true,
+            referenced_by_milestone_index: Some(1),
+            ledger_inclusion_state,
+            should_promote: None,
+            should_reattach: None,
+            conflict_reason,
+        }
+    }
+
+    fn milestone(receipt: Option<Payload>) -> MilestonePayload {
+        let essence = MilestonePayloadEssence::new(
+            MilestoneIndex(1),
+            1_600_000_000,
+            Parents::new(vec![MessageId::new([1; 32]), MessageId::new([2; 32])]).unwrap(),
+            [0; MILESTONE_MERKLE_PROOF_LENGTH],
+            0,
+            0,
+            vec![[0; MILESTONE_PUBLIC_KEY_LENGTH]],
+            receipt,
+        )
+        .unwrap();
+        MilestonePayload::new(essence, vec![Box::new([0; MILESTONE_SIGNATURE_LENGTH])]).unwrap()
+    }
+
+    fn receipt(treasury_amount: u64) -> Payload {
+        let funds = MigratedFundsEntry::new(
+            TailTransactionHash::new([0; 49]).unwrap(),
+            SignatureLockedSingleOutput::new(Address::Ed25519(Ed25519Address::new([4; 32])), 1_000_000).unwrap(),
+        )
+        .unwrap();
+        let treasury_transaction = TreasuryTransactionPayload::new(
+            Input::Treasury(TreasuryInput::new(MilestoneId::new([5; 32]))),
+            Output::Treasury(TreasuryOutput::new(treasury_amount).unwrap()),
+        )
+        .unwrap();
+        Payload::Receipt(Box::new(
+            ReceiptPayload::new(
+                MilestoneIndex(100),
+                true,
+                vec![funds],
+                Payload::TreasuryTransaction(Box::new(treasury_transaction)),
+            )
+            .unwrap(),
+        ))
+    }
+
+    fn transaction(public_key: [u8; 32], outputs: Vec<Output>) -> Payload {
+        transaction_spending(
+            OutputId::new(TransactionId::new([0; 32]), 0).unwrap(),
+            public_key,
+            outputs,
+        )
+    }
+
+    fn transaction_spending(output_id: OutputId, public_key: [u8; 32], outputs: Vec<Output>) -> Payload {
+        let input = Input::Utxo(UtxoInput::new(*output_id.transaction_id(), output_id.index()).unwrap());
+        let essence = RegularEssenceBuilder::new()
+            .with_inputs(vec![input])
+            .with_outputs(outputs)
+            .finish()
+            .unwrap();
+        let signature = Ed25519Signature::new(public_key, Box::new([0; 64]));
+        let unlock_blocks =
+            UnlockBlocks::new(vec![UnlockBlock::Signature(SignatureUnlock::Ed25519(signature))]).unwrap();
+        Payload::Transaction(Box::new(
+            TransactionPayloadBuilder::new()
+                .with_essence(Essence::Regular(essence))
+                .with_unlock_blocks(unlock_blocks)
+                .finish()
+                .unwrap(),
+        ))
+    }
+
+    fn single_output(address: [u8; 32]) -> Output {
+        Output::SignatureLockedSingle(
+            SignatureLockedSingleOutput::new(Address::Ed25519(Ed25519Address::new(address)), 1_000_000).unwrap(),
+        )
+    }
+
+    fn dust_allowance_output(address: [u8; 32]) -> Output {
+        Output::SignatureLockedDustAllowance(
+            SignatureLockedDustAllowanceOutput::new(Address::Ed25519(Ed25519Address::new(address)), 1_000_000).unwrap(),
+        )
+    }
+
+    fn add(milestone_data: &mut MilestoneData, payload: Option<Payload>, state: Option<LedgerInclusionState>) {
+        add_with_reason(milestone_data, payload, state, None);
+    }
+
+    fn add_with_reason(
+        milestone_data: &mut MilestoneData,
+        payload: Option<Payload>,
+        state: Option<LedgerInclusionState>,
+        conflict_reason: Option<u8>,
+    ) {
+        let message = message(payload);
+        let message_id = message.id().0;
+        milestone_data.add_full_message(FullMessage::new(message, metadata(message_id, state, conflict_reason)));
+    }
+
+    #[test]
+    fn extended_analytic_record_of_a_milestone_cone() {
+        let mut milestone_data = MilestoneData::new(1, CreatedBy::Incoming);
+        let milestone = milestone(None);
+        let index = Payload::Indexation(Box::new(IndexationPayload::new(b"Chronicle", &[]).unwrap()));
+        add(
+            &mut milestone_data,
+            Some(index),
+            Some(LedgerInclusionState::NoTransaction),
+        );
+        add(&mut milestone_data, None, Some(LedgerInclusionState::NoTransaction));
+        add(
+            &mut milestone_data,
+            Some(Payload::Milestone(Box::new(milestone.clone()))),
+            Some(LedgerInclusionState::NoTransaction),
+        );
+        // Two included transactions signed by the same key, sending to three distinct addresses
+        add(
+            &mut milestone_data,
+            Some(transaction(
+                [3; 32],
+                vec![single_output([4; 32]), single_output([5; 32])],
+            )),
+            Some(LedgerInclusionState::Included),
+        );
+        add(
+            &mut milestone_data,
+            Some(transaction(
+                [3; 32],
+                vec![single_output([4; 32]), dust_allowance_output([6; 32])],
+            )),
+            Some(LedgerInclusionState::Included),
+        );
+        // A conflicting transaction does not count towards the addresses
+        add_with_reason(
+            &mut milestone_data,
+            Some(transaction([7; 32], vec![single_output([8; 32])])),
+            Some(LedgerInclusionState::Conflicting),
+            Some(2),
+        );
+        add_with_reason(
+            &mut milestone_data,
+            Some(transaction([9; 32], vec![single_output([8; 32])])),
+            Some(LedgerInclusionState::Conflicting),
+            None,
+        );
+
+        // The record is only available once the milestone data is complete
+        assert!(milestone_data.get_extended_analytic_record().is_err());
+        milestone_data.set_milestone(Box::new(milestone));
+
+        let record = milestone_data.get_extended_analytic_record().unwrap();
+        assert_eq!(record.milestone_index, MilestoneIndex(1));
+        assert_eq!(record.indexation_payloads, 1);
+        assert_eq!(record.transaction_payloads, 4);
+        assert_eq!(record.milestone_payloads, 1);
+        assert_eq!(record.receipt_payloads, 0);
+        assert_eq!(record.conflicts.input_utxo_already_spent_in_this_milestone, 1);
+        assert_eq!(record.conflicts.unknown, 1);
+        assert_eq!(record.sending_addresses, 1);
+        assert_eq!(record.receiving_addresses, 3);
+        assert_eq!(record.dust_allowance_outputs, 1);
+    }
+
+    #[test]
+    fn receipt_rows_of_a_milestone() {
+        assert!(ReceiptRows::new(&milestone(None)).is_none());
+
+        let milestone = milestone(Some(receipt(2_000_000)));
+        let rows = ReceiptRows::new(&milestone).unwrap();
+        assert_eq!(rows.migrated_funds.len(), 1);
+        let (tail_transaction_hash, migrated_funds) = &rows.migrated_funds[0];
+        // An all zero hash is made of the zero tryte only
+        assert_eq!(tail_transaction_hash.0, "9".repeat(81));
+        assert_eq!(migrated_funds.milestone_index, MilestoneIndex(1));
+        assert_eq!(migrated_funds.migrated_at, MilestoneIndex(100));
+        assert_eq!(migrated_funds.output.amount(), 1_000_000);
+        let treasury = rows.treasury.unwrap();
+        assert_eq!(treasury.milestone_index, MilestoneIndex(1));
+        assert_eq!(treasury.milestone_id, milestone.id());
+        assert_eq!(treasury.amount, 2_000_000);
+        assert_eq!(rows.receipt.milestone_index, MilestoneIndex(1));
+        assert_eq!(rows.receipt.receipt.migrated_at(), MilestoneIndex(100));
+    }
+
+    #[test]
+    fn milestone_diff_of_a_milestone_cone() {
+        let spent = OutputId::new(TransactionId::new([0; 32]), 0).unwrap();
+        let first = transaction_spending(spent, [3; 32], vec![single_output([4; 32]), single_output([5; 32])]);
+        let first_id = match &first {
+            Payload::Transaction(payload) => payload.id(),
+            _ => unreachable!(),
+        };
+        // Spends an output created by the same milestone
+        let second = transaction_spending(
+            OutputId::new(first_id, 1).unwrap(),
+            [5; 32],
+            vec![dust_allowance_output([6; 32])],
+        );
+        let second_id = match &second {
+            Payload::Transaction(payload) => payload.id(),
+            _ => unreachable!(),
+        };
+        // A conflicting transaction neither consumes nor creates outputs
+        let conflicting = transaction_spending(
+            OutputId::new(TransactionId::new([9; 32]), 0).unwrap(),
+            [7; 32],
+            vec![single_output([8; 32])],
+        );
+        let milestone = milestone(Some(receipt(2_000_000)));
+        let milestone_id = milestone.id();
+        let messages = vec![
+            (first, LedgerInclusionState::Included),
+            (second, LedgerInclusionState::Included),
+            (conflicting, LedgerInclusionState::Conflicting),
+            (
+                Payload::Milestone(Box::new(milestone)),
+                LedgerInclusionState::NoTransaction,
+            ),
+        ]
+        .into_iter()
+        .map(|(payload, state)| {
+            let message = message(Some(payload));
+            let message_id = message.id().0;
+            (message, metadata(message_id, Some(state), None))
+        })
+        .collect::<Vec<_>>();
+
+        let diff = MilestoneDiff::new(1, messages.iter().map(|(message, metadata)| (message, metadata))).unwrap();
+        assert_eq!(diff.milestone_index(), 1);
+        assert_eq!(diff.consumed().len(), 2);
+        assert_eq!(diff.consumed()[&spent], first_id);
+        assert_eq!(diff.consumed()[&OutputId::new(first_id, 1).unwrap()], second_id);
+        assert_eq!(diff.created().len(), 4);
+        for (output_id, address) in vec![
+            (OutputId::new(first_id, 0).unwrap(), [4; 32]),
+            (OutputId::new(first_id, 1).unwrap(), [5; 32]),
+            (OutputId::new(second_id, 0).unwrap(), [6; 32]),
+        ] {
+            let record = &diff.created()[&output_id];
+            assert_eq!(record.address(), Some(&Ed25519Address::new(address)));
+            assert_eq!(record.created_at, MilestoneIndex(1));
+        }
+        assert!(diff.created()[&OutputId::new(second_id, 0).unwrap()].is_dust_allowance());
+        // The migrated funds are created as outputs of the milestone itself
+        let mut transaction_id = [0; 32];
+        transaction_id.copy_from_slice(milestone_id.as_ref());
+        let receipt_output_id = OutputId::new(TransactionId::new(transaction_id), 0).unwrap();
+        let record = &diff.created()[&receipt_output_id];
+        assert_eq!(record.address(), Some(&Ed25519Address::new([4; 32])));
+        assert_eq!(record.amount(), 1_000_000);
+        assert_eq!(record.message_id, messages[3].0.id().0);
+    }
 }
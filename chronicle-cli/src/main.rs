@@ -331,7 +331,9 @@ async fn archive<'a>(matches: &ArgMatches<'a>) -> anyhow::Result<()> {
             if is_url {
                 panic!("URL imports are not currently supported!");
             }
-            let import_type = if subcommand.is_present("analytics") {
+            let import_type = if subcommand.is_present("snapshot") {
+                ImportType::Snapshot
+            } else if subcommand.is_present("analytics") {
                 ImportType::Analytics
             } else {
                 ImportType::All
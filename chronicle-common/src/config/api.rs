@@ -2,14 +2,172 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::*;
+use std::collections::HashSet;
+
+/// A permission which can be granted to an API key or bearer token
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Hash)]
+pub enum ApiScope {
+    /// Access to the routes of a keyspace
+    Keyspace(String),
+    /// Access to the `/metrics` and `/service` routes
+    Monitoring,
+}
+
+/// An API key and the scopes it grants
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct ApiKeyConfig {
+    /// The key, sent by clients as `Authorization: Bearer <key>`
+    pub key: String,
+    /// The scopes granted to the key
+    pub scopes: HashSet<ApiScope>,
+}
+
+/// Token bucket rate limit applied to every client of the API
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub struct RateLimitConfig {
+    /// The number of requests per second a client is allowed on average
+    pub requests_per_second: u32,
+    /// The number of requests a client is allowed in a single burst
+    pub burst: u32,
+}
+
 /// Configuration for the Chronicle API
-#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Clone)]
-pub struct ApiConfig {}
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct ApiConfig {
+    /// Hex encoded secret used to sign the paging cursors. A random secret is used if none is provided,
+    /// in which case cursors are invalidated by a restart.
+    #[serde(default)]
+    pub cursor_secret: Option<String>,
+    /// The API keys which are allowed to access the API
+    #[serde(default)]
+    pub api_keys: Vec<ApiKeyConfig>,
+    /// The secret used to verify HS256 signed JWT bearer tokens, which carry their scopes in a `scopes` claim
+    #[serde(default)]
+    pub jwt_secret: Option<String>,
+    /// The rate limit per client, which is identified by its valid API key or JWT, or else by its IP address
+    #[serde(default)]
+    pub rate_limit: Option<RateLimitConfig>,
+    /// The maximum page size of paged requests
+    #[serde(default = "default_max_page_size")]
+    pub max_page_size: usize,
+    /// The maximum number of partitions a single paged request may scan
+    #[serde(default = "default_max_partitions_per_request")]
+    pub max_partitions_per_request: usize,
+    /// The maximum number of queries a single address balance request may run to resolve its outputs
+    #[serde(default = "default_max_queries_per_balance")]
+    pub max_queries_per_balance: usize,
+    /// The number of confirmed messages and milestones kept in the response cache. Zero disables the cache.
+    #[serde(default = "default_cache_size")]
+    pub cache_size: usize,
+    /// The keyspace served under Hornet's `/api/v1` paths, if any
+    #[serde(default)]
+    pub hornet_keyspace: Option<String>,
+    /// The age (in seconds) of the latest synced milestone above which the instance is reported unhealthy
+    #[serde(default = "default_max_sync_lag_secs")]
+    pub max_sync_lag_secs: u64,
+}
+
+fn default_max_page_size() -> usize {
+    1000
+}
+
+fn default_max_partitions_per_request() -> usize {
+    100
+}
+
+fn default_max_queries_per_balance() -> usize {
+    2000
+}
+
+fn default_cache_size() -> usize {
+    10000
+}
+
+fn default_max_sync_lag_secs() -> u64 {
+    60
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            cursor_secret: None,
+            api_keys: Vec::new(),
+            jwt_secret: None,
+            rate_limit: None,
+            max_page_size: default_max_page_size(),
+            max_partitions_per_request: default_max_partitions_per_request(),
+            max_queries_per_balance: default_max_queries_per_balance(),
+            cache_size: default_cache_size(),
+            hornet_keyspace: None,
+            max_sync_lag_secs: default_max_sync_lag_secs(),
+        }
+    }
+}
 
 impl ApiConfig {
-    /// Verify that the api config is valid
-    pub async fn verify(&mut self) -> anyhow::Result<()> {
-        // TODO
+    /// Verify that the api config is valid against the configured keyspaces
+    pub async fn verify(&mut self, keyspaces: &[KeyspaceConfig]) -> anyhow::Result<()> {
+        if let Some(secret) = self.cursor_secret.as_ref() {
+            ensure!(
+                secret.len() % 2 == 0 && secret.chars().all(|c| c.is_ascii_hexdigit()),
+                "The cursor_secret must be hex encoded"
+            );
+            ensure!(
+                secret.len() >= 64,
+                "The cursor_secret must be at least 32 bytes long, found: {}",
+                secret.len() / 2
+            );
+        }
+        let mut keys = HashSet::new();
+        for (i, api_key) in self.api_keys.iter().enumerate() {
+            ensure!(!api_key.key.is_empty(), "API key {} is empty", i);
+            ensure!(keys.insert(&api_key.key), "API key {} is configured more than once", i);
+            for scope in api_key.scopes.iter() {
+                if let ApiScope::Keyspace(keyspace) = scope {
+                    ensure!(
+                        keyspaces.iter().any(|k| &k.name == keyspace),
+                        "API key {} is scoped to unknown keyspace: {}, ensure your config is correct",
+                        i,
+                        keyspace
+                    );
+                }
+            }
+        }
+        if let Some(secret) = self.jwt_secret.as_ref() {
+            ensure!(!secret.is_empty(), "The jwt_secret is empty");
+        }
+        if let Some(rate_limit) = self.rate_limit.as_ref() {
+            ensure!(
+                rate_limit.requests_per_second > 0 && rate_limit.burst > 0,
+                "The rate_limit must allow at least one request per second and burst"
+            );
+        }
+        ensure!(self.max_page_size > 0, "The max_page_size must be greater than zero");
+        ensure!(
+            self.max_partitions_per_request > 0,
+            "The max_partitions_per_request must be greater than zero"
+        );
+        ensure!(
+            self.max_queries_per_balance > 0,
+            "The max_queries_per_balance must be greater than zero"
+        );
+        if let Some(hornet_keyspace) = self.hornet_keyspace.as_ref() {
+            ensure!(
+                keyspaces.iter().any(|k| &k.name == hornet_keyspace),
+                "The hornet_keyspace is an unknown keyspace: {}, ensure your config is correct",
+                hornet_keyspace
+            );
+            // The `/api/v1` paths of the Hornet API would shadow the routes of such a keyspace
+            ensure!(
+                keyspaces.iter().all(|k| k.name != "v1"),
+                "A keyspace named v1 cannot be configured along with a hornet_keyspace"
+            );
+        }
         Ok(())
     }
+
+    /// Whether requests to the API must be authenticated
+    pub fn requires_auth(&self) -> bool {
+        !self.api_keys.is_empty() || self.jwt_secret.is_some()
+    }
 }
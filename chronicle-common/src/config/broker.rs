@@ -48,6 +48,66 @@ pub struct BrokerConfig {
     pub logs_dir: Option<String>,
     /// The maximum log file size
     pub max_log_size: Option<u64>,
+    /// Maintain the ledger state (unspent outputs and balances) of the confirmed milestones
+    #[serde(default)]
+    pub ledger: bool,
+    /// The coordinator used to verify the solidified milestones, the verification is skipped if None
+    #[serde(default)]
+    pub coordinator: Option<CoordinatorConfig>,
+}
+
+/// The coordinator config, which defines the keys that sign the milestones
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct CoordinatorConfig {
+    /// The number of signatures a milestone requires
+    pub public_key_count: usize,
+    /// The coordinator public keys along with the milestone index range they are valid for
+    pub public_key_ranges: Vec<PublicKeyRange>,
+}
+
+/// A coordinator public key, along with the milestone index range it is valid for
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct PublicKeyRange {
+    /// The hex encoded ed25519 public key
+    pub public_key: String,
+    /// The first milestone index the key is valid for
+    pub start: u32,
+    /// The last milestone index the key is valid for, 0 if the key has no end
+    pub end: u32,
+}
+
+impl CoordinatorConfig {
+    /// Get the public keys which are valid for the milestone index
+    pub fn public_keys(&self, milestone_index: u32) -> Vec<String> {
+        self.public_key_ranges
+            .iter()
+            .filter(|range| range.start <= milestone_index && (range.end == 0 || milestone_index <= range.end))
+            .map(|range| range.public_key.clone())
+            .collect()
+    }
+    /// Verify that the coordinator config is valid
+    pub fn verify(&self) -> anyhow::Result<()> {
+        if self.public_key_count == 0 {
+            bail!("Error verifying coordinator, zero public key count provided!");
+        }
+        for range in self.public_key_ranges.iter() {
+            if range.public_key.len() != 64 || !range.public_key.chars().all(|c| c.is_ascii_hexdigit()) {
+                bail!(
+                    "Error verifying coordinator public key {}, expected 32 hex encoded bytes",
+                    range.public_key
+                );
+            }
+            if range.end != 0 && range.start > range.end {
+                bail!(
+                    "Error verifying coordinator public key {}, start {} is greater than end {}",
+                    range.public_key,
+                    range.start,
+                    range.end
+                );
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Enumerated MQTT feed source type
@@ -89,6 +149,8 @@ impl Default for BrokerConfig {
             sync_range: Some(Default::default()),
             logs_dir: Some("chronicle/logs/".to_owned()),
             max_log_size: Some(4 * 1024 * 1024 * 1024),
+            ledger: false,
+            coordinator: None,
         }
     }
 }
@@ -121,6 +183,9 @@ impl BrokerConfig {
         } else if sync_range.from >= sync_range.to {
             bail!("Error verifying sync from/to, greater or equal provided!\nPlease provide lower \"Sync range from\" milestone index");
         }
+        if let Some(coordinator) = self.coordinator.as_ref() {
+            coordinator.verify()?;
+        }
         Ok(())
     }
     /// Adjust IOTA api endpoint url and ensure it's correct or return None otherwise
@@ -0,0 +1,114 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use std::ops::Range;
+
+/// The payload variants a filter rule can match on
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum PayloadType {
+    /// Messages without any payload
+    NoPayload,
+    /// Transaction payloads
+    Transaction,
+    /// Milestone payloads
+    Milestone,
+    /// Indexation payloads
+    Indexation,
+    /// Receipt payloads
+    Receipt,
+    /// Treasury transaction payloads
+    TreasuryTransaction,
+}
+
+/// What to do with the messages which match a filter rule
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum FilterAction {
+    /// Store the message records in the rule keyspace
+    Store,
+    /// Drop the message without storing any of its records
+    Drop,
+}
+
+impl Default for FilterAction {
+    fn default() -> Self {
+        FilterAction::Store
+    }
+}
+
+/// A single filter rule. A message matches a rule when it satisfies every condition
+/// which is set on the rule; unset conditions always match.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct FilterRule {
+    /// The payload type of the message
+    #[serde(default)]
+    pub payload_type: Option<PayloadType>,
+    /// Hex encoded prefix of the indexation index (including indexations nested in transactions)
+    #[serde(default)]
+    pub index_prefix: Option<String>,
+    /// Hex encoded ed25519 address which receives an output of the message's transaction
+    #[serde(default)]
+    pub address: Option<String>,
+    /// The range of milestone indexes (start inclusive, end exclusive) which reference the message
+    #[serde(default)]
+    pub milestone_range: Option<Range<u32>>,
+    /// What to do with matching messages
+    #[serde(default)]
+    pub action: FilterAction,
+    /// The keyspace in which matching messages are stored, unused by `Drop` rules
+    #[serde(default)]
+    pub keyspace: String,
+    /// The time-to-live (in seconds) of the records of matching messages
+    #[serde(default)]
+    pub ttl: Option<usize>,
+}
+
+/// Configuration for the message filter. Rules are evaluated in order
+/// and the first matching rule decides where a message is stored, or whether it is dropped.
+/// Messages which match no rule are stored in the default (first) keyspace.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct FilterConfig {
+    /// The ordered filter rules
+    pub rules: Vec<FilterRule>,
+}
+
+impl FilterConfig {
+    /// Get the keyspaces messages can be stored in: the default keyspace and the keyspaces of the store rules
+    pub fn keyspaces<'a>(&'a self, default_keyspace: &'a str) -> Vec<&'a str> {
+        let mut keyspaces = vec![default_keyspace];
+        for rule in self.rules.iter().filter(|rule| rule.action == FilterAction::Store) {
+            if !keyspaces.contains(&rule.keyspace.as_str()) {
+                keyspaces.push(&rule.keyspace);
+            }
+        }
+        keyspaces
+    }
+    /// Verify that the filter config is valid against the configured keyspaces
+    pub fn verify(&self, keyspaces: &[KeyspaceConfig]) -> anyhow::Result<()> {
+        for (i, rule) in self.rules.iter().enumerate() {
+            if rule.action == FilterAction::Store && !keyspaces.iter().any(|k| k.name == rule.keyspace) {
+                bail!(
+                    "Filter rule {} targets unknown keyspace: {}, ensure your config is correct",
+                    i,
+                    rule.keyspace
+                );
+            }
+            if let Some(prefix) = rule.index_prefix.as_ref() {
+                if !prefix.chars().all(|c| c.is_ascii_hexdigit()) {
+                    bail!("Filter rule {} has a non-hex index_prefix: {}", i, prefix);
+                }
+            }
+            if let Some(address) = rule.address.as_ref() {
+                if address.len() != 64 || !address.chars().all(|c| c.is_ascii_hexdigit()) {
+                    bail!("Filter rule {} has an invalid ed25519 address: {}", i, address);
+                }
+            }
+            if let Some(range) = rule.milestone_range.as_ref() {
+                if range.start >= range.end {
+                    bail!("Filter rule {} has an empty milestone_range: {:?}", i, range);
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -9,6 +9,7 @@ use anyhow::{
 };
 pub use api::*;
 pub use broker::*;
+pub use filter::*;
 use maplit::{
     hashmap,
     hashset,
@@ -25,6 +26,7 @@ pub use storage::*;
 
 mod api;
 mod broker;
+mod filter;
 mod storage;
 
 /// The default config file path
@@ -33,7 +35,7 @@ pub const CONFIG_PATH: &str = "./config.ron";
 pub const HISTORICAL_CONFIG_PATH: &str = "./historical_config";
 /// The current config version.
 /// **Must be updated with each change to the config format.**
-const CURRENT_VERSION: u32 = 2;
+const CURRENT_VERSION: u32 = 3;
 
 /// Versioned config. Tracks version between config changes so that it can be validated on load.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
@@ -210,6 +212,9 @@ pub struct Config {
     pub api_config: ApiConfig,
     /// Broker configuration
     pub broker_config: BrokerConfig,
+    /// Message filter configuration
+    #[serde(default)]
+    pub filter_config: FilterConfig,
     /// Historical config file path
     pub historical_config_path: String,
 }
@@ -221,6 +226,7 @@ impl Default for Config {
             storage_config: Default::default(),
             api_config: Default::default(),
             broker_config: Default::default(),
+            filter_config: Default::default(),
             historical_config_path: HISTORICAL_CONFIG_PATH.to_owned(),
         }
     }
@@ -248,8 +254,9 @@ impl Config {
     /// Verify this config
     pub async fn verify(mut self) -> anyhow::Result<Self> {
         self.storage_config.verify().await?;
-        self.api_config.verify().await?;
+        self.api_config.verify(&self.storage_config.keyspaces).await?;
         self.broker_config.verify().await?;
+        self.filter_config.verify(&self.storage_config.keyspaces)?;
         Ok(self)
     }
 }
@@ -285,6 +292,9 @@ mod test {
                             replication_factor: 1,
                         },
                     },
+                    ttl: None,
+                    bech32_hrp: "atoi".to_string(),
+                    network_id: "testnet7".to_string(),
                 }],
                 listen_address: ([127, 0, 0, 1], 8080).into(),
                 thread_count: ThreadCount::CoreMultiple(1),
@@ -293,7 +303,18 @@ mod test {
                 nodes: hashset![([127, 0, 0, 1], 9042).into()],
                 partition_config: PartitionConfig::default(),
             },
-            api_config: ApiConfig {},
+            api_config: ApiConfig {
+                cursor_secret: None,
+                api_keys: Vec::new(),
+                jwt_secret: None,
+                rate_limit: None,
+                max_page_size: 1000,
+                max_partitions_per_request: 100,
+                max_queries_per_balance: 2000,
+                cache_size: 10000,
+                hornet_keyspace: None,
+                max_sync_lag_secs: 60,
+            },
             broker_config: BrokerConfig {
                 collector_count: 10,
                 requester_count: 10,
@@ -322,6 +343,19 @@ mod test {
                 sync_range: Some(SyncRange::default()),
                 logs_dir: Some("chronicle/logs/".to_owned()),
                 max_log_size: Some(4294967296),
+                ledger: false,
+                coordinator: None,
+            },
+            filter_config: FilterConfig {
+                rules: vec![FilterRule {
+                    payload_type: Some(PayloadType::Indexation),
+                    index_prefix: Some("4368726f6e69636c65".to_owned()),
+                    address: None,
+                    milestone_range: None,
+                    action: FilterAction::Store,
+                    keyspace: "permanode".to_owned(),
+                    ttl: Some(2592000),
+                }],
             },
             historical_config_path: HISTORICAL_CONFIG_PATH.to_owned(),
         };
@@ -338,4 +372,68 @@ mod test {
 
         assert_eq!(config, deserialized_config);
     }
+
+    #[test]
+    pub fn filter_keyspaces() {
+        let rule = |action, keyspace: &str| FilterRule {
+            payload_type: None,
+            index_prefix: None,
+            address: None,
+            milestone_range: None,
+            action,
+            keyspace: keyspace.to_owned(),
+            ttl: None,
+        };
+        let filter_config = FilterConfig {
+            rules: vec![
+                rule(FilterAction::Store, "indexations"),
+                rule(FilterAction::Drop, ""),
+                rule(FilterAction::Store, "permanode"),
+                rule(FilterAction::Store, "indexations"),
+                rule(FilterAction::Store, "transactions"),
+            ],
+        };
+        assert_eq!(
+            filter_config.keyspaces("permanode"),
+            vec!["permanode", "indexations", "transactions"]
+        );
+        assert_eq!(FilterConfig::default().keyspaces("permanode"), vec!["permanode"]);
+    }
+
+    #[test]
+    pub fn coordinator_public_keys() {
+        let range = |public_key: &str, start, end| PublicKeyRange {
+            public_key: public_key.repeat(64),
+            start,
+            end,
+        };
+        let coordinator = CoordinatorConfig {
+            public_key_count: 2,
+            public_key_ranges: vec![range("a", 1, 10), range("b", 5, 0), range("c", 11, 20)],
+        };
+        assert!(coordinator.verify().is_ok());
+        assert!(coordinator.public_keys(0).is_empty());
+        assert_eq!(coordinator.public_keys(1), vec!["a".repeat(64)]);
+        // The end of a range is inclusive, and a zero end never ends
+        assert_eq!(coordinator.public_keys(10), vec!["a".repeat(64), "b".repeat(64)]);
+        assert_eq!(coordinator.public_keys(11), vec!["b".repeat(64), "c".repeat(64)]);
+        assert_eq!(coordinator.public_keys(u32::MAX), vec!["b".repeat(64)]);
+
+        // A range can't start after it ends, and a key must be hex encoded
+        let mut invalid = coordinator.clone();
+        invalid.public_key_ranges.push(range("d", 20, 10));
+        assert!(invalid.verify().is_err());
+        let mut invalid = coordinator.clone();
+        invalid.public_key_ranges.push(range("g", 1, 0));
+        assert!(invalid.verify().is_err());
+        let mut invalid = coordinator.clone();
+        invalid.public_key_count = 0;
+        assert!(invalid.verify().is_err());
+        // A range which starts and ends at the same milestone holds a single milestone
+        let mut single = coordinator;
+        single.public_key_ranges = vec![range("e", 7, 7)];
+        assert!(single.verify().is_ok());
+        assert_eq!(single.public_keys(7), vec!["e".repeat(64)]);
+        assert!(single.public_keys(8).is_empty());
+    }
 }
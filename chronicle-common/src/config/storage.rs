@@ -95,6 +95,24 @@ pub struct KeyspaceConfig {
     pub name: KeyspaceName,
     /// Datacenters configured for this keyspace, keyed by name
     pub data_centers: HashMap<DatacenterName, DatacenterConfig>,
+    /// The default time-to-live (in seconds) of the records stored in this keyspace,
+    /// records never expire if not provided
+    #[serde(default)]
+    pub ttl: Option<u32>,
+    /// The bech32 human readable part of the addresses of the network stored in this keyspace
+    #[serde(default = "default_bech32_hrp")]
+    pub bech32_hrp: String,
+    /// The id of the network stored in this keyspace
+    #[serde(default = "default_network_id")]
+    pub network_id: String,
+}
+
+fn default_bech32_hrp() -> String {
+    "iota".to_string()
+}
+
+fn default_network_id() -> String {
+    "chrysalis-mainnet".to_string()
 }
 
 impl Default for KeyspaceConfig {
@@ -109,10 +127,22 @@ impl Default for KeyspaceConfig {
                     replication_factor: 1,
                 },
             },
+            ttl: None,
+            bech32_hrp: default_bech32_hrp(),
+            network_id: default_network_id(),
         }
     }
 }
 impl StorageConfig {
+    /// Get the config of a keyspace by its name, if it is configured
+    pub fn keyspace_config(&self, keyspace_name: &str) -> Option<&KeyspaceConfig> {
+        self.keyspaces.iter().find(|keyspace| keyspace.name == keyspace_name)
+    }
+    /// Get the default time-to-live of a keyspace, if any
+    pub fn keyspace_ttl(&self, keyspace_name: &str) -> Option<u32> {
+        self.keyspace_config(keyspace_name).and_then(|keyspace| keyspace.ttl)
+    }
+
     /// Try to get the uniform replication factor, which is the lowest rf in all keyspace across all dc
     pub fn try_get_uniform_rf(&self) -> Option<u8> {
         // collect all data_centers from all keyspaces
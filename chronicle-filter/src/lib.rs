@@ -1,6 +1,20 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
-use bee_message::Message;
+use bee_message::{
+    address::Address,
+    output::Output,
+    payload::{
+        transaction::Essence,
+        Payload,
+    },
+    Message,
+};
+use chronicle_common::config::{
+    FilterAction,
+    FilterConfig,
+    FilterRule,
+    PayloadType,
+};
 use std::borrow::Cow;
 
 pub struct FilterResponse {
@@ -10,7 +24,232 @@ pub struct FilterResponse {
     pub ttl: Option<usize>,
 }
 
-#[allow(unused_variables)]
-pub async fn filter_messages(message: &mut Message) -> FilterResponse {
-    todo!()
+/// Filter a message against the provided filter config, which is loaded once by the caller. The milestone index
+/// is the index of the milestone which references the message (if known yet). The first matching rule decides the
+/// keyspace and ttl, or drops the message in which case None is returned; messages which match no rule are stored
+/// in the default keyspace without ttl.
+pub fn filter_with(
+    filter_config: &FilterConfig,
+    default_keyspace: &str,
+    message: &Message,
+    milestone_index: Option<u32>,
+) -> Option<FilterResponse> {
+    match filter_config
+        .rules
+        .iter()
+        .find(|rule| matches(rule, message, milestone_index))
+    {
+        Some(rule) if rule.action == FilterAction::Drop => None,
+        Some(rule) => Some(FilterResponse {
+            keyspace: rule.keyspace.clone().into(),
+            ttl: rule.ttl,
+        }),
+        None => Some(FilterResponse {
+            keyspace: default_keyspace.to_owned().into(),
+            ttl: None,
+        }),
+    }
+}
+
+fn matches(rule: &FilterRule, message: &Message, milestone_index: Option<u32>) -> bool {
+    let payload = message.payload().as_ref();
+    if let Some(payload_type) = rule.payload_type {
+        if payload_type != payload_type_of(payload) {
+            return false;
+        }
+    }
+    if let Some(prefix) = rule.index_prefix.as_ref() {
+        match payload.and_then(index_of) {
+            Some(index) if hex::encode(index).starts_with(&prefix.to_lowercase()) => (),
+            _ => return false,
+        }
+    }
+    if let Some(address) = rule.address.as_ref() {
+        if !payload.map(|p| sends_to(p, &address.to_lowercase())).unwrap_or(false) {
+            return false;
+        }
+    }
+    if let Some(range) = rule.milestone_range.as_ref() {
+        // milestone messages are known to be referenced by the milestone they carry
+        let milestone_index = milestone_index.or_else(|| match payload {
+            Some(Payload::Milestone(milestone)) => Some(*milestone.essence().index()),
+            _ => None,
+        });
+        if !milestone_index.map(|index| range.contains(&index)).unwrap_or(false) {
+            return false;
+        }
+    }
+    true
+}
+
+fn payload_type_of(payload: Option<&Payload>) -> PayloadType {
+    match payload {
+        None => PayloadType::NoPayload,
+        Some(Payload::Transaction(_)) => PayloadType::Transaction,
+        Some(Payload::Milestone(_)) => PayloadType::Milestone,
+        Some(Payload::Indexation(_)) => PayloadType::Indexation,
+        Some(Payload::Receipt(_)) => PayloadType::Receipt,
+        Some(Payload::TreasuryTransaction(_)) => PayloadType::TreasuryTransaction,
+    }
+}
+
+/// Get the indexation index of a payload, looking into the transaction essence if needed
+fn index_of(payload: &Payload) -> Option<&[u8]> {
+    match payload {
+        Payload::Indexation(indexation) => Some(indexation.index()),
+        Payload::Transaction(transaction) => {
+            let Essence::Regular(regular) = transaction.essence();
+            regular.payload().as_ref().and_then(index_of)
+        }
+        _ => None,
+    }
+}
+
+/// Check if any output of a transaction payload is locked to the given hex encoded ed25519 address
+fn sends_to(payload: &Payload, address: &str) -> bool {
+    if let Payload::Transaction(transaction) = payload {
+        let Essence::Regular(regular) = transaction.essence();
+        regular.outputs().iter().any(|output| {
+            let Address::Ed25519(ed_address) = match output {
+                Output::SignatureLockedSingle(sls) => sls.address(),
+                Output::SignatureLockedDustAllowance(slda) => slda.address(),
+                _ => return false,
+            };
+            ed_address.to_string() == address
+        })
+    } else {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bee_message::prelude::*;
+    use bee_pow::providers::miner::Miner;
+
+    const ADDRESS: [u8; 32] = [7; 32];
+
+    fn message(payload: Option<Payload>) -> Message {
+        let builder = MessageBuilder::<Miner>::new()
+            .with_network_id(0)
+            .with_parents(Parents::new(vec![MessageId::new([1; 32]), MessageId::new([2; 32])]).unwrap());
+        match payload {
+            Some(payload) => builder.with_payload(payload),
+            None => builder,
+        }
+        .finish()
+        .unwrap()
+    }
+
+    fn indexation(index: &[u8]) -> Payload {
+        Payload::Indexation(Box::new(IndexationPayload::new(index, &[]).unwrap()))
+    }
+
+    fn transaction(address: [u8; 32], payload: Option<Payload>) -> Payload {
+        let input = Input::Utxo(UtxoInput::new(TransactionId::new([0; 32]), 0).unwrap());
+        let output = Output::SignatureLockedSingle(
+            SignatureLockedSingleOutput::new(Address::Ed25519(Ed25519Address::new(address)), 1_000_000).unwrap(),
+        );
+        let mut essence = RegularEssenceBuilder::new()
+            .with_inputs(vec![input])
+            .with_outputs(vec![output]);
+        if let Some(payload) = payload {
+            essence = essence.with_payload(payload);
+        }
+        let signature = Ed25519Signature::new([0; 32], Box::new([0; 64]));
+        let unlock_blocks =
+            UnlockBlocks::new(vec![UnlockBlock::Signature(SignatureUnlock::Ed25519(signature))]).unwrap();
+        Payload::Transaction(Box::new(
+            TransactionPayloadBuilder::new()
+                .with_essence(Essence::Regular(essence.finish().unwrap()))
+                .with_unlock_blocks(unlock_blocks)
+                .finish()
+                .unwrap(),
+        ))
+    }
+
+    fn rule(keyspace: &str) -> FilterRule {
+        FilterRule {
+            payload_type: None,
+            index_prefix: None,
+            address: None,
+            milestone_range: None,
+            action: FilterAction::Store,
+            keyspace: keyspace.to_owned(),
+            ttl: None,
+        }
+    }
+
+    #[test]
+    fn unset_conditions_match_every_message() {
+        let rule = rule("all");
+        assert!(matches(&rule, &message(None), None));
+        assert!(matches(&rule, &message(Some(indexation(b"Chronicle"))), Some(1)));
+    }
+
+    #[test]
+    fn matches_payload_type() {
+        let mut rule = rule("indexations");
+        rule.payload_type = Some(PayloadType::Indexation);
+        assert!(matches(&rule, &message(Some(indexation(b"Chronicle"))), None));
+        assert!(!matches(&rule, &message(None), None));
+        rule.payload_type = Some(PayloadType::NoPayload);
+        assert!(matches(&rule, &message(None), None));
+    }
+
+    #[test]
+    fn matches_index_prefix() {
+        let mut rule = rule("dapp");
+        // "Chron" in upper case hex
+        rule.index_prefix = Some("4368726F6E".to_owned());
+        assert!(matches(&rule, &message(Some(indexation(b"Chronicle"))), None));
+        assert!(!matches(&rule, &message(Some(indexation(b"Hornet"))), None));
+        assert!(!matches(&rule, &message(None), None));
+        // indexations nested in transactions are matched as well
+        let nested = transaction(ADDRESS, Some(indexation(b"Chronicle")));
+        assert!(matches(&rule, &message(Some(nested)), None));
+    }
+
+    #[test]
+    fn matches_address() {
+        let mut rule = rule("wallet");
+        rule.address = Some(hex::encode(ADDRESS).to_uppercase());
+        assert!(matches(&rule, &message(Some(transaction(ADDRESS, None))), None));
+        assert!(!matches(&rule, &message(Some(transaction([8; 32], None))), None));
+        assert!(!matches(&rule, &message(Some(indexation(b"Chronicle"))), None));
+    }
+
+    #[test]
+    fn matches_milestone_range() {
+        let mut rule = rule("recent");
+        rule.milestone_range = Some(10..20);
+        let message = message(Some(indexation(b"Chronicle")));
+        assert!(matches(&rule, &message, Some(10)));
+        assert!(matches(&rule, &message, Some(19)));
+        assert!(!matches(&rule, &message, Some(20)));
+        assert!(!matches(&rule, &message, Some(9)));
+        // the referencing milestone isn't known yet
+        assert!(!matches(&rule, &message, None));
+    }
+
+    #[test]
+    fn first_matching_rule_decides() {
+        let mut spam = rule("");
+        spam.index_prefix = Some(hex::encode(b"spam"));
+        spam.action = FilterAction::Drop;
+        let mut dapp = rule("dapp");
+        dapp.payload_type = Some(PayloadType::Indexation);
+        dapp.ttl = Some(60);
+        let filter_config = FilterConfig {
+            rules: vec![spam, dapp],
+        };
+        assert!(filter_with(&filter_config, "permanode", &message(Some(indexation(b"spam"))), None).is_none());
+        let response = filter_with(&filter_config, "permanode", &message(Some(indexation(b"dapp"))), None).unwrap();
+        assert_eq!(response.keyspace, "dapp");
+        assert_eq!(response.ttl, Some(60));
+        let response = filter_with(&filter_config, "permanode", &message(None), None).unwrap();
+        assert_eq!(response.keyspace, "permanode");
+        assert_eq!(response.ttl, None);
+    }
 }
@@ -168,3 +168,189 @@ impl Delete<ParentPK, ParentRecord> for ChronicleKeyspace {
             .value(&message_id.to_string())
     }
 }
+
+/// Delete a spent output from the ledger
+impl Delete<OutputId, LedgerOutputRecord> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "DELETE FROM {}.ledger_outputs WHERE transaction_id = ? AND idx = ?",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(builder: T, output_id: &OutputId) -> T::Return {
+        builder
+            .value(&output_id.transaction_id().to_string())
+            .value(&output_id.index())
+    }
+}
+
+/// A representation of the primary key for the `milestones` table
+#[derive(Clone)]
+pub struct MilestonePK {
+    pub(crate) milestone_index: MilestoneIndex,
+    pub(crate) message_id: MessageId,
+}
+impl MilestonePK {
+    /// Creates a new milestones primary key
+    pub fn new(milestone_index: MilestoneIndex, message_id: MessageId) -> Self {
+        Self {
+            milestone_index,
+            message_id,
+        }
+    }
+}
+
+/// Delete Milestone record from Milestones table
+impl Delete<MilestonePK, (MessageId, Box<MilestonePayload>)> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "DELETE FROM {}.milestones WHERE milestone_index = ? AND message_id = ?",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(
+        builder: T,
+        MilestonePK {
+            milestone_index,
+            message_id,
+        }: &MilestonePK,
+    ) -> T::Return {
+        builder.value(&milestone_index.0).value(&message_id.to_string())
+    }
+}
+
+/// A representation of the primary key for the `milestones_by_time` table
+#[derive(Clone)]
+pub struct MilestoneTimestampPK {
+    pub(crate) timestamp: MilestoneTimestamp,
+    pub(crate) milestone_index: MilestoneIndex,
+}
+impl MilestoneTimestampPK {
+    /// Creates a new milestones_by_time primary key
+    pub fn new(timestamp: MilestoneTimestamp, milestone_index: MilestoneIndex) -> Self {
+        Self {
+            timestamp,
+            milestone_index,
+        }
+    }
+}
+
+/// Delete Milestone record from MilestonesByTime table
+impl Delete<MilestoneTimestampPK, (MilestoneIndex, MessageId)> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "DELETE FROM {}.milestones_by_time WHERE bucket = ? AND timestamp = ? AND milestone_index = ?",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(
+        builder: T,
+        MilestoneTimestampPK {
+            timestamp,
+            milestone_index,
+        }: &MilestoneTimestampPK,
+    ) -> T::Return {
+        builder
+            .value(&timestamp.bucket())
+            .value(&timestamp.0)
+            .value(&milestone_index.0)
+    }
+}
+
+/// A representation of the primary key for the `receipts` table
+#[derive(Clone)]
+pub struct ReceiptPK {
+    pub(crate) migrated_at: MilestoneIndex,
+    pub(crate) milestone_index: MilestoneIndex,
+}
+impl ReceiptPK {
+    /// Creates a new receipts primary key
+    pub fn new(migrated_at: MilestoneIndex, milestone_index: MilestoneIndex) -> Self {
+        Self {
+            migrated_at,
+            milestone_index,
+        }
+    }
+}
+
+/// Delete Receipt record from Receipts table
+impl Delete<ReceiptPK, ReceiptRecord> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "DELETE FROM {}.receipts WHERE key = ? AND migrated_at = ? AND milestone_index = ?",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(
+        builder: T,
+        ReceiptPK {
+            migrated_at,
+            milestone_index,
+        }: &ReceiptPK,
+    ) -> T::Return {
+        builder
+            .value(&"permanode")
+            .value(&migrated_at.0)
+            .value(&milestone_index.0)
+    }
+}
+
+/// A representation of the primary key for the `receipts_by_tail_transaction_hash` table
+#[derive(Clone)]
+pub struct MigratedFundsPK {
+    pub(crate) tail_transaction_hash: LegacyTailTransactionHash,
+    pub(crate) milestone_index: MilestoneIndex,
+}
+impl MigratedFundsPK {
+    /// Creates a new receipts_by_tail_transaction_hash primary key
+    pub fn new(tail_transaction_hash: LegacyTailTransactionHash, milestone_index: MilestoneIndex) -> Self {
+        Self {
+            tail_transaction_hash,
+            milestone_index,
+        }
+    }
+}
+
+/// Delete MigratedFunds record from ReceiptsByTailTransactionHash table
+impl Delete<MigratedFundsPK, MigratedFundsRecord> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "DELETE FROM {}.receipts_by_tail_transaction_hash WHERE tail_transaction_hash = ? AND milestone_index = ?",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(
+        builder: T,
+        MigratedFundsPK {
+            tail_transaction_hash,
+            milestone_index,
+        }: &MigratedFundsPK,
+    ) -> T::Return {
+        builder.value(&tail_transaction_hash.0).value(&milestone_index.0)
+    }
+}
+
+/// Delete the treasury output created by a milestone
+impl Delete<MilestoneIndex, TreasuryRecord> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "DELETE FROM {}.treasury WHERE key = ? AND milestone_index = ?",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(builder: T, milestone_index: &MilestoneIndex) -> T::Return {
+        builder.value(&"permanode").value(&milestone_index.0)
+    }
+}
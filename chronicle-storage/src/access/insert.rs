@@ -3,6 +3,21 @@
 
 use super::*;
 
+/// Insert any record with a time-to-live (in seconds).
+/// Note: a time-to-live of zero means the record never expires.
+impl<K, V> Insert<K, TTL<V>> for ChronicleKeyspace
+where
+    ChronicleKeyspace: Insert<K, V>,
+{
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!("{} USING TTL ?", <Self as Insert<K, V>>::statement(self)).into()
+    }
+    fn bind_values<T: Values>(builder: T, key: &K, TTL { inner, ttl }: &TTL<V>) -> T::Return {
+        <Self as Insert<K, V>>::bind_values(builder, key, inner).value(ttl)
+    }
+}
+
 impl Insert<MessageId, Message> for ChronicleKeyspace {
     type QueryOrPrepared = PreparedStatement;
     fn statement(&self) -> std::borrow::Cow<'static, str> {
@@ -265,6 +280,49 @@ impl Insert<MilestoneIndex, (MessageId, Box<MilestonePayload>)> for ChronicleKey
     }
 }
 
+impl Insert<Partitioned<MilestoneIndex>, MessageId> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "INSERT INTO {}.milestone_messages (milestone_index, partition_id, message_id) VALUES (?, ?, ?)",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(
+        builder: T,
+        milestone_index: &Partitioned<MilestoneIndex>,
+        message_id: &MessageId,
+    ) -> T::Return {
+        builder
+            .value(&milestone_index.0)
+            .value(&milestone_index.partition_id())
+            .value(&message_id.to_string())
+    }
+}
+
+impl Insert<MilestoneTimestamp, (MilestoneIndex, MessageId)> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "INSERT INTO {}.milestones_by_time (bucket, timestamp, milestone_index, message_id) VALUES (?, ?, ?, ?)",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(
+        builder: T,
+        timestamp: &MilestoneTimestamp,
+        (milestone_index, message_id): &(MilestoneIndex, MessageId),
+    ) -> T::Return {
+        builder
+            .value(&timestamp.bucket())
+            .value(&timestamp.0)
+            .value(&milestone_index.0)
+            .value(&message_id.to_string())
+    }
+}
+
 impl Insert<Synckey, SyncRecord> for ChronicleKeyspace {
     type QueryOrPrepared = PreparedStatement;
     fn statement(&self) -> std::borrow::Cow<'static, str> {
@@ -318,3 +376,254 @@ impl Insert<Synckey, AnalyticRecord> for ChronicleKeyspace {
             .value(&transferred_tokens.0)
     }
 }
+
+impl Insert<Synckey, ExtendedAnalyticRecord> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "INSERT INTO {}.extended_analytics (key, milestone_index, indexation_payloads, transaction_payloads, milestone_payloads, receipt_payloads, input_utxo_already_spent, input_utxo_already_spent_in_this_milestone, input_utxo_not_found, input_output_sum_mismatch, invalid_signature, invalid_dust_allowance, semantic_validation_failed, unknown_conflicts, sending_addresses, receiving_addresses, dust_allowance_outputs) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(builder: T, _: &Synckey, record: &ExtendedAnalyticRecord) -> T::Return {
+        let conflicts = &record.conflicts;
+        builder
+            .value(&"permanode")
+            .value(&record.milestone_index.0)
+            .value(&record.indexation_payloads)
+            .value(&record.transaction_payloads)
+            .value(&record.milestone_payloads)
+            .value(&record.receipt_payloads)
+            .value(&conflicts.input_utxo_already_spent)
+            .value(&conflicts.input_utxo_already_spent_in_this_milestone)
+            .value(&conflicts.input_utxo_not_found)
+            .value(&conflicts.input_output_sum_mismatch)
+            .value(&conflicts.invalid_signature)
+            .value(&conflicts.invalid_dust_allowance)
+            .value(&conflicts.semantic_validation_failed)
+            .value(&conflicts.unknown)
+            .value(&record.sending_addresses)
+            .value(&record.receiving_addresses)
+            .value(&record.dust_allowance_outputs)
+    }
+}
+
+impl Insert<OutputId, SpentRecord> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "INSERT INTO {}.spent_outputs (transaction_id, idx, spent_by_transaction_id, spent_by_message_id, milestone_index) VALUES (?, ?, ?, ?, ?)",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(
+        builder: T,
+        output_id: &OutputId,
+        SpentRecord {
+            transaction_id,
+            message_id,
+            milestone_index,
+        }: &SpentRecord,
+    ) -> T::Return {
+        builder
+            .value(&output_id.transaction_id().to_string())
+            .value(&output_id.index())
+            .value(&transaction_id.to_string())
+            .value(&message_id.to_string())
+            .value(&milestone_index.0)
+    }
+}
+
+impl Insert<Synckey, ReceiptRecord> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "INSERT INTO {}.receipts (key, migrated_at, milestone_index, payload) VALUES (?, ?, ?, ?)",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(
+        builder: T,
+        _: &Synckey,
+        ReceiptRecord {
+            milestone_index,
+            receipt,
+        }: &ReceiptRecord,
+    ) -> T::Return {
+        let mut receipt_bytes = Vec::new();
+        receipt
+            .pack(&mut receipt_bytes)
+            .expect("Error occurred packing ReceiptPayload");
+        builder
+            .value(&"permanode")
+            .value(&receipt.migrated_at().0)
+            .value(&milestone_index.0)
+            .value(&receipt_bytes.as_slice())
+    }
+}
+
+impl Insert<LegacyTailTransactionHash, MigratedFundsRecord> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "INSERT INTO {}.receipts_by_tail_transaction_hash (tail_transaction_hash, milestone_index, migrated_at, output) VALUES (?, ?, ?, ?)",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(
+        builder: T,
+        tail_transaction_hash: &LegacyTailTransactionHash,
+        MigratedFundsRecord {
+            milestone_index,
+            migrated_at,
+            output,
+        }: &MigratedFundsRecord,
+    ) -> T::Return {
+        let mut output_bytes = Vec::new();
+        output
+            .pack(&mut output_bytes)
+            .expect("Error occurred packing SignatureLockedSingleOutput");
+        builder
+            .value(&tail_transaction_hash.0)
+            .value(&milestone_index.0)
+            .value(&migrated_at.0)
+            .value(&output_bytes.as_slice())
+    }
+}
+
+impl Insert<Synckey, TreasuryRecord> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "INSERT INTO {}.treasury (key, milestone_index, milestone_id, amount) VALUES (?, ?, ?, ?)",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(
+        builder: T,
+        _: &Synckey,
+        TreasuryRecord {
+            milestone_index,
+            milestone_id,
+            amount,
+        }: &TreasuryRecord,
+    ) -> T::Return {
+        builder
+            .value(&"permanode")
+            .value(&milestone_index.0)
+            .value(&milestone_id.to_string())
+            .value(amount)
+    }
+}
+
+impl Insert<OutputId, LedgerOutputRecord> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "INSERT INTO {}.ledger_outputs (transaction_id, idx, message_id, address, amount, output, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(builder: T, output_id: &OutputId, record: &LedgerOutputRecord) -> T::Return {
+        let mut output_bytes = Vec::new();
+        record
+            .output
+            .pack(&mut output_bytes)
+            .expect("Error occurred packing Output");
+        builder
+            .value(&output_id.transaction_id().to_string())
+            .value(&output_id.index())
+            .value(&record.message_id.to_string())
+            .value(&record.address().map(|address| address.to_string()))
+            .value(&record.amount())
+            .value(&output_bytes.as_slice())
+            .value(&record.created_at.0)
+    }
+}
+
+impl Insert<Ed25519Address, BalanceRecord> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "INSERT INTO {}.ledger_balances (address, milestone_index, balance, dust_allowance, output_count, dust_output_count) VALUES (?, ?, ?, ?, ?, ?)",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(
+        builder: T,
+        address: &Ed25519Address,
+        BalanceRecord {
+            milestone_index,
+            balance,
+            dust_allowance,
+            output_count,
+            dust_output_count,
+        }: &BalanceRecord,
+    ) -> T::Return {
+        builder
+            .value(&address.to_string())
+            .value(&milestone_index.0)
+            .value(balance)
+            .value(dust_allowance)
+            .value(output_count)
+            .value(dust_output_count)
+    }
+}
+
+impl Insert<Synckey, LedgerCheckpoint> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "INSERT INTO {}.ledger_checkpoint (key, milestone_index, consuming) VALUES (?, ?, ?)",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(
+        builder: T,
+        _: &Synckey,
+        LedgerCheckpoint {
+            milestone_index,
+            consuming,
+        }: &LedgerCheckpoint,
+    ) -> T::Return {
+        builder.value(&"permanode").value(milestone_index).value(consuming)
+    }
+}
+
+impl Insert<Synckey, QuarantinedMilestoneRecord> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "INSERT INTO {}.quarantined_milestones (key, milestone_index, payload, reason) VALUES (?, ?, ?, ?)",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(
+        builder: T,
+        _: &Synckey,
+        QuarantinedMilestoneRecord {
+            milestone_index,
+            milestone,
+            reason,
+        }: &QuarantinedMilestoneRecord,
+    ) -> T::Return {
+        let mut milestone_bytes = Vec::new();
+        milestone
+            .pack(&mut milestone_bytes)
+            .expect("Error occurred packing MilestonePayload");
+        builder
+            .value(&"permanode")
+            .value(&milestone_index.0)
+            .value(&milestone_bytes.as_slice())
+            .value(reason)
+    }
+}
@@ -30,7 +30,11 @@ use chronicle_common::{
 pub use delete::{
     Ed25519AddressPK,
     IndexationPK,
+    MigratedFundsPK,
+    MilestonePK,
+    MilestoneTimestampPK,
     ParentPK,
+    ReceiptPK,
 };
 use scylla_rs::{
     cql::{
@@ -157,6 +161,7 @@ impl<T> Partitioned<T> {
 pub const MAX_TTL: u32 = 20 * 365 * 24 * 60 * 60;
 
 /// A time-to-live specifier
+#[derive(Clone)]
 pub struct TTL<T> {
     inner: T,
     ttl: u32,
@@ -2,6 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::*;
+use bee_message::prelude::{
+    MilestoneId,
+    ReceiptPayload,
+    SignatureLockedSingleOutput,
+};
 use chronicle_common::SyncRange;
 use std::{
     collections::{
@@ -82,6 +87,29 @@ impl RowsDecoder<MessageId, (Option<Message>, Option<MessageMetadata>)> for Chro
     }
 }
 
+/// Selects the stored bytes of a message, without unpacking them, along with its metadata
+impl Select<MessageId, (Option<RawMessage>, Option<MessageMetadata>)> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "SELECT message, metadata FROM {}.messages WHERE message_id = ?",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(builder: T, message_id: &MessageId) -> T::Return {
+        builder.value(&message_id.to_string())
+    }
+}
+
+impl RowsDecoder<MessageId, (Option<RawMessage>, Option<MessageMetadata>)> for ChronicleKeyspace {
+    type Row = Record<(Option<RawMessage>, Option<MessageMetadata>)>;
+    fn try_decode(decoder: Decoder) -> anyhow::Result<Option<(Option<RawMessage>, Option<MessageMetadata>)>> {
+        ensure!(decoder.is_rows()?, "Decoded response is not rows!");
+        Ok(Self::Row::rows_iter(decoder)?.next().map(|row| row.into_inner()))
+    }
+}
+
 impl Select<Partitioned<MessageId>, Paged<VecDeque<Partitioned<ParentRecord>>>> for ChronicleKeyspace {
     type QueryOrPrepared = PreparedStatement;
     fn statement(&self) -> std::borrow::Cow<'static, str> {
@@ -305,6 +333,32 @@ impl RowsDecoder<TransactionId, MessageId> for ChronicleKeyspace {
     }
 }
 
+impl Select<TransactionId, Vec<(Index, TransactionRecord)>> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "SELECT idx, variant, message_id, data, inclusion_state, milestone_index
+            FROM {}.transactions
+            WHERE transaction_id = ?",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(builder: T, transaction_id: &TransactionId) -> T::Return {
+        builder.value(&transaction_id.to_string())
+    }
+}
+
+impl RowsDecoder<TransactionId, Vec<(Index, TransactionRecord)>> for ChronicleKeyspace {
+    type Row = Record<(Index, TransactionRecord)>;
+    fn try_decode(decoder: Decoder) -> anyhow::Result<Option<Vec<(Index, TransactionRecord)>>> {
+        ensure!(decoder.is_rows()?, "Decoded response is not rows!");
+        Ok(Some(
+            Self::Row::rows_iter(decoder)?.map(|row| row.into_inner()).collect(),
+        ))
+    }
+}
+
 impl Select<MilestoneIndex, Milestone> for ChronicleKeyspace {
     type QueryOrPrepared = PreparedStatement;
     fn statement(&self) -> std::borrow::Cow<'static, str> {
@@ -329,6 +383,92 @@ impl RowsDecoder<MilestoneIndex, Milestone> for ChronicleKeyspace {
     }
 }
 
+impl Select<Partitioned<MilestoneIndex>, Paged<Vec<MessageId>>> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "SELECT message_id
+            FROM {}.milestone_messages
+            WHERE milestone_index = ? AND partition_id = ?",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(builder: T, milestone_index: &Partitioned<MilestoneIndex>) -> T::Return {
+        builder.value(&milestone_index.0).value(&milestone_index.partition_id())
+    }
+}
+
+impl RowsDecoder<Partitioned<MilestoneIndex>, Paged<Vec<MessageId>>> for ChronicleKeyspace {
+    type Row = Record<MessageId>;
+    fn try_decode(decoder: Decoder) -> anyhow::Result<Option<Paged<Vec<MessageId>>>> {
+        ensure!(decoder.is_rows()?, "Decoded response is not rows!");
+        let mut iter = Self::Row::rows_iter(decoder)?;
+        let paging_state = iter.take_paging_state();
+        let values = iter.map(|row| row.into_inner()).collect();
+        Ok(Some(Paged::new(values, paging_state)))
+    }
+}
+
+impl Select<MilestoneTimestamp, (MilestoneIndex, Milestone)> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "SELECT milestone_index, message_id, timestamp
+            FROM {}.milestones_by_time
+            WHERE bucket = ? AND timestamp <= ?
+            LIMIT 1",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(builder: T, timestamp: &MilestoneTimestamp) -> T::Return {
+        builder.value(&timestamp.bucket()).value(&timestamp.0)
+    }
+}
+
+impl RowsDecoder<MilestoneTimestamp, (MilestoneIndex, Milestone)> for ChronicleKeyspace {
+    type Row = Record<(u32, MessageId, u64)>;
+    fn try_decode(decoder: Decoder) -> anyhow::Result<Option<(MilestoneIndex, Milestone)>> {
+        ensure!(decoder.is_rows()?, "Decoded response is not rows!");
+        Ok(Self::Row::rows_iter(decoder)?
+            .next()
+            .map(|row| (MilestoneIndex(row.0), Milestone::new(row.1, row.2))))
+    }
+}
+
+/// Selects the milestones of the time bucket of a timestamp, up to the timestamp
+impl Select<MilestoneTimestamp, Vec<(MilestoneIndex, MilestoneTimestamp)>> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "SELECT milestone_index, timestamp
+            FROM {}.milestones_by_time
+            WHERE bucket = ? AND timestamp <= ?",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(builder: T, timestamp: &MilestoneTimestamp) -> T::Return {
+        builder.value(&timestamp.bucket()).value(&timestamp.0)
+    }
+}
+
+impl RowsDecoder<MilestoneTimestamp, Vec<(MilestoneIndex, MilestoneTimestamp)>> for ChronicleKeyspace {
+    type Row = Record<(u32, u64)>;
+    fn try_decode(decoder: Decoder) -> anyhow::Result<Option<Vec<(MilestoneIndex, MilestoneTimestamp)>>> {
+        ensure!(decoder.is_rows()?, "Decoded response is not rows!");
+        Ok(Some(
+            Self::Row::rows_iter(decoder)?
+                .map(|row| {
+                    let (index, timestamp) = row.into_inner();
+                    (MilestoneIndex(index), MilestoneTimestamp(timestamp))
+                })
+                .collect(),
+        ))
+    }
+}
+
 impl Select<Hint, Vec<(MilestoneIndex, PartitionId)>> for ChronicleKeyspace {
     type QueryOrPrepared = PreparedStatement;
 
@@ -393,6 +533,98 @@ impl RowsDecoder<SyncRange, Iter<SyncRecord>> for ChronicleKeyspace {
     }
 }
 
+/// Selects the latest synced milestone, the sync table is clustered by descending milestone index
+impl Select<Synckey, SyncRecord> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "SELECT milestone_index, synced_by, logged_by FROM {}.sync WHERE key = ? LIMIT 1",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(builder: T, _: &Synckey) -> T::Return {
+        builder.value(&"permanode")
+    }
+}
+
+impl RowsDecoder<Synckey, SyncRecord> for ChronicleKeyspace {
+    type Row = SyncRecord;
+    fn try_decode(decoder: Decoder) -> anyhow::Result<Option<SyncRecord>> {
+        ensure!(decoder.is_rows()?, "Decoded response is not rows!");
+        Ok(Self::Row::rows_iter(decoder)?.next())
+    }
+}
+
+/// Selects the earliest synced milestone
+impl Select<EarliestSynckey, SyncRecord> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "SELECT milestone_index, synced_by, logged_by FROM {}.sync WHERE key = ? ORDER BY milestone_index ASC LIMIT 1",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(builder: T, _: &EarliestSynckey) -> T::Return {
+        builder.value(&"permanode")
+    }
+}
+
+impl RowsDecoder<EarliestSynckey, SyncRecord> for ChronicleKeyspace {
+    type Row = SyncRecord;
+    fn try_decode(decoder: Decoder) -> anyhow::Result<Option<SyncRecord>> {
+        ensure!(decoder.is_rows()?, "Decoded response is not rows!");
+        Ok(Self::Row::rows_iter(decoder)?.next())
+    }
+}
+
+/// Selects the sync row of a single milestone
+impl Select<MilestoneIndex, SyncRecord> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "SELECT milestone_index, synced_by, logged_by FROM {}.sync WHERE key = ? AND milestone_index = ?",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(builder: T, milestone_index: &MilestoneIndex) -> T::Return {
+        builder.value(&"permanode").value(&milestone_index.0)
+    }
+}
+
+impl RowsDecoder<MilestoneIndex, SyncRecord> for ChronicleKeyspace {
+    type Row = SyncRecord;
+    fn try_decode(decoder: Decoder) -> anyhow::Result<Option<SyncRecord>> {
+        ensure!(decoder.is_rows()?, "Decoded response is not rows!");
+        Ok(Self::Row::rows_iter(decoder)?.next())
+    }
+}
+
+/// Selects the analytic row of a single milestone
+impl Select<MilestoneIndex, AnalyticRecord> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "SELECT milestone_index, message_count, transaction_count, transferred_tokens FROM {}.analytics WHERE key = ? AND milestone_index = ?",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(builder: T, milestone_index: &MilestoneIndex) -> T::Return {
+        builder.value(&"permanode").value(&milestone_index.0)
+    }
+}
+
+impl RowsDecoder<MilestoneIndex, AnalyticRecord> for ChronicleKeyspace {
+    type Row = AnalyticRecord;
+    fn try_decode(decoder: Decoder) -> anyhow::Result<Option<AnalyticRecord>> {
+        ensure!(decoder.is_rows()?, "Decoded response is not rows!");
+        Ok(Self::Row::rows_iter(decoder)?.next())
+    }
+}
+
 impl Select<SyncRange, Iter<AnalyticRecord>> for ChronicleKeyspace {
     type QueryOrPrepared = QueryStatement;
     fn statement(&self) -> std::borrow::Cow<'static, str> {
@@ -424,6 +656,217 @@ impl RowsDecoder<SyncRange, Iter<AnalyticRecord>> for ChronicleKeyspace {
     }
 }
 
+impl Select<SyncRange, Iter<ExtendedAnalyticRecord>> for ChronicleKeyspace {
+    type QueryOrPrepared = QueryStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "SELECT milestone_index, indexation_payloads, transaction_payloads, milestone_payloads, receipt_payloads, input_utxo_already_spent, input_utxo_already_spent_in_this_milestone, input_utxo_not_found, input_output_sum_mismatch, invalid_signature, invalid_dust_allowance, semantic_validation_failed, unknown_conflicts, sending_addresses, receiving_addresses, dust_allowance_outputs FROM {}.extended_analytics WHERE key = ? AND milestone_index >= ? AND milestone_index < ?",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(builder: T, sync_range: &SyncRange) -> T::Return {
+        builder
+            .value(&"permanode")
+            .value(&sync_range.from)
+            .value(&sync_range.to)
+    }
+}
+
+impl RowsDecoder<SyncRange, Iter<ExtendedAnalyticRecord>> for ChronicleKeyspace {
+    type Row = ExtendedAnalyticRecord;
+    fn try_decode(decoder: Decoder) -> anyhow::Result<Option<Iter<ExtendedAnalyticRecord>>> {
+        ensure!(decoder.is_rows()?, "Decoded response is not rows!");
+        let rows_iter = Self::Row::rows_iter(decoder)?;
+        if rows_iter.is_empty() && !rows_iter.has_more_pages() {
+            Ok(None)
+        } else {
+            // CQL specs states that the page result might be empty but has more pages to fetch.
+            Ok(Some(rows_iter))
+        }
+    }
+}
+
+impl Select<OutputId, SpentRecord> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "SELECT spent_by_transaction_id, spent_by_message_id, milestone_index FROM {}.spent_outputs WHERE transaction_id = ? AND idx = ?",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(builder: T, output_id: &OutputId) -> T::Return {
+        builder
+            .value(&output_id.transaction_id().to_string())
+            .value(&output_id.index())
+    }
+}
+
+impl RowsDecoder<OutputId, SpentRecord> for ChronicleKeyspace {
+    type Row = SpentRecord;
+    fn try_decode(decoder: Decoder) -> anyhow::Result<Option<SpentRecord>> {
+        ensure!(decoder.is_rows()?, "Decoded response is not rows!");
+        Ok(Self::Row::rows_iter(decoder)?.next())
+    }
+}
+
+/// Selects every receipt
+impl Select<Synckey, Paged<Vec<ReceiptRecord>>> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "SELECT milestone_index, payload FROM {}.receipts WHERE key = ?",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(builder: T, _: &Synckey) -> T::Return {
+        builder.value(&"permanode")
+    }
+}
+
+/// Selects the receipts which migrated funds at a legacy milestone index
+impl Select<MigratedAt, Paged<Vec<ReceiptRecord>>> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "SELECT milestone_index, payload FROM {}.receipts WHERE key = ? AND migrated_at = ?",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(builder: T, migrated_at: &MigratedAt) -> T::Return {
+        builder.value(&"permanode").value(&migrated_at.0)
+    }
+}
+
+impl<K> RowsDecoder<K, Paged<Vec<ReceiptRecord>>> for ChronicleKeyspace {
+    type Row = ReceiptRecord;
+    fn try_decode(decoder: Decoder) -> anyhow::Result<Option<Paged<Vec<ReceiptRecord>>>> {
+        ensure!(decoder.is_rows()?, "Decoded response is not rows!");
+        let mut iter = Self::Row::rows_iter(decoder)?;
+        let paging_state = iter.take_paging_state();
+        let receipts = iter.collect();
+        Ok(Some(Paged::new(receipts, paging_state)))
+    }
+}
+
+impl Select<LegacyTailTransactionHash, Vec<MigratedFundsRecord>> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "SELECT milestone_index, migrated_at, output FROM {}.receipts_by_tail_transaction_hash WHERE tail_transaction_hash = ?",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(builder: T, tail_transaction_hash: &LegacyTailTransactionHash) -> T::Return {
+        builder.value(&tail_transaction_hash.0)
+    }
+}
+
+impl RowsDecoder<LegacyTailTransactionHash, Vec<MigratedFundsRecord>> for ChronicleKeyspace {
+    type Row = MigratedFundsRecord;
+    fn try_decode(decoder: Decoder) -> anyhow::Result<Option<Vec<MigratedFundsRecord>>> {
+        ensure!(decoder.is_rows()?, "Decoded response is not rows!");
+        let records = Self::Row::rows_iter(decoder)?.collect::<Vec<_>>();
+        Ok(if records.is_empty() { None } else { Some(records) })
+    }
+}
+
+/// Selects the latest treasury output
+impl Select<Synckey, TreasuryRecord> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "SELECT milestone_index, milestone_id, amount FROM {}.treasury WHERE key = ? LIMIT 1",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(builder: T, _: &Synckey) -> T::Return {
+        builder.value(&"permanode")
+    }
+}
+
+impl RowsDecoder<Synckey, TreasuryRecord> for ChronicleKeyspace {
+    type Row = TreasuryRecord;
+    fn try_decode(decoder: Decoder) -> anyhow::Result<Option<TreasuryRecord>> {
+        ensure!(decoder.is_rows()?, "Decoded response is not rows!");
+        Ok(Self::Row::rows_iter(decoder)?.next())
+    }
+}
+
+impl Select<OutputId, LedgerOutputRecord> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "SELECT message_id, output, created_at FROM {}.ledger_outputs WHERE transaction_id = ? AND idx = ?",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(builder: T, output_id: &OutputId) -> T::Return {
+        builder
+            .value(&output_id.transaction_id().to_string())
+            .value(&output_id.index())
+    }
+}
+
+impl RowsDecoder<OutputId, LedgerOutputRecord> for ChronicleKeyspace {
+    type Row = LedgerOutputRecord;
+    fn try_decode(decoder: Decoder) -> anyhow::Result<Option<LedgerOutputRecord>> {
+        ensure!(decoder.is_rows()?, "Decoded response is not rows!");
+        Ok(Self::Row::rows_iter(decoder)?.next())
+    }
+}
+
+/// Selects the balance of an address as of a milestone index
+impl Select<(Ed25519Address, MilestoneIndex), BalanceRecord> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "SELECT milestone_index, balance, dust_allowance, output_count, dust_output_count FROM {}.ledger_balances WHERE address = ? AND milestone_index <= ? LIMIT 1",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(builder: T, (address, milestone_index): &(Ed25519Address, MilestoneIndex)) -> T::Return {
+        builder.value(&address.to_string()).value(&milestone_index.0)
+    }
+}
+
+impl RowsDecoder<(Ed25519Address, MilestoneIndex), BalanceRecord> for ChronicleKeyspace {
+    type Row = BalanceRecord;
+    fn try_decode(decoder: Decoder) -> anyhow::Result<Option<BalanceRecord>> {
+        ensure!(decoder.is_rows()?, "Decoded response is not rows!");
+        Ok(Self::Row::rows_iter(decoder)?.next())
+    }
+}
+
+impl Select<Synckey, LedgerCheckpoint> for ChronicleKeyspace {
+    type QueryOrPrepared = PreparedStatement;
+    fn statement(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "SELECT milestone_index, consuming FROM {}.ledger_checkpoint WHERE key = ?",
+            self.name()
+        )
+        .into()
+    }
+    fn bind_values<T: Values>(builder: T, _: &Synckey) -> T::Return {
+        builder.value(&"permanode")
+    }
+}
+
+impl RowsDecoder<Synckey, LedgerCheckpoint> for ChronicleKeyspace {
+    type Row = LedgerCheckpoint;
+    fn try_decode(decoder: Decoder) -> anyhow::Result<Option<LedgerCheckpoint>> {
+        ensure!(decoder.is_rows()?, "Decoded response is not rows!");
+        Ok(Self::Row::rows_iter(decoder)?.next())
+    }
+}
+
 // ###############
 // ROW DEFINITIONS
 // ###############
@@ -452,6 +895,16 @@ impl Row for Record<(Option<Message>, Option<MessageMetadata>)> {
     }
 }
 
+impl Row for Record<(Option<RawMessage>, Option<MessageMetadata>)> {
+    fn try_decode_row<T: ColumnValue>(rows: &mut T) -> anyhow::Result<Self> {
+        let message = rows
+            .column_value::<Option<Cursor<Vec<u8>>>>()?
+            .map(|bytes| RawMessage(bytes.into_inner()));
+        let metadata = rows.column_value::<Option<MessageMetadata>>()?;
+        Ok(Record::new((message, metadata)))
+    }
+}
+
 impl Row for Record<MessageId> {
     fn try_decode_row<T: ColumnValue>(rows: &mut T) -> anyhow::Result<Self> {
         Ok(Record::new(MessageId::from_str(&rows.column_value::<String>()?)?))
@@ -483,6 +936,27 @@ impl Row for Record<(MessageId, TransactionData, Option<LedgerInclusionState>)>
     }
 }
 
+impl Row for Record<(Index, TransactionRecord)> {
+    fn try_decode_row<T: ColumnValue>(rows: &mut T) -> anyhow::Result<Self> {
+        let index = rows.column_value::<Index>()?;
+        let variant = rows.column_value::<TransactionVariant>()?;
+        let message_id = MessageId::from_str(&rows.column_value::<String>()?)?;
+        let data = rows.column_value::<TransactionData>()?;
+        let inclusion_state = rows.column_value::<Option<LedgerInclusionState>>()?;
+        let milestone_index = rows.column_value::<Option<u32>>()?.map(MilestoneIndex);
+        Ok(Record::new((
+            index,
+            TransactionRecord {
+                variant,
+                message_id,
+                data,
+                inclusion_state,
+                milestone_index,
+            },
+        )))
+    }
+}
+
 impl Row for Record<(MessageId, u64)> {
     fn try_decode_row<T: ColumnValue>(rows: &mut T) -> anyhow::Result<Self> {
         let message_id = MessageId::from_str(&rows.column_value::<String>()?)?;
@@ -491,6 +965,15 @@ impl Row for Record<(MessageId, u64)> {
     }
 }
 
+impl Row for Record<(u32, MessageId, u64)> {
+    fn try_decode_row<T: ColumnValue>(rows: &mut T) -> anyhow::Result<Self> {
+        let milestone_index = rows.column_value::<u32>()?;
+        let message_id = MessageId::from_str(&rows.column_value::<String>()?)?;
+        let timestamp = rows.column_value::<u64>()?;
+        Ok(Record::new((milestone_index, message_id, timestamp)))
+    }
+}
+
 impl Row for Record<(u32, u16)> {
     fn try_decode_row<R: Rows + ColumnValue>(rows: &mut R) -> anyhow::Result<Self> {
         Ok(Record::new((rows.column_value::<u32>()?, rows.column_value::<u16>()?)))
@@ -552,6 +1035,100 @@ impl Row for SyncRecord {
     }
 }
 
+impl Row for SpentRecord {
+    fn try_decode_row<T: ColumnValue>(rows: &mut T) -> anyhow::Result<Self> {
+        let transaction_id = TransactionId::from_str(&rows.column_value::<String>()?)?;
+        let message_id = MessageId::from_str(&rows.column_value::<String>()?)?;
+        let milestone_index = MilestoneIndex(rows.column_value::<u32>()?);
+        Ok(SpentRecord::new(transaction_id, message_id, milestone_index))
+    }
+}
+
+impl Row for ReceiptRecord {
+    fn try_decode_row<T: ColumnValue>(rows: &mut T) -> anyhow::Result<Self> {
+        let milestone_index = MilestoneIndex(rows.column_value::<u32>()?);
+        let receipt = ReceiptPayload::unpack(&mut rows.column_value::<Cursor<Vec<u8>>>()?)?;
+        Ok(ReceiptRecord::new(milestone_index, receipt))
+    }
+}
+
+impl Row for MigratedFundsRecord {
+    fn try_decode_row<T: ColumnValue>(rows: &mut T) -> anyhow::Result<Self> {
+        let milestone_index = MilestoneIndex(rows.column_value::<u32>()?);
+        let migrated_at = MilestoneIndex(rows.column_value::<u32>()?);
+        let output = SignatureLockedSingleOutput::unpack(&mut rows.column_value::<Cursor<Vec<u8>>>()?)?;
+        Ok(MigratedFundsRecord::new(milestone_index, migrated_at, output))
+    }
+}
+
+impl Row for TreasuryRecord {
+    fn try_decode_row<T: ColumnValue>(rows: &mut T) -> anyhow::Result<Self> {
+        let milestone_index = MilestoneIndex(rows.column_value::<u32>()?);
+        let milestone_id = MilestoneId::from_str(&rows.column_value::<String>()?)?;
+        let amount = rows.column_value::<u64>()?;
+        Ok(TreasuryRecord::new(milestone_index, milestone_id, amount))
+    }
+}
+
+impl Row for LedgerOutputRecord {
+    fn try_decode_row<T: ColumnValue>(rows: &mut T) -> anyhow::Result<Self> {
+        let message_id = MessageId::from_str(&rows.column_value::<String>()?)?;
+        let output = Output::unpack(&mut rows.column_value::<Cursor<Vec<u8>>>()?)?;
+        let created_at = MilestoneIndex(rows.column_value::<u32>()?);
+        Ok(LedgerOutputRecord::new(message_id, output, created_at))
+    }
+}
+
+impl Row for BalanceRecord {
+    fn try_decode_row<T: ColumnValue>(rows: &mut T) -> anyhow::Result<Self> {
+        let milestone_index = MilestoneIndex(rows.column_value::<u32>()?);
+        let balance = rows.column_value::<u64>()?;
+        let dust_allowance = rows.column_value::<u64>()?;
+        let output_count = rows.column_value::<u64>()?;
+        let dust_output_count = rows.column_value::<u64>()?;
+        Ok(BalanceRecord::new(
+            milestone_index,
+            balance,
+            dust_allowance,
+            output_count,
+            dust_output_count,
+        ))
+    }
+}
+
+impl Row for LedgerCheckpoint {
+    fn try_decode_row<T: ColumnValue>(rows: &mut T) -> anyhow::Result<Self> {
+        let milestone_index = rows.column_value::<u32>()?;
+        let consuming = rows.column_value::<Option<u32>>()?;
+        Ok(LedgerCheckpoint::new(milestone_index, consuming))
+    }
+}
+
+impl Row for ExtendedAnalyticRecord {
+    fn try_decode_row<T: ColumnValue>(rows: &mut T) -> anyhow::Result<Self> {
+        Ok(ExtendedAnalyticRecord {
+            milestone_index: MilestoneIndex(rows.column_value::<u32>()?),
+            indexation_payloads: rows.column_value::<u32>()?,
+            transaction_payloads: rows.column_value::<u32>()?,
+            milestone_payloads: rows.column_value::<u32>()?,
+            receipt_payloads: rows.column_value::<u32>()?,
+            conflicts: ConflictCounts {
+                input_utxo_already_spent: rows.column_value::<u32>()?,
+                input_utxo_already_spent_in_this_milestone: rows.column_value::<u32>()?,
+                input_utxo_not_found: rows.column_value::<u32>()?,
+                input_output_sum_mismatch: rows.column_value::<u32>()?,
+                invalid_signature: rows.column_value::<u32>()?,
+                invalid_dust_allowance: rows.column_value::<u32>()?,
+                semantic_validation_failed: rows.column_value::<u32>()?,
+                unknown: rows.column_value::<u32>()?,
+            },
+            sending_addresses: rows.column_value::<u32>()?,
+            receiving_addresses: rows.column_value::<u32>()?,
+            dust_allowance_outputs: rows.column_value::<u32>()?,
+        })
+    }
+}
+
 impl Row for AnalyticRecord {
     fn try_decode_row<T: ColumnValue>(rows: &mut T) -> anyhow::Result<Self> {
         let milestone_index = MilestoneIndex(rows.column_value::<u32>()?);
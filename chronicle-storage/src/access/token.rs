@@ -27,6 +27,18 @@ impl ComputeToken<Partitioned<Ed25519Address>> for ChronicleKeyspace {
     }
 }
 
+impl ComputeToken<Ed25519Address> for ChronicleKeyspace {
+    fn token(key: &Ed25519Address) -> i64 {
+        key.to_string().get_token()
+    }
+}
+
+impl ComputeToken<(Ed25519Address, MilestoneIndex)> for ChronicleKeyspace {
+    fn token(key: &(Ed25519Address, MilestoneIndex)) -> i64 {
+        key.0.to_string().get_token()
+    }
+}
+
 impl ComputeToken<OutputId> for ChronicleKeyspace {
     fn token(key: &OutputId) -> i64 {
         key.transaction_id().to_string().chain_token(&key.index()).finish()
@@ -45,6 +57,19 @@ impl ComputeToken<MilestoneIndex> for ChronicleKeyspace {
     }
 }
 
+impl ComputeToken<Partitioned<MilestoneIndex>> for ChronicleKeyspace {
+    fn token(key: &Partitioned<MilestoneIndex>) -> i64 {
+        key.0.chain_token(&key.partition_id()).finish()
+    }
+}
+
+impl ComputeToken<MilestoneTimestamp> for ChronicleKeyspace {
+    fn token(key: &MilestoneTimestamp) -> i64 {
+        // an unbucketable timestamp is rejected by the query itself
+        key.bucket().unwrap_or_default().get_token()
+    }
+}
+
 impl ComputeToken<Hint> for ChronicleKeyspace {
     fn token(key: &Hint) -> i64 {
         key.hint.chain_token(&key.variant.to_string()).finish()
@@ -63,6 +88,18 @@ impl ComputeToken<Synckey> for ChronicleKeyspace {
     }
 }
 
+impl ComputeToken<MigratedAt> for ChronicleKeyspace {
+    fn token(_: &MigratedAt) -> i64 {
+        "permanode".get_token()
+    }
+}
+
+impl ComputeToken<LegacyTailTransactionHash> for ChronicleKeyspace {
+    fn token(key: &LegacyTailTransactionHash) -> i64 {
+        key.0.get_token()
+    }
+}
+
 impl ComputeToken<(TransactionId, Index)> for ChronicleKeyspace {
     fn token(key: &(TransactionId, Index)) -> i64 {
         key.0.to_string().chain_token(&key.1).finish()
@@ -5,7 +5,11 @@ use super::*;
 use bee_common::packable::Packable;
 use bee_message::{
     prelude::{
+        Address,
+        MilestoneId,
         Output,
+        ReceiptPayload,
+        SignatureLockedSingleOutput,
         TransactionId,
         TreasuryInput,
         UnlockBlock,
@@ -14,7 +18,11 @@ use bee_message::{
     MessageId,
 };
 use std::{
-    io::Cursor,
+    convert::TryFrom,
+    io::{
+        Cursor,
+        Read,
+    },
     ops::{
         Deref,
         DerefMut,
@@ -259,6 +267,9 @@ pub struct MessageMetadata {
     pub should_promote: Option<bool>,
     #[serde(rename = "shouldReattach")]
     pub should_reattach: Option<bool>,
+    /// The reason a transaction conflicts, as reported by the node
+    #[serde(rename = "conflictReason", default)]
+    pub conflict_reason: Option<u8>,
 }
 
 /// A message's ledger inclusion state
@@ -299,7 +310,10 @@ impl ColumnEncoder for MessageMetadata {
 
 impl ColumnDecoder for MessageMetadata {
     fn try_decode(slice: &[u8]) -> anyhow::Result<Self> {
-        bincode_config().deserialize(slice).map_err(Into::into)
+        // Metadata stored before the conflict reason was added lacks its trailing `None` tag
+        bincode_config()
+            .deserialize_from(slice.chain(&[0u8][..]))
+            .map_err(Into::into)
     }
 }
 impl ColumnEncoder for TransactionData {
@@ -311,6 +325,28 @@ impl ColumnEncoder for TransactionData {
     }
 }
 
+/// A marker of a spent output, which links it to the included transaction which spent it
+#[derive(Debug, Clone)]
+pub struct SpentRecord {
+    /// The id of the transaction which spent the output
+    pub transaction_id: TransactionId,
+    /// The id of the message which carried the spending transaction
+    pub message_id: MessageId,
+    /// The milestone index which confirmed the spending transaction
+    pub milestone_index: MilestoneIndex,
+}
+
+impl SpentRecord {
+    /// Create a new spent marker
+    pub fn new(transaction_id: TransactionId, message_id: MessageId, milestone_index: MilestoneIndex) -> Self {
+        Self {
+            transaction_id,
+            message_id,
+            milestone_index,
+        }
+    }
+}
+
 /// A result struct which holds a retrieved output as well as all associated unlock blocks
 #[derive(Debug, Clone)]
 pub struct OutputRes {
@@ -341,6 +377,35 @@ pub type PartitionId = u16;
 #[derive(Clone)]
 pub struct Indexation(pub String);
 
+/// The packed bytes of a message, as stored in the `messages` table
+#[derive(Clone, Debug)]
+pub struct RawMessage(pub Vec<u8>);
+
+/// Used to lookup the earliest row of the `sync` table, which is clustered by descending milestone index
+#[derive(Clone, Copy, Debug)]
+pub struct EarliestSynckey;
+
+/// A milestone timestamp (UNIX seconds), used to lookup in the `milestones_by_time` table.
+/// Milestones are bucketed by day, so a lookup only scans the bucket of its timestamp.
+#[derive(Clone, Copy, Debug)]
+pub struct MilestoneTimestamp(pub u64);
+
+impl MilestoneTimestamp {
+    /// The length (in seconds) of a time bucket
+    pub const BUCKET_SECS: u64 = 24 * 60 * 60;
+
+    /// Get the time bucket of this timestamp, or None if it's too far in the future to be bucketed.
+    /// Such a timestamp binds a null bucket, which gets the query rejected.
+    pub fn bucket(&self) -> Option<u32> {
+        u32::try_from(self.0 / Self::BUCKET_SECS).ok()
+    }
+
+    /// Get the last timestamp of the previous bucket, if any
+    pub fn previous_bucket(&self) -> Option<Self> {
+        (self.bucket()? as u64 * Self::BUCKET_SECS).checked_sub(1).map(Self)
+    }
+}
+
 /// A hint, used to lookup in the `hints` table
 #[derive(Clone)]
 pub struct Hint {
@@ -524,3 +589,281 @@ impl AnalyticRecord {
         &self.transferred_tokens
     }
 }
+
+/// The number of conflicting transactions within a milestone cone, by conflict reason
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ConflictCounts {
+    /// The referenced UTXO was already spent
+    pub input_utxo_already_spent: u32,
+    /// The referenced UTXO was already spent while confirming this milestone
+    pub input_utxo_already_spent_in_this_milestone: u32,
+    /// The referenced UTXO cannot be found
+    pub input_utxo_not_found: u32,
+    /// The sum of the inputs and output values does not match
+    pub input_output_sum_mismatch: u32,
+    /// The unlock block signature is invalid
+    pub invalid_signature: u32,
+    /// The dust allowance for the address is invalid
+    pub invalid_dust_allowance: u32,
+    /// The semantic validation failed
+    pub semantic_validation_failed: u32,
+    /// The node did not report a conflict reason
+    pub unknown: u32,
+}
+
+impl ConflictCounts {
+    /// Count a conflicting transaction with the given conflict reason code
+    pub fn count(&mut self, conflict_reason: Option<u8>) {
+        let counter = match conflict_reason {
+            Some(1) => &mut self.input_utxo_already_spent,
+            Some(2) => &mut self.input_utxo_already_spent_in_this_milestone,
+            Some(3) => &mut self.input_utxo_not_found,
+            Some(4) => &mut self.input_output_sum_mismatch,
+            Some(5) => &mut self.invalid_signature,
+            Some(6) => &mut self.invalid_dust_allowance,
+            Some(255) => &mut self.semantic_validation_failed,
+            _ => &mut self.unknown,
+        };
+        *counter += 1;
+    }
+}
+
+#[derive(Clone, Debug)]
+/// MilestoneData extended analytics information.
+pub struct ExtendedAnalyticRecord {
+    /// The milestone index
+    pub milestone_index: MilestoneIndex,
+    /// The number of indexation payloads within a milestone cone
+    pub indexation_payloads: u32,
+    /// The number of transaction payloads within a milestone cone
+    pub transaction_payloads: u32,
+    /// The number of milestone payloads within a milestone cone
+    pub milestone_payloads: u32,
+    /// The number of receipts within a milestone cone
+    pub receipt_payloads: u32,
+    /// The conflicting transactions within a milestone cone
+    pub conflicts: ConflictCounts,
+    /// The number of distinct addresses which sent tokens within a milestone cone
+    pub sending_addresses: u32,
+    /// The number of distinct addresses which received tokens within a milestone cone
+    pub receiving_addresses: u32,
+    /// The number of dust allowance outputs created within a milestone cone
+    pub dust_allowance_outputs: u32,
+}
+
+impl ExtendedAnalyticRecord {
+    /// Create an empty record for the given milestone index
+    pub fn new(milestone_index: MilestoneIndex) -> Self {
+        Self {
+            milestone_index,
+            indexation_payloads: 0,
+            transaction_payloads: 0,
+            milestone_payloads: 0,
+            receipt_payloads: 0,
+            conflicts: ConflictCounts::default(),
+            sending_addresses: 0,
+            receiving_addresses: 0,
+            dust_allowance_outputs: 0,
+        }
+    }
+}
+
+/// The legacy network milestone index at which funds were migrated
+#[derive(Clone, Copy, Debug)]
+pub struct MigratedAt(pub u32);
+
+/// The tail transaction hash of a legacy bundle which migrated funds
+#[derive(Clone, Debug)]
+pub struct LegacyTailTransactionHash(pub String);
+
+/// A receipt along with the milestone which carried it
+#[derive(Clone, Debug)]
+pub struct ReceiptRecord {
+    /// The milestone index which carried the receipt
+    pub milestone_index: MilestoneIndex,
+    /// The receipt
+    pub receipt: ReceiptPayload,
+}
+
+impl ReceiptRecord {
+    /// Create a new receipt record
+    pub fn new(milestone_index: MilestoneIndex, receipt: ReceiptPayload) -> Self {
+        Self {
+            milestone_index,
+            receipt,
+        }
+    }
+}
+
+/// A migrated funds entry of a receipt
+#[derive(Clone, Debug)]
+pub struct MigratedFundsRecord {
+    /// The milestone index which carried the receipt
+    pub milestone_index: MilestoneIndex,
+    /// The legacy network milestone index at which the funds were migrated
+    pub migrated_at: MilestoneIndex,
+    /// The output the funds were migrated to
+    pub output: SignatureLockedSingleOutput,
+}
+
+impl MigratedFundsRecord {
+    /// Create a new migrated funds record
+    pub fn new(
+        milestone_index: MilestoneIndex,
+        migrated_at: MilestoneIndex,
+        output: SignatureLockedSingleOutput,
+    ) -> Self {
+        Self {
+            milestone_index,
+            migrated_at,
+            output,
+        }
+    }
+}
+
+/// A treasury output along with the milestone which created it
+#[derive(Clone, Debug)]
+pub struct TreasuryRecord {
+    /// The milestone index which created the treasury output
+    pub milestone_index: MilestoneIndex,
+    /// The id of the milestone which created the treasury output
+    pub milestone_id: MilestoneId,
+    /// The amount of the treasury output
+    pub amount: u64,
+}
+
+impl TreasuryRecord {
+    /// Create a new treasury record
+    pub fn new(milestone_index: MilestoneIndex, milestone_id: MilestoneId, amount: u64) -> Self {
+        Self {
+            milestone_index,
+            milestone_id,
+            amount,
+        }
+    }
+}
+
+/// An unspent output of the ledger, along with the milestone which created it
+#[derive(Clone, Debug)]
+pub struct LedgerOutputRecord {
+    /// The id of the message which created the output
+    pub message_id: MessageId,
+    /// The output
+    pub output: Output,
+    /// The milestone index which created the output
+    pub created_at: MilestoneIndex,
+}
+
+impl LedgerOutputRecord {
+    /// Create a new ledger output record
+    pub fn new(message_id: MessageId, output: Output, created_at: MilestoneIndex) -> Self {
+        Self {
+            message_id,
+            output,
+            created_at,
+        }
+    }
+    /// Get the address which owns the output, if any
+    pub fn address(&self) -> Option<&Ed25519Address> {
+        match &self.output {
+            Output::SignatureLockedSingle(output) => {
+                let Address::Ed25519(address) = output.address();
+                Some(address)
+            }
+            Output::SignatureLockedDustAllowance(output) => {
+                let Address::Ed25519(address) = output.address();
+                Some(address)
+            }
+            _ => None,
+        }
+    }
+    /// Get the amount of the output
+    pub fn amount(&self) -> u64 {
+        match &self.output {
+            Output::SignatureLockedSingle(output) => output.amount(),
+            Output::SignatureLockedDustAllowance(output) => output.amount(),
+            Output::Treasury(output) => output.amount(),
+            _ => 0,
+        }
+    }
+    /// Check if the output is a dust allowance output
+    pub fn is_dust_allowance(&self) -> bool {
+        matches!(self.output, Output::SignatureLockedDustAllowance(_))
+    }
+}
+
+/// The balance of an address, valid as of a milestone
+#[derive(Clone, Copy, Debug)]
+pub struct BalanceRecord {
+    /// The milestone index at which the balance is valid
+    pub milestone_index: MilestoneIndex,
+    /// The sum of the unspent outputs of the address
+    pub balance: u64,
+    /// The sum of the unspent dust allowance outputs of the address
+    pub dust_allowance: u64,
+    /// The number of unspent outputs of the address
+    pub output_count: u64,
+    /// The number of unspent dust outputs of the address
+    pub dust_output_count: u64,
+}
+
+impl BalanceRecord {
+    /// Create a new balance record
+    pub fn new(
+        milestone_index: MilestoneIndex,
+        balance: u64,
+        dust_allowance: u64,
+        output_count: u64,
+        dust_output_count: u64,
+    ) -> Self {
+        Self {
+            milestone_index,
+            balance,
+            dust_allowance,
+            output_count,
+            dust_output_count,
+        }
+    }
+}
+
+/// The ledger checkpoint
+#[derive(Clone, Copy, Debug)]
+pub struct LedgerCheckpoint {
+    /// The last milestone index fully applied to the ledger
+    pub milestone_index: u32,
+    /// The milestone index whose consumed outputs are being removed, once its created outputs and balances are
+    /// written
+    pub consuming: Option<u32>,
+}
+
+impl LedgerCheckpoint {
+    /// Create a new ledger checkpoint
+    pub fn new(milestone_index: u32, consuming: Option<u32>) -> Self {
+        Self {
+            milestone_index,
+            consuming,
+        }
+    }
+}
+
+/// A milestone which failed the verification, along with the reason
+#[derive(Clone, Debug)]
+pub struct QuarantinedMilestoneRecord {
+    /// The milestone index
+    pub milestone_index: MilestoneIndex,
+    /// The milestone
+    pub milestone: Box<MilestonePayload>,
+    /// Why the milestone failed the verification
+    pub reason: String,
+}
+
+impl QuarantinedMilestoneRecord {
+    /// Create a new quarantined milestone record
+    pub fn new(milestone_index: MilestoneIndex, milestone: Box<MilestonePayload>, reason: String) -> Self {
+        Self {
+            milestone_index,
+            milestone,
+            reason,
+        }
+    }
+}
@@ -6,6 +6,7 @@ use bee_message::{
     parents::Parents,
     prelude::{
         MilestoneIndex,
+        OutputId,
         TransactionId,
     },
     Message,
@@ -15,8 +16,10 @@ use bee_message::{
 use bee_pow::providers::miner::Miner;
 use chronicle_storage::access::{
     AddressRecord,
+    ConflictCounts,
     Paged,
     Partitioned,
+    SpentRecord,
 };
 use core::marker::PhantomData;
 
@@ -172,6 +175,15 @@ async fn init_database() {
                 PRIMARY KEY (transaction_id, idx, variant, message_id, data)
             );
 
+            CREATE TABLE IF NOT EXISTS {0}.spent_outputs  (
+                transaction_id text,
+                idx smallint,
+                spent_by_transaction_id text,
+                spent_by_message_id text,
+                milestone_index int,
+                PRIMARY KEY ((transaction_id, idx))
+            );
+
             CREATE TABLE IF NOT EXISTS {0}.milestones  (
                 milestone_index int,
                 message_id text,
@@ -180,6 +192,21 @@ async fn init_database() {
                 PRIMARY KEY (milestone_index, message_id)
             );
 
+            CREATE TABLE IF NOT EXISTS {0}.milestones_by_time  (
+                bucket int,
+                timestamp bigint,
+                milestone_index int,
+                message_id text,
+                PRIMARY KEY (bucket, timestamp, milestone_index)
+            ) WITH CLUSTERING ORDER BY (timestamp DESC, milestone_index DESC);
+
+            CREATE TABLE IF NOT EXISTS {0}.milestone_messages  (
+                milestone_index int,
+                partition_id smallint,
+                message_id text,
+                PRIMARY KEY ((milestone_index, partition_id), message_id)
+            );
+
             CREATE TABLE IF NOT EXISTS {0}.hints  (
                 hint text,
                 variant text,
@@ -391,3 +418,103 @@ pub async fn test_insert_select_delete() {
         panic!("Could not verify if keyspace was created!")
     }
 }
+
+// #[tokio::test]
+pub async fn test_insert_select_spent_record() {
+    // Init Scylla Application
+    init_scylla_application().await;
+
+    let keyspace = ChronicleKeyspace::new("chronicle_test".to_owned());
+
+    // Insert (OutputId, SpentRecord) pair
+    let key = OutputId::new(TransactionId::new([5; 32]), 1).unwrap();
+    let value = SpentRecord::new(TransactionId::new([6; 32]), MessageId::new([7; 32]), MilestoneIndex(8));
+
+    let (sender, mut inbox) = unbounded_channel::<Result<(), WorkerError>>();
+    let worker = BatchWorker::boxed(sender.clone());
+    let insert_req = keyspace
+        .insert_query(&key, &value)
+        .consistency(Consistency::One)
+        .build()
+        .unwrap();
+    insert_req.send_local(worker);
+    if let Some(msg) = inbox.recv().await {
+        match msg {
+            Ok(_) => println!("(OutputId, SpentRecord) Inserted"),
+            Err(e) => panic!("Inbox recv() error: {}", e),
+        }
+    } else {
+        panic!("Could not verify if keyspace was created!")
+    }
+
+    // Select the spent marker of the output
+    let request = keyspace
+        .select::<SpentRecord>(&key)
+        .consistency(Consistency::One)
+        .paging_state(&None)
+        .build()
+        .unwrap();
+
+    let (sender, mut inbox) = unbounded_channel::<Result<Option<SpentRecord>, WorkerError>>();
+    let worker = ValueWorker::new(sender, keyspace.clone(), key, 0, PhantomData);
+    let worker = Box::new(worker);
+
+    request.send_local(worker);
+
+    if let Some(msg) = inbox.recv().await {
+        match msg {
+            Ok(res) => {
+                let spent = res.expect("The spent marker was not found");
+                assert_eq!(spent.transaction_id, value.transaction_id);
+                assert_eq!(spent.message_id, value.message_id);
+                assert_eq!(spent.milestone_index, value.milestone_index);
+            }
+            Err(e) => panic!("Inbox recv() worker error: {}", e),
+        }
+    } else {
+        panic!("Could not verify if keyspace was created!")
+    }
+
+    // An output which wasn't spent has no marker
+    let key = OutputId::new(TransactionId::new([5; 32]), 2).unwrap();
+    let request = keyspace
+        .select::<SpentRecord>(&key)
+        .consistency(Consistency::One)
+        .paging_state(&None)
+        .build()
+        .unwrap();
+
+    let (sender, mut inbox) = unbounded_channel::<Result<Option<SpentRecord>, WorkerError>>();
+    let worker = ValueWorker::new(sender, keyspace.clone(), key, 0, PhantomData);
+    let worker = Box::new(worker);
+
+    request.send_local(worker);
+
+    if let Some(msg) = inbox.recv().await {
+        match msg {
+            Ok(res) => assert!(res.is_none()),
+            Err(e) => panic!("Inbox recv() worker error: {}", e),
+        }
+    } else {
+        panic!("Could not verify if keyspace was created!")
+    }
+}
+
+#[test]
+fn conflict_counts_by_reason() {
+    let mut conflicts = ConflictCounts::default();
+    for reason in &[Some(1), Some(2), Some(2), Some(3), Some(4), Some(5), Some(6), Some(255)] {
+        conflicts.count(*reason);
+    }
+    // Unknown reason codes and missing reasons are counted together
+    conflicts.count(Some(7));
+    conflicts.count(None);
+    assert_eq!(conflicts.input_utxo_already_spent, 1);
+    assert_eq!(conflicts.input_utxo_already_spent_in_this_milestone, 2);
+    assert_eq!(conflicts.input_utxo_not_found, 1);
+    assert_eq!(conflicts.input_output_sum_mismatch, 1);
+    assert_eq!(conflicts.invalid_signature, 1);
+    assert_eq!(conflicts.invalid_dust_allowance, 1);
+    assert_eq!(conflicts.semantic_validation_failed, 1);
+    assert_eq!(conflicts.unknown, 2);
+}
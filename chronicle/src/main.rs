@@ -23,6 +23,9 @@ use websocket::*;
 
 mod websocket;
 
+/// The number of milestone events buffered for slow API subscribers
+const MILESTONE_EVENTS_CAPACITY: usize = 100;
+
 launcher!
 (
     builder: AppsBuilder
@@ -42,8 +45,10 @@ impl Builder for AppsBuilder {
         let config = get_config();
         let storage_config = config.storage_config;
         let broker_config = config.broker_config;
-        let chronicle_api_builder = ChronicleAPIBuilder::new();
+        let (milestone_events, _) = tokio::sync::broadcast::channel(MILESTONE_EVENTS_CAPACITY);
+        let chronicle_api_builder = ChronicleAPIBuilder::new().milestone_events(milestone_events.clone());
         let chronicle_broker_builder = ChronicleBrokerBuilder::new()
+            .milestone_events(milestone_events)
             .collector_count(broker_config.collector_count)
             .parallelism(broker_config.parallelism)
             .complete_gaps_interval_secs(broker_config.complete_gaps_interval_secs);
@@ -217,6 +222,15 @@ async fn init_database() -> anyhow::Result<()> {
                 PRIMARY KEY (transaction_id, idx, variant, message_id, data)
             );
 
+            CREATE TABLE IF NOT EXISTS {0}.spent_outputs  (
+                transaction_id text,
+                idx smallint,
+                spent_by_transaction_id text,
+                spent_by_message_id text,
+                milestone_index int,
+                PRIMARY KEY ((transaction_id, idx))
+            );
+
             CREATE TABLE IF NOT EXISTS {0}.milestones  (
                 milestone_index int,
                 message_id text,
@@ -225,6 +239,21 @@ async fn init_database() -> anyhow::Result<()> {
                 PRIMARY KEY (milestone_index, message_id)
             );
 
+            CREATE TABLE IF NOT EXISTS {0}.milestones_by_time  (
+                bucket int,
+                timestamp bigint,
+                milestone_index int,
+                message_id text,
+                PRIMARY KEY (bucket, timestamp, milestone_index)
+            ) WITH CLUSTERING ORDER BY (timestamp DESC, milestone_index DESC);
+
+            CREATE TABLE IF NOT EXISTS {0}.milestone_messages  (
+                milestone_index int,
+                partition_id smallint,
+                message_id text,
+                PRIMARY KEY ((milestone_index, partition_id), message_id)
+            );
+
             CREATE TABLE IF NOT EXISTS {0}.hints  (
                 hint text,
                 variant text,
@@ -248,6 +277,87 @@ async fn init_database() -> anyhow::Result<()> {
                 transaction_count int,
                 transferred_tokens bigint,
                 PRIMARY KEY (key, milestone_index)
+            ) WITH CLUSTERING ORDER BY (milestone_index DESC);
+
+            CREATE TABLE IF NOT EXISTS {0}.extended_analytics (
+                key text,
+                milestone_index int,
+                indexation_payloads int,
+                transaction_payloads int,
+                milestone_payloads int,
+                receipt_payloads int,
+                input_utxo_already_spent int,
+                input_utxo_already_spent_in_this_milestone int,
+                input_utxo_not_found int,
+                input_output_sum_mismatch int,
+                invalid_signature int,
+                invalid_dust_allowance int,
+                semantic_validation_failed int,
+                unknown_conflicts int,
+                sending_addresses int,
+                receiving_addresses int,
+                dust_allowance_outputs int,
+                PRIMARY KEY (key, milestone_index)
+            ) WITH CLUSTERING ORDER BY (milestone_index DESC);
+
+            CREATE TABLE IF NOT EXISTS {0}.receipts (
+                key text,
+                migrated_at int,
+                milestone_index int,
+                payload blob,
+                PRIMARY KEY (key, migrated_at, milestone_index)
+            );
+
+            CREATE TABLE IF NOT EXISTS {0}.receipts_by_tail_transaction_hash (
+                tail_transaction_hash text,
+                milestone_index int,
+                migrated_at int,
+                output blob,
+                PRIMARY KEY (tail_transaction_hash, milestone_index)
+            );
+
+            CREATE TABLE IF NOT EXISTS {0}.treasury (
+                key text,
+                milestone_index int,
+                milestone_id text,
+                amount bigint,
+                PRIMARY KEY (key, milestone_index)
+            ) WITH CLUSTERING ORDER BY (milestone_index DESC);
+
+            CREATE TABLE IF NOT EXISTS {0}.ledger_outputs (
+                transaction_id text,
+                idx smallint,
+                message_id text,
+                address text,
+                amount bigint,
+                output blob,
+                created_at int,
+                PRIMARY KEY ((transaction_id, idx))
+            );
+
+            CREATE TABLE IF NOT EXISTS {0}.ledger_balances (
+                address text,
+                milestone_index int,
+                balance bigint,
+                dust_allowance bigint,
+                output_count bigint,
+                dust_output_count bigint,
+                PRIMARY KEY (address, milestone_index)
+            ) WITH CLUSTERING ORDER BY (milestone_index DESC);
+
+            CREATE TABLE IF NOT EXISTS {0}.ledger_checkpoint (
+                key text,
+                milestone_index int,
+                consuming int,
+                PRIMARY KEY (key)
+            );
+
+            CREATE TABLE IF NOT EXISTS {0}.quarantined_milestones (
+                key text,
+                milestone_index int,
+                payload blob,
+                reason text,
+                PRIMARY KEY (key, milestone_index)
             ) WITH CLUSTERING ORDER BY (milestone_index DESC);",
             keyspace.name()
         );